@@ -33,6 +33,9 @@ import { BottomSheetModalProvider } from "@gorhom/bottom-sheet";
 import BrowserBridgeSessionSync from "./src/features/browserBridge/components/BrowserBridgeSessionSync";
 import BrowserBridgeWriteSync from "./src/features/browserBridge/components/BrowserBridgeWriteSync";
 import BrowserBridgePairingPrompt from "./src/features/browserBridge/components/BrowserBridgePairingPrompt";
+import CliAccessPrompt from "./src/features/cliAccess/components/CliAccessPrompt";
+import SshSignPrompt from "./src/features/sshAgent/components/SshSignPrompt";
+import SecretServiceAccessPrompt from "./src/features/secretService/components/SecretServiceAccessPrompt";
 import ExpiryNotificationScheduler from "./src/features/vault/components/ExpiryNotificationScheduler";
 import { useTheme } from "./src/app/providers/ThemeProvider";
 import ClipboardLifecycleCleanup from "./src/shared/components/ClipboardLifecycleCleanup";
@@ -272,6 +275,9 @@ function AppShell() {
                       <CustomTitlebar />
                       <NavigationContainer />
                       <BrowserBridgePairingPrompt />
+                      <CliAccessPrompt />
+                      <SshSignPrompt />
+                      <SecretServiceAccessPrompt />
                     </View>
                   </View>
                 </BottomSheetModalProvider>
@@ -1,10 +1,16 @@
 import { NativeMessagingClient, NativeMessagingError } from "../native/client";
+import { BRIDGE_CONFIRMATION_TIMEOUT_MS } from "../native/protocol";
 import type {
   BrowserWriteResult,
   CreateEntryFromBrowserPayload,
+  DeleteEntryFromBrowserPayload,
   FillDataResult,
+  GetFillDataForEntryPayload,
   GetStatusResult,
+  MoveEntryToFolderPayload,
+  QueuedBrowserWriteResult,
   SearchEntrySuggestion,
+  SetFavoritePayload,
   UpdateEntryFromBrowserPayload
 } from "../shared/bridge";
 import type { DesktopBridgeStatusView } from "../shared/types";
@@ -35,7 +41,9 @@ function normalizeSuggestions(items: unknown): SearchEntrySuggestion[] {
       email: typeof item.email === "string" ? item.email : undefined,
       hasPassword: Boolean(item.hasPassword),
       hasTotp: Boolean(item.hasTotp),
-      matchedHost: typeof item.matchedHost === "string" ? item.matchedHost : undefined
+      hasCustomFields: Boolean(item.hasCustomFields),
+      matchedHost: typeof item.matchedHost === "string" ? item.matchedHost : undefined,
+      lastUpdated: typeof item.lastUpdated === "string" ? item.lastUpdated : undefined
     }))
     .filter((item) => item.entryId.length > 0);
 }
@@ -46,7 +54,8 @@ function normalizeFillData(result: FillDataResult): FillDataResult {
     title: result.title,
     username: result.username,
     password: result.password,
-    totp: result.totp
+    totp: result.totp,
+    customFields: result.customFields
   };
 }
 
@@ -143,19 +152,46 @@ export class DesktopBridgeService {
     return normalizeSuggestions(result);
   }
 
-  async getDesktopFillData(entryId: string): Promise<FillDataResult> {
+  async getDesktopFillData(
+    entryId: string,
+    origins: Pick<GetFillDataForEntryPayload, "topLevelOrigin" | "frameOrigin"> = {}
+  ): Promise<FillDataResult> {
     const pairingStatus = await getStoredPairingStatus();
-    const result = await this.client.request("getFillDataForEntry", { entryId }, { status: pairingStatus });
+    const result = await this.client.request("getFillDataForEntry", { entryId, ...origins }, { status: pairingStatus });
     return normalizeFillData(result);
   }
 
-  async createEntryFromBrowser(payload: CreateEntryFromBrowserPayload): Promise<BrowserWriteResult> {
+  async createEntryFromBrowser(
+    payload: CreateEntryFromBrowserPayload
+  ): Promise<BrowserWriteResult | QueuedBrowserWriteResult> {
     const pairingStatus = await getStoredPairingStatus();
     return this.client.request("createEntryFromBrowser", payload, { status: pairingStatus });
   }
 
-  async updateEntryFromBrowser(payload: UpdateEntryFromBrowserPayload): Promise<BrowserWriteResult> {
+  async updateEntryFromBrowser(
+    payload: UpdateEntryFromBrowserPayload
+  ): Promise<BrowserWriteResult | QueuedBrowserWriteResult> {
     const pairingStatus = await getStoredPairingStatus();
     return this.client.request("updateEntryFromBrowser", payload, { status: pairingStatus });
   }
+
+  async deleteEntryFromBrowser(payload: DeleteEntryFromBrowserPayload): Promise<BrowserWriteResult> {
+    const pairingStatus = await getStoredPairingStatus();
+    return this.client.request(
+      "deleteEntryFromBrowser",
+      payload,
+      { status: pairingStatus },
+      BRIDGE_CONFIRMATION_TIMEOUT_MS
+    );
+  }
+
+  async moveEntryToFolder(payload: MoveEntryToFolderPayload): Promise<BrowserWriteResult> {
+    const pairingStatus = await getStoredPairingStatus();
+    return this.client.request("moveEntryToFolder", payload, { status: pairingStatus });
+  }
+
+  async setFavorite(payload: SetFavoritePayload): Promise<BrowserWriteResult> {
+    const pairingStatus = await getStoredPairingStatus();
+    return this.client.request("setFavorite", payload, { status: pairingStatus });
+  }
 }
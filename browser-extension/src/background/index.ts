@@ -1,11 +1,18 @@
 import { BackgroundMessageRouter } from "./router";
 import { DesktopBridgeService } from "./bridge";
+import { NativeMessagingError } from "../native/client";
 import { ExtensionState } from "./state";
 import { getActiveDomainContext } from "./tab-context";
 import { isExtensionMessage } from "../shared/messages";
 import type { ContentMessage } from "../shared/content-messages";
 import type { FillDataResult, SearchEntrySuggestion } from "../shared/bridge";
-import type { BrowserWriteResult, CreateEntryFromBrowserPayload, UpdateEntryFromBrowserPayload } from "../shared/bridge";
+import type {
+  BrowserWriteResult,
+  CreateEntryFromBrowserPayload,
+  EntryConflictResult,
+  QueuedBrowserWriteResult,
+  UpdateEntryFromBrowserPayload
+} from "../shared/bridge";
 import type { ContentDebugResponse, FillExecutionResult, OpenDesktopAppPayload, PromptResolutionResult, SavePromptCandidate } from "../shared/types";
 import { getNormalizedDomainFromUrl } from "../shared/domain";
 
@@ -48,13 +55,17 @@ async function injectContentScriptIntoOpenTabs(): Promise<void> {
   );
 }
 
-async function getActiveTabId(): Promise<number | undefined> {
-  const [activeTab] = await chrome.tabs.query({
-    active: true,
-    lastFocusedWindow: true
-  });
+function originOf(url: string | undefined): string | undefined {
+  if (!url) {
+    return undefined;
+  }
 
-  return typeof activeTab?.id === "number" ? activeTab.id : undefined;
+  try {
+    const origin = new URL(url).origin;
+    return origin === "null" ? undefined : origin;
+  } catch {
+    return undefined;
+  }
 }
 
 async function getActiveTab() {
@@ -113,7 +124,8 @@ async function openDesktopApp(payload?: OpenDesktopAppPayload): Promise<{ succes
 }
 
 async function prepareFillForActiveTab(entryId: string): Promise<{ tabId?: number; fillData?: FillDataResult; result: import("../shared/types").PrepareFillForActiveTabResult }> {
-  const tabId = await getActiveTabId();
+  const activeTab = await getActiveTab();
+  const tabId = typeof activeTab?.id === "number" ? activeTab.id : undefined;
 
   if (typeof tabId !== "number") {
     return {
@@ -125,7 +137,9 @@ async function prepareFillForActiveTab(entryId: string): Promise<{ tabId?: numbe
   }
 
   try {
-    const fillData = await desktopBridge.getDesktopFillData(entryId);
+    const fillData = await desktopBridge.getDesktopFillData(entryId, {
+      topLevelOrigin: originOf(activeTab?.url)
+    });
     const record = state.setPreparedFill(tabId, fillData);
 
     return {
@@ -365,10 +379,17 @@ function buildUpdatePayload(prompt: import("../shared/types").SavePromptDecision
     username: prompt.candidate.username,
     password: prompt.candidate.password,
     url: prompt.candidate.url,
-    matchedHost: prompt.matchedHostname
+    matchedHost: prompt.matchedHostname,
+    lastUpdated: prompt.existingEntryLastUpdated
   };
 }
 
+function isQueuedWrite(
+  result: BrowserWriteResult | QueuedBrowserWriteResult
+): result is QueuedBrowserWriteResult {
+  return "queued" in result && result.queued === true;
+}
+
 function toAppliedResult(
   prompt: import("../shared/types").SavePromptDecision,
   result: BrowserWriteResult
@@ -382,6 +403,18 @@ function toAppliedResult(
   } as const;
 }
 
+function toEntryConflict(
+  prompt: import("../shared/types").SavePromptDecision,
+  result: unknown
+): EntryConflictResult {
+  const current = result && typeof result === "object" ? (result as Record<string, unknown>) : {};
+  return {
+    entryId: typeof current.entryId === "string" ? current.entryId : prompt.existingEntryId ?? "",
+    title: typeof current.title === "string" ? current.title : prompt.existingEntryTitle ?? prompt.suggestedTitle,
+    lastUpdated: typeof current.lastUpdated === "string" ? current.lastUpdated : undefined
+  };
+}
+
 async function resolvePromptWithDesktopWrite(
   promptId: string,
   decision: import("../shared/types").SavePromptResolution
@@ -406,6 +439,9 @@ async function resolvePromptWithDesktopWrite(
       }
 
       const result = await desktopBridge.createEntryFromBrowser(payload);
+      if (isQueuedWrite(result)) {
+        return state.buildPromptQueuedResult(prompt);
+      }
       return state.buildPromptAppliedResult(prompt, toAppliedResult(prompt, result));
     }
 
@@ -416,6 +452,9 @@ async function resolvePromptWithDesktopWrite(
       }
 
       const result = await desktopBridge.updateEntryFromBrowser(payload);
+      if (isQueuedWrite(result)) {
+        return state.buildPromptQueuedResult(prompt);
+      }
       return state.buildPromptAppliedResult(prompt, toAppliedResult(prompt, result));
     }
 
@@ -423,6 +462,10 @@ async function resolvePromptWithDesktopWrite(
       message: "The selected prompt action did not match the current save suggestion."
     };
   } catch (error) {
+    if (error instanceof NativeMessagingError && error.bridgeError.code === "ENTRY_CONFLICT") {
+      return state.buildPromptConflictResult(prompt, toEntryConflict(prompt, error.result));
+    }
+
     return {
       message:
         error instanceof Error
@@ -476,7 +519,11 @@ const router = new BackgroundMessageRouter({
       state.clearPreparedFill(prepared.tabId);
     }
   },
-  "bridge:getFillDataForEntry": async (payload) => desktopBridge.getDesktopFillData(payload.entryId),
+  "bridge:getFillDataForEntry": async (payload, { sender }) =>
+    desktopBridge.getDesktopFillData(payload.entryId, {
+      topLevelOrigin: originOf(sender.tab?.url),
+      frameOrigin: sender.origin ?? originOf(sender.url)
+    }),
   "bridge:getContentDebug": async () => {
     const activeTab = await getActiveTab();
     const tabId = typeof activeTab?.id === "number" ? activeTab.id : undefined;
@@ -6,7 +6,7 @@ import type {
   SavePromptDecision,
   SavePromptResolution
 } from "../shared/types";
-import type { SearchEntrySuggestion } from "../shared/bridge";
+import type { EntryConflictResult, SearchEntrySuggestion } from "../shared/bridge";
 import { getNormalizedDomainFromUrl } from "../shared/domain";
 
 interface PreparedFillRecord {
@@ -141,6 +141,7 @@ export class ExtensionState {
         suggestedTitle: matchingSuggestion.title,
         existingEntryId: matchingSuggestion.entryId,
         existingEntryTitle: matchingSuggestion.title,
+        existingEntryLastUpdated: matchingSuggestion.lastUpdated,
         matchedHostname: normalizedDomain,
         createdAt: Date.now()
       };
@@ -202,6 +203,35 @@ export class ExtensionState {
     return prompt;
   }
 
+  buildPromptQueuedResult(prompt: SavePromptDecision): PromptResolutionResult {
+    const title = prompt.existingEntryTitle ?? prompt.suggestedTitle;
+    return {
+      message: `ClavisPass is locked. ${title} will be saved after the next unlock.`
+    };
+  }
+
+  /**
+   * Puts an update back up for confirmation after the desktop app rejected it
+   * with ENTRY_CONFLICT. The new prompt carries the entry's current
+   * `lastUpdated`, so confirming it again overwrites the newer version.
+   */
+  buildPromptConflictResult(prompt: SavePromptDecision, conflict: EntryConflictResult): PromptResolutionResult {
+    const title = conflict.title || prompt.existingEntryTitle || prompt.suggestedTitle;
+    const nextPrompt: SavePromptDecision = {
+      ...prompt,
+      id: crypto.randomUUID(),
+      existingEntryTitle: title,
+      existingEntryLastUpdated: conflict.lastUpdated,
+      conflict
+    };
+
+    this.pendingPrompt = nextPrompt;
+    return {
+      prompt: nextPrompt,
+      message: `${title} was changed in ClavisPass after this login was captured. Update again to overwrite it.`
+    };
+  }
+
   buildPromptAppliedResult(
     prompt: SavePromptDecision,
     applied: PromptResolutionAppliedResult
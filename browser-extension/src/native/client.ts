@@ -13,7 +13,11 @@ import {
 import type { BridgeError } from "../shared/bridge";
 
 export class NativeMessagingError extends Error {
-  constructor(public readonly bridgeError: BridgeError) {
+  /** `result` carries details the desktop app sent along with the error, e.g. for ENTRY_CONFLICT. */
+  constructor(
+    public readonly bridgeError: BridgeError,
+    public readonly result?: unknown
+  ) {
     super(bridgeError.message);
     this.name = "NativeMessagingError";
   }
@@ -102,7 +106,8 @@ export class NativeMessagingClient {
   async request<T extends BridgeCommand>(
     command: T,
     payload: BridgeRequestPayloadMap[T],
-    pairing?: BridgePairingContext
+    pairing?: BridgePairingContext,
+    timeoutMs: number = this.timeoutMs
   ): Promise<BridgeResultMap[T]> {
     const client = await getBridgeClientInfo();
     const requestId = `${command}:${Date.now()}:${crypto.randomUUID()}`;
@@ -119,7 +124,7 @@ export class NativeMessagingClient {
     try {
       const rawResponse = await withTimeout(
         chrome.runtime.sendNativeMessage(this.hostName, request),
-        this.timeoutMs
+        timeoutMs
       );
 
       const response = validateBridgeResponse(command, requestId, rawResponse);
@@ -128,7 +133,8 @@ export class NativeMessagingClient {
           response.error ?? {
             code: "desktop_error",
             message: "Desktop app rejected the request."
-          }
+          },
+          response.result
         );
       }
 
@@ -141,6 +147,9 @@ export class NativeMessagingClient {
 
       return response.result;
     } catch (error) {
+      if (error instanceof NativeMessagingError) {
+        throw error;
+      }
       throw new NativeMessagingError(toBridgeError(error));
     }
   }
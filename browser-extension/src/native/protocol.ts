@@ -3,3 +3,5 @@ export { type BridgeCommand, type BridgePairingContext, type BridgeRequest, type
 export const NATIVE_HOST_NAME = "com.clavispass.native_host";
 export const BRIDGE_PROTOCOL_VERSION = 1;
 export const BRIDGE_TIMEOUT_MS = 4000;
+/** Requests that wait for the user to confirm in the desktop app. */
+export const BRIDGE_CONFIRMATION_TIMEOUT_MS = 65_000;
@@ -66,6 +66,7 @@ const translations = {
     saveEntryPrompt: "Save {{title}}?",
     updateEntryPrompt: "Update {{title}}?",
     noUsernameCaptured: "No username captured from this login form.",
+    entryConflict: "This entry was changed in ClavisPass after the login was captured. Updating again overwrites those changes.",
     username: "Username",
     url: "URL",
     working: "Working...",
@@ -140,6 +141,7 @@ const translations = {
     saveEntryPrompt: "{{title}} speichern?",
     updateEntryPrompt: "{{title}} aktualisieren?",
     noUsernameCaptured: "Aus diesem Login-Formular wurde kein Benutzername erkannt.",
+    entryConflict: "Dieser Eintrag wurde in ClavisPass geändert, nachdem der Login erkannt wurde. Erneutes Aktualisieren überschreibt diese Änderungen.",
     username: "Benutzername",
     url: "URL",
     working: "Arbeite...",
@@ -330,6 +332,7 @@ export function App() {
   const [isLoadingSuggestions, setIsLoadingSuggestions] = useState(false);
   const [isFilling, setIsFilling] = useState(false);
   const [isResolvingPrompt, setIsResolvingPrompt] = useState(false);
+  const [promptError, setPromptError] = useState<string>();
   const [isOpeningDesktopApp, setIsOpeningDesktopApp] = useState(false);
   const [error, setError] = useState<string>();
   const [searchError, setSearchError] = useState<string>();
@@ -451,6 +454,7 @@ export function App() {
         decision
       });
       setPendingPrompt(result.prompt);
+      setPromptError(decision === "dismiss" || result.applied || result.prompt?.conflict ? undefined : result.message);
       if (result.applied) {
         await refreshStatus({ reloadSuggestions: true });
       }
@@ -591,6 +595,7 @@ export function App() {
           </div>
           <p className="subtle">{pendingPrompt.candidate.username ? `${t("username")}: ${pendingPrompt.candidate.username}` : t("noUsernameCaptured")}</p>
           <p className="subtle">{t("url")}: {pendingPrompt.candidate.url}</p>
+          {pendingPrompt.conflict ? <p className="error-inline">{t("entryConflict")}</p> : null}
           <div className="suggestion-actions">
             <button className="row-button row-button-primary" type="button" disabled={isResolvingPrompt} onClick={() => void handlePromptResolution(pendingPrompt.kind === "create" ? "save" : "update")}>
               {isResolvingPrompt ? t("working") : pendingPrompt.kind === "create" ? t("saveEntry") : t("updateEntry")}
@@ -602,6 +607,12 @@ export function App() {
         </section>
       ) : null}
 
+      {!pendingPrompt && promptError ? (
+        <section className="prompt-card">
+          <p className="error-inline">{promptError}</p>
+        </section>
+      ) : null}
+
       {status.state === "ready" ? (
         <section className="suggestions-card">
           <div className="section-header-row">
@@ -13,6 +13,7 @@ export interface BridgeError {
     | "timeout"
     | "protocol_error"
     | "desktop_error"
+    | "ENTRY_CONFLICT"
     | "unknown";
   message: string;
   details?: string;
@@ -38,6 +39,11 @@ export interface SearchEntriesByDomainPayload {
   domain: string;
 }
 
+export interface SearchEntriesPayload {
+  query: string;
+  limit?: number;
+}
+
 export interface SearchEntrySuggestion {
   entryId: string;
   title: string;
@@ -47,11 +53,15 @@ export interface SearchEntrySuggestion {
   email?: string;
   hasPassword: boolean;
   hasTotp: boolean;
+  hasCustomFields?: boolean;
   matchedHost?: string;
+  lastUpdated?: string;
 }
 
 export interface GetFillDataForEntryPayload {
   entryId: string;
+  topLevelOrigin?: string;
+  frameOrigin?: string;
 }
 
 export interface FillDataResult {
@@ -60,6 +70,105 @@ export interface FillDataResult {
   username?: string;
   password: string;
   totp?: string;
+  customFields?: CustomFillField[];
+}
+
+export interface CustomFillField {
+  title: string;
+  value: string;
+  fieldName?: string;
+  inputType?: "text" | "secret" | "number" | "date" | string;
+}
+
+export interface GetHttpAuthCredentialsPayload {
+  host: string;
+  port?: number;
+  scheme: "basic" | "digest" | string;
+  realm?: string;
+}
+
+export interface HttpAuthCredential {
+  entryId: string;
+  title: string;
+  username?: string;
+  password: string;
+  matchedHost: string;
+  realm?: string;
+}
+
+export interface SearchCardsPayload {
+  query?: string;
+  limit?: number;
+}
+
+export interface CardSuggestion {
+  entryId: string;
+  moduleId?: string | null;
+  title: string;
+  fav: boolean;
+  holder?: string;
+  brand?: string;
+  lastFour?: string;
+  expiryMonth?: string;
+  expiryYear?: string;
+}
+
+export interface GetCardFillDataPayload {
+  entryId: string;
+  moduleId?: string;
+}
+
+export interface CardFillData {
+  entryId: string;
+  moduleId?: string | null;
+  title: string;
+  number: string;
+  holder?: string;
+  expiryMonth?: string;
+  expiryYear?: string;
+  cvc?: string;
+  brand?: string;
+  luhnValid: boolean;
+}
+
+export interface SearchIdentitiesPayload {
+  query?: string;
+  limit?: number;
+}
+
+export interface IdentitySuggestion {
+  entryId: string;
+  title: string;
+  fav: boolean;
+  fullName?: string;
+  email?: string;
+  city?: string;
+}
+
+export interface GetIdentityFillDataPayload {
+  entryId: string;
+}
+
+export interface IdentityFillData {
+  entryId: string;
+  title: string;
+  honorific?: string;
+  firstName?: string;
+  middleName?: string;
+  lastName?: string;
+  fullName?: string;
+  username?: string;
+  email?: string;
+  phone?: string;
+  organization?: string;
+  department?: string;
+  jobTitle?: string;
+  addressLine1?: string;
+  addressLine2?: string;
+  postalCode?: string;
+  city?: string;
+  state?: string;
+  country?: string;
 }
 
 export interface CreateEntryFromBrowserPayload {
@@ -71,6 +180,7 @@ export interface CreateEntryFromBrowserPayload {
   email?: string;
   folderId?: string;
   totp?: string;
+  force?: boolean;
 }
 
 export interface UpdateEntryFromBrowserPayload {
@@ -82,6 +192,47 @@ export interface UpdateEntryFromBrowserPayload {
   matchedHost: string;
   email?: string;
   totp?: string;
+  /** `lastUpdated` of the entry when it was read; rejected with ENTRY_CONFLICT if stale. */
+  lastUpdated?: string;
+}
+
+export interface DeleteEntryFromBrowserPayload {
+  entryId: string;
+  lastUpdated?: string;
+}
+
+export interface MoveEntryToFolderPayload {
+  entryId: string;
+  /** Omit to move the entry out of any folder. */
+  folderId?: string;
+  lastUpdated?: string;
+}
+
+export interface SetFavoritePayload {
+  entryId: string;
+  fav: boolean;
+}
+
+export interface DuplicateCandidateResult {
+  candidates: SearchEntrySuggestion[];
+}
+
+/** Returned instead of a BrowserWriteResult when the desktop app is locked. */
+export interface QueuedBrowserWriteResult {
+  queued: true;
+  requestId: string;
+}
+
+export interface EntryConflictResult {
+  entryId: string;
+  title: string;
+  lastUpdated?: string;
+}
+
+export interface PasswordHistoryReport {
+  recorded: boolean;
+  replacedAtMs?: number;
+  retained: number;
 }
 
 export interface BrowserWriteResult {
@@ -89,14 +240,24 @@ export interface BrowserWriteResult {
   title?: string;
   createdAt?: string;
   updatedAt?: string;
+  passwordHistory?: PasswordHistoryReport;
 }
 
 export type BridgeCommand =
   | "getStatus"
   | "searchEntriesByDomain"
+  | "searchEntries"
   | "getFillDataForEntry"
+  | "getHttpAuthCredentials"
+  | "searchCards"
+  | "getCardFillData"
+  | "searchIdentities"
+  | "getIdentityFillData"
   | "createEntryFromBrowser"
-  | "updateEntryFromBrowser";
+  | "updateEntryFromBrowser"
+  | "deleteEntryFromBrowser"
+  | "moveEntryToFolder"
+  | "setFavorite";
 
 export interface BridgePairingContext {
   status: PairingStatus;
@@ -105,17 +266,35 @@ export interface BridgePairingContext {
 export interface BridgeRequestPayloadMap {
   getStatus: undefined;
   searchEntriesByDomain: SearchEntriesByDomainPayload;
+  searchEntries: SearchEntriesPayload;
   getFillDataForEntry: GetFillDataForEntryPayload;
+  getHttpAuthCredentials: GetHttpAuthCredentialsPayload;
+  searchCards: SearchCardsPayload;
+  getCardFillData: GetCardFillDataPayload;
+  searchIdentities: SearchIdentitiesPayload;
+  getIdentityFillData: GetIdentityFillDataPayload;
   createEntryFromBrowser: CreateEntryFromBrowserPayload;
   updateEntryFromBrowser: UpdateEntryFromBrowserPayload;
+  deleteEntryFromBrowser: DeleteEntryFromBrowserPayload;
+  moveEntryToFolder: MoveEntryToFolderPayload;
+  setFavorite: SetFavoritePayload;
 }
 
 export interface BridgeResultMap {
   getStatus: GetStatusResult;
   searchEntriesByDomain: SearchEntrySuggestion[];
+  searchEntries: SearchEntrySuggestion[];
   getFillDataForEntry: FillDataResult;
-  createEntryFromBrowser: BrowserWriteResult;
-  updateEntryFromBrowser: BrowserWriteResult;
+  getHttpAuthCredentials: HttpAuthCredential[];
+  searchCards: CardSuggestion[];
+  getCardFillData: CardFillData;
+  searchIdentities: IdentitySuggestion[];
+  getIdentityFillData: IdentityFillData;
+  createEntryFromBrowser: BrowserWriteResult | QueuedBrowserWriteResult;
+  updateEntryFromBrowser: BrowserWriteResult | QueuedBrowserWriteResult;
+  deleteEntryFromBrowser: BrowserWriteResult;
+  moveEntryToFolder: BrowserWriteResult;
+  setFavorite: BrowserWriteResult;
 }
 
 export interface BridgeRequest<T extends BridgeCommand = BridgeCommand> {
@@ -167,8 +167,11 @@ export interface SavePromptDecision {
   suggestedTitle: string;
   existingEntryId?: string;
   existingEntryTitle?: string;
+  existingEntryLastUpdated?: string;
   matchedHostname?: string;
   createdAt: number;
+  /** Set when the desktop app rejected the update because the entry changed; confirming again overwrites it. */
+  conflict?: import("./bridge").EntryConflictResult;
 }
 
 export interface PromptResolutionAppliedResult {
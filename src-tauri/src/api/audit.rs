@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    sync::Mutex,
+};
+
+use crate::bridge::path::local_api_audit_log_path;
+
+/// Once the log grows past this size, the older half of it is dropped.
+const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// One call to the local API. Only the method, path and outcome are kept,
+/// never request or response bodies.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    pub at_ms: u64,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_id: Option<String>,
+}
+
+pub fn record(entry: &AuditRecord) -> io::Result<()> {
+    let mut line =
+        serde_json::to_string(entry).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    line.push('\n');
+
+    let _guard = LOG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let path = local_api_audit_log_path()?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?
+        .write_all(line.as_bytes())?;
+
+    if fs::metadata(&path)?.len() > MAX_LOG_BYTES {
+        let content = fs::read(&path)?;
+        let keep_from = content.len() - MAX_LOG_BYTES as usize / 2;
+        let kept = match content[keep_from..].iter().position(|&byte| byte == b'\n') {
+            Some(index) => &content[keep_from + index + 1..],
+            None => &[],
+        };
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, kept)?;
+        fs::rename(temp, &path)?;
+    }
+    Ok(())
+}
+
+/// The newest `limit` records, newest first. Lines that do not parse are
+/// skipped.
+pub fn recent(limit: usize) -> io::Result<Vec<AuditRecord>> {
+    let _guard = LOG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let path = local_api_audit_log_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<AuditRecord>(line).ok())
+        .take(limit)
+        .collect())
+}
@@ -0,0 +1,244 @@
+//! Just enough HTTP/1.1 for the local API: one request per connection,
+//! bodies sized by `Content-Length`, JSON responses.
+
+use serde_json::Value;
+use std::{
+    fmt,
+    io::{self, BufRead, Read, Write},
+};
+
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    /// Percent-decoded path without the query.
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    /// Header names are lowercased.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn bearer_token(&self) -> Option<&str> {
+        let value = self.header("authorization")?.trim();
+        let (scheme, token) = value.split_once(' ')?;
+        scheme
+            .eq_ignore_ascii_case("bearer")
+            .then(|| token.trim())
+            .filter(|token| !token.is_empty())
+    }
+}
+
+/// Inner error of a request whose body exceeds the size limit. Everything else
+/// `read_request` rejects is malformed.
+#[derive(Debug)]
+struct BodyTooLarge;
+
+impl fmt::Display for BodyTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Request body is too large")
+    }
+}
+
+impl std::error::Error for BodyTooLarge {}
+
+/// Tells a request rejected for its size (413) from a malformed one (400).
+pub fn is_body_too_large(error: &io::Error) -> bool {
+    error
+        .get_ref()
+        .is_some_and(|inner| inner.is::<BodyTooLarge>())
+}
+
+pub fn read_request<R: BufRead>(reader: &mut R) -> io::Result<HttpRequest> {
+    let mut head_bytes = 0usize;
+    let request_line = read_line(reader, &mut head_bytes)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid("Malformed request line"));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(invalid("Unsupported HTTP version"));
+    }
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader, &mut head_bytes)?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| invalid("Malformed header"))?;
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+    }
+
+    let mut request = HttpRequest {
+        method: method.to_ascii_uppercase(),
+        headers,
+        ..HttpRequest::default()
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    request.path = percent_decode(path, false);
+    request.query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key, true), percent_decode(value, true))
+        })
+        .collect();
+
+    let length = match request.header("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| invalid("Invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > MAX_BODY_BYTES {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, BodyTooLarge));
+    }
+    request.body = vec![0u8; length];
+    reader.read_exact(&mut request.body)?;
+    Ok(request)
+}
+
+pub fn write_response<W: Write>(writer: &mut W, status: u16, body: &Value) -> io::Result<()> {
+    let body = serde_json::to_vec(body).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    write!(
+        writer,
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        reason_phrase(status),
+        body.len()
+    )?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+fn read_line<R: BufRead>(reader: &mut R, head_bytes: &mut usize) -> io::Result<String> {
+    let mut line = Vec::new();
+    let limit = (MAX_HEAD_BYTES - *head_bytes) as u64 + 1;
+    reader.by_ref().take(limit).read_until(b'\n', &mut line)?;
+    *head_bytes += line.len();
+    if *head_bytes > MAX_HEAD_BYTES {
+        return Err(invalid("Request head is too large"));
+    }
+    if !line.ends_with(b"\n") {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "Connection closed mid-request",
+        ));
+    }
+
+    let line = String::from_utf8(line).map_err(|_| invalid("Request head is not UTF-8"))?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn percent_decode(value: &str, plus_as_space: bool) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' if index + 2 < bytes.len() => {
+                let hex = |byte: u8| (byte as char).to_digit(16);
+                match (hex(bytes[index + 1]), hex(bytes[index + 2])) {
+                    (Some(high), Some(low)) => {
+                        decoded.push((high * 16 + low) as u8);
+                        index += 3;
+                        continue;
+                    }
+                    _ => decoded.push(b'%'),
+                }
+            }
+            b'+' if plus_as_space => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        423 => "Locked",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_body_too_large, percent_decode, read_request, write_response};
+    use serde_json::json;
+
+    #[test]
+    fn parses_request_line_headers_query_and_body() {
+        let raw = b"POST /v1/entries/a%20b?query=mail+box&limit=5 HTTP/1.1\r\nHost: 127.0.0.1:17380\r\nAuthorization: Bearer cpat_abc\r\nContent-Length: 2\r\n\r\n{}";
+        let request = read_request(&mut &raw[..]).unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/entries/a b");
+        assert_eq!(request.query_param("query"), Some("mail box"));
+        assert_eq!(request.query_param("limit"), Some("5"));
+        assert_eq!(request.header("HOST"), Some("127.0.0.1:17380"));
+        assert_eq!(request.bearer_token(), Some("cpat_abc"));
+        assert_eq!(request.body, b"{}");
+
+        assert!(read_request(&mut &b"GET / HTTP/1.1\r\nHost: x\r\n"[..]).is_err());
+        let too_large =
+            read_request(&mut &b"GET / HTTP/1.1\r\nContent-Length: 99999999\r\n\r\n"[..])
+                .unwrap_err();
+        assert!(is_body_too_large(&too_large));
+        for malformed in [
+            &b"GET /\r\n\r\n"[..],
+            &b"GET / HTTP/1.1\r\nNo colon here\r\n\r\n"[..],
+            &b"GET / HTTP/1.1\r\nContent-Length: many\r\n\r\n"[..],
+        ] {
+            let error = read_request(&mut &malformed[..]).unwrap_err();
+            assert!(!is_body_too_large(&error));
+        }
+        assert_eq!(percent_decode("%zz%4", false), "%zz%4");
+    }
+
+    #[test]
+    fn writes_json_responses() {
+        let mut output = Vec::new();
+        write_response(&mut output, 423, &json!({ "ok": false })).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.starts_with("HTTP/1.1 423 Locked\r\n"));
+        assert!(output.contains("Content-Length: 12\r\n"));
+        assert!(output.ends_with("\r\n\r\n{\"ok\":false}"));
+    }
+}
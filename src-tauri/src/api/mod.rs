@@ -0,0 +1,10 @@
+//! Opt-in HTTP API on 127.0.0.1 for local automation. Calls authenticate
+//! with bearer tokens issued in the app, each limited to folders and to
+//! reading or also writing, and answer from the published bridge session.
+//! Every call is appended to an audit log.
+
+pub mod audit;
+pub mod http;
+pub mod server;
+pub mod settings;
+pub mod tokens;
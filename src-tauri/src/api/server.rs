@@ -0,0 +1,541 @@
+use serde_json::{json, Value};
+use std::{
+    io::{self, BufReader},
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::{
+    audit::{self, AuditRecord},
+    http::{is_body_too_large, read_request, write_response, HttpRequest},
+    tokens::{self, ApiToken},
+};
+use crate::bridge::{
+    protocol::{BridgeClientInfo, BridgeError},
+    session,
+    vault::{self, VaultData},
+    write::{self, BrowserWriteResult, CreateEntryFromBrowserPayload},
+};
+
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_LIMIT: usize = 20;
+
+/// The HTTP listener on 127.0.0.1. Dropping it stops accepting connections.
+pub struct LocalApiServer {
+    address: SocketAddr,
+    stopped: Arc<AtomicBool>,
+}
+
+impl LocalApiServer {
+    /// Binds `port` on the loopback interface; `0` picks a free port.
+    pub fn start(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        let address = listener.local_addr()?;
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let accepting = stopped.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if accepting.load(Ordering::SeqCst) {
+                    break;
+                }
+                match stream {
+                    Ok(stream) => {
+                        thread::spawn(move || {
+                            if let Err(error) = serve_client(stream, address.port()) {
+                                eprintln!("Local API client failed: {error}");
+                            }
+                        });
+                    }
+                    Err(error) => eprintln!("Failed to accept local API client: {error}"),
+                }
+            }
+        });
+
+        Ok(Self { address, stopped })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.address.port()
+    }
+}
+
+impl Drop for LocalApiServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wakes the accept loop so it sees the flag and drops the listener.
+        let _ = TcpStream::connect(self.address);
+    }
+}
+
+struct ApiReply {
+    status: u16,
+    body: Value,
+    entry_id: Option<String>,
+}
+
+impl ApiReply {
+    fn ok(status: u16, body: impl serde::Serialize) -> Self {
+        Self {
+            status,
+            body: serde_json::to_value(body).unwrap_or(Value::Null),
+            entry_id: None,
+        }
+    }
+
+    fn error(status: u16, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({
+                "error": BridgeError {
+                    code: code.to_string(),
+                    message: message.into(),
+                }
+            }),
+            entry_id: None,
+        }
+    }
+
+    fn for_entry(mut self, entry_id: &str) -> Self {
+        self.entry_id = Some(entry_id.to_string());
+        self
+    }
+}
+
+fn serve_client(stream: TcpStream, port: u16) -> io::Result<()> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    let (request, (reply, token)) = match read_request(&mut reader) {
+        Ok(request) => {
+            let response = respond(&request, port);
+            (request, response)
+        }
+        Err(error) if is_body_too_large(&error) => (
+            HttpRequest::default(),
+            (
+                ApiReply::error(413, "PAYLOAD_TOO_LARGE", error.to_string()),
+                None,
+            ),
+        ),
+        Err(error) => (
+            HttpRequest::default(),
+            (ApiReply::error(400, "BAD_REQUEST", error.to_string()), None),
+        ),
+    };
+
+    let record = AuditRecord {
+        at_ms: now_ms(),
+        method: request.method,
+        path: request.path,
+        status: reply.status,
+        token_id: token.as_ref().map(|token| token.id.clone()),
+        token_name: token.map(|token| token.name),
+        entry_id: reply.entry_id.clone(),
+    };
+    if let Err(error) = audit::record(&record) {
+        eprintln!("Failed to record local API call: {error}");
+    }
+    write_response(&mut writer, reply.status, &reply.body)
+}
+
+/// Checks where the request comes from and who sends it, then dispatches it
+/// against the unlocked session.
+fn respond(request: &HttpRequest, port: u16) -> (ApiReply, Option<ApiToken>) {
+    // Browsers always send `Origin` on cross-site requests, and a `Host` other
+    // than the loopback address means a rebound DNS name.
+    let host_allowed = request.header("host").is_some_and(|host| {
+        host == format!("127.0.0.1:{port}") || host == format!("localhost:{port}")
+    });
+    if !host_allowed || request.header("origin").is_some() {
+        let reply = ApiReply::error(
+            403,
+            "FORBIDDEN_ORIGIN",
+            "The local API only accepts requests from local programs.",
+        );
+        return (reply, None);
+    }
+
+    let token = match request.bearer_token().map(tokens::authenticate) {
+        Some(Ok(Some(token))) => token,
+        Some(Err(error)) => {
+            let reply = ApiReply::error(
+                500,
+                "TOKEN_STORE_ERROR",
+                format!("Failed to check the token: {error}"),
+            );
+            return (reply, None);
+        }
+        _ => {
+            let reply = ApiReply::error(401, "UNAUTHORIZED", "A valid bearer token is required.");
+            return (reply, None);
+        }
+    };
+
+    let reply = match session::load_session() {
+        Ok(Some(snapshot)) => dispatch(request, &token, &snapshot.vault),
+        Ok(None) => ApiReply::error(423, "APP_LOCKED", "The vault is locked."),
+        Err(error) => ApiReply::error(
+            500,
+            "SESSION_STORE_ERROR",
+            format!("Failed to load bridge session: {error}"),
+        ),
+    };
+    (reply, Some(token))
+}
+
+fn dispatch(request: &HttpRequest, token: &ApiToken, vault: &VaultData) -> ApiReply {
+    let segments = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["v1", "status"]) => ApiReply::ok(
+            200,
+            json!({
+                "ready": true,
+                "token": {
+                    "name": token.name,
+                    "folderIds": token.folder_ids,
+                    "write": token.write,
+                },
+            }),
+        ),
+        ("GET", ["v1", "entries"]) => {
+            let limit = match request.query_param("limit").map(str::parse::<usize>) {
+                Some(Ok(limit)) => Some(limit),
+                Some(Err(_)) => {
+                    return ApiReply::error(400, "INVALID_QUERY", "limit must be a number.")
+                }
+                None => None,
+            };
+            let entries = match request.query_param("query") {
+                Some(query) => {
+                    // Search past the limit so entries outside the scope do
+                    // not crowd out visible ones.
+                    let mut entries = vault::search_entries(vault, query, Some(usize::MAX));
+                    entries.retain(|entry| token.allows_folder(entry.folder_id.as_deref()));
+                    entries.truncate(limit.unwrap_or(DEFAULT_LIMIT));
+                    entries
+                }
+                None => {
+                    let mut entries = vault::list_entries(vault);
+                    entries.retain(|entry| token.allows_folder(entry.folder_id.as_deref()));
+                    if let Some(limit) = limit {
+                        entries.truncate(limit);
+                    }
+                    entries
+                }
+            };
+            ApiReply::ok(200, entries)
+        }
+        ("GET", ["v1", "entries", entry_id]) => {
+            let visible = vault
+                .values
+                .iter()
+                .find(|entry| entry.id == *entry_id)
+                .is_some_and(|entry| token.allows_entry(entry));
+            match vault::fill_data_for_entry(vault, entry_id).filter(|_| visible) {
+                Some(entry) => ApiReply::ok(200, entry),
+                None => ApiReply::error(404, "ENTRY_NOT_FOUND", "Entry could not be resolved."),
+            }
+            .for_entry(entry_id)
+        }
+        ("POST", ["v1", "entries"]) => create_entry(request, token),
+        (_, ["v1", "status"]) | (_, ["v1", "entries", ..]) => {
+            ApiReply::error(405, "METHOD_NOT_ALLOWED", "Method not allowed.")
+        }
+        _ => ApiReply::error(404, "NOT_FOUND", "Unknown endpoint."),
+    }
+}
+
+/// Queues the entry like a browser save, so the app applies it to the real
+/// vault and answers once it did.
+fn create_entry(request: &HttpRequest, token: &ApiToken) -> ApiReply {
+    if !token.write {
+        return ApiReply::error(403, "READ_ONLY_TOKEN", "The token may not create entries.");
+    }
+    let mut payload = match serde_json::from_slice::<CreateEntryFromBrowserPayload>(&request.body) {
+        Ok(payload) => payload,
+        Err(error) => {
+            return ApiReply::error(
+                400,
+                "INVALID_PAYLOAD",
+                format!("Entry payload is invalid: {error}"),
+            )
+        }
+    };
+    // API clients never skip the duplicate check; only the user can.
+    payload.force = false;
+    if !token.allows_folder(payload.folder_id.as_deref()) {
+        return ApiReply::error(
+            403,
+            "FOLDER_NOT_ALLOWED",
+            "The token may not create entries in this folder.",
+        );
+    }
+
+    let client = BridgeClientInfo {
+        extension_id: "local-api".to_string(),
+        name: Some(token.name.clone()),
+        version: None,
+        instance_id: Some(token.id.clone()),
+    };
+    let id = format!("local-api-{}", now_ms());
+    let mut queued = write::queue_create_request(id.clone(), client.clone(), payload.clone());
+    // Duplicates in folders the token cannot see neither block the create nor
+    // show up in the reply.
+    if let Ok(result) = &queued {
+        if visible_duplicates(result, token).is_some_and(|visible| visible.is_empty()) {
+            payload.force = true;
+            queued = write::queue_create_request(id, client, payload);
+        }
+    }
+
+    match queued {
+        Ok(result) if result.ok => {
+            let entry_id = result
+                .result
+                .as_ref()
+                .and_then(|result| result.get("entryId"))
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            let reply = ApiReply::ok(201, result.result);
+            match entry_id {
+                Some(entry_id) => reply.for_entry(&entry_id),
+                None => reply,
+            }
+        }
+        Ok(result) => {
+            let candidates = visible_duplicates(&result, token);
+            let error = result.error.unwrap_or(BridgeError {
+                code: "WRITE_FAILED".to_string(),
+                message: "The entry could not be created.".to_string(),
+            });
+            match candidates {
+                Some(candidates) => ApiReply::ok(
+                    409,
+                    json!({ "error": error, "result": { "candidates": candidates } }),
+                ),
+                None => ApiReply::ok(400, json!({ "error": error, "result": result.result })),
+            }
+        }
+        Err(error) if error.kind() == io::ErrorKind::InvalidInput => {
+            ApiReply::error(400, "INVALID_PAYLOAD", error.to_string())
+        }
+        Err(error) if error.kind() == io::ErrorKind::TimedOut => ApiReply::error(
+            504,
+            "WRITE_TIMEOUT",
+            "The app did not save the entry in time.",
+        ),
+        Err(error) => ApiReply::error(
+            500,
+            "WRITE_REQUEST_ERROR",
+            format!("Failed to queue the entry: {error}"),
+        ),
+    }
+}
+
+/// The duplicate candidates of a create refused as a duplicate, limited to the
+/// folders `token` may read. `None` if the create failed for another reason.
+fn visible_duplicates(result: &BrowserWriteResult, token: &ApiToken) -> Option<Vec<Value>> {
+    if result.error.as_ref()?.code != "DUPLICATE_CANDIDATE" {
+        return None;
+    }
+    let candidates = result
+        .result
+        .as_ref()
+        .and_then(|result| result.get("candidates"))
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    Some(
+        candidates
+            .iter()
+            .filter(|candidate| {
+                token.allows_folder(candidate.get("folderId").and_then(Value::as_str))
+            })
+            .cloned()
+            .collect(),
+    )
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dispatch, respond, visible_duplicates};
+    use crate::{
+        api::{http::HttpRequest, tokens::ApiToken},
+        bridge::{protocol::BridgeError, vault::VaultData, write::BrowserWriteResult},
+    };
+    use serde_json::json;
+
+    fn vault() -> VaultData {
+        serde_json::from_value(json!({
+            "values": [
+                {
+                    "id": "work-mail",
+                    "title": "Work mail",
+                    "folder": { "id": "work", "name": "Work" },
+                    "modules": [
+                        { "module": "USERNAME", "value": "alice" },
+                        { "module": "PASSWORD", "value": "secret" }
+                    ]
+                },
+                {
+                    "id": "home-mail",
+                    "title": "Home mail",
+                    "modules": [{ "module": "PASSWORD", "value": "private" }]
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn token(folder_ids: &[&str], write: bool) -> ApiToken {
+        ApiToken {
+            id: "token-1".into(),
+            name: "CI".into(),
+            folder_ids: folder_ids.iter().map(|id| id.to_string()).collect(),
+            write,
+            created_at_ms: 0,
+            last_used_at_ms: None,
+        }
+    }
+
+    fn get(path: &str, query: &[(&str, &str)]) -> HttpRequest {
+        HttpRequest {
+            method: "GET".into(),
+            path: path.into(),
+            query: query
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            ..HttpRequest::default()
+        }
+    }
+
+    #[test]
+    fn lists_searches_and_gets_entries_within_the_token_scope() {
+        let vault = vault();
+        let scoped = token(&["work"], false);
+
+        let listed = dispatch(&get("/v1/entries", &[]), &scoped, &vault);
+        assert_eq!(listed.status, 200);
+        assert_eq!(listed.body.as_array().unwrap().len(), 1);
+        assert_eq!(listed.body[0]["entryId"], "work-mail");
+
+        let searched = dispatch(&get("/v1/entries", &[("query", "mail")]), &scoped, &vault);
+        assert_eq!(searched.body.as_array().unwrap().len(), 1);
+        let everything = dispatch(
+            &get("/v1/entries", &[("query", "mail")]),
+            &token(&[], false),
+            &vault,
+        );
+        assert_eq!(everything.body.as_array().unwrap().len(), 2);
+
+        let entry = dispatch(&get("/v1/entries/work-mail", &[]), &scoped, &vault);
+        assert_eq!(entry.status, 200);
+        assert_eq!(entry.body["password"], "secret");
+        assert_eq!(entry.entry_id.as_deref(), Some("work-mail"));
+
+        let hidden = dispatch(&get("/v1/entries/home-mail", &[]), &scoped, &vault);
+        assert_eq!(hidden.status, 404);
+        assert!(hidden.body.get("password").is_none());
+    }
+
+    #[test]
+    fn refuses_writes_without_permission_and_unknown_routes() {
+        let vault = vault();
+        let mut create = get("/v1/entries", &[]);
+        create.method = "POST".into();
+        create.body = br#"{"title":"New","password":"x","folderId":"work"}"#.to_vec();
+
+        let refused = dispatch(&create, &token(&["work"], false), &vault);
+        assert_eq!(refused.status, 403);
+        assert_eq!(refused.body["error"]["code"], "READ_ONLY_TOKEN");
+
+        create.body = br#"{"title":"New","password":"x","folderId":"home"}"#.to_vec();
+        let refused = dispatch(&create, &token(&["work"], true), &vault);
+        assert_eq!(refused.body["error"]["code"], "FOLDER_NOT_ALLOWED");
+
+        let mut delete = get("/v1/entries/work-mail", &[]);
+        delete.method = "DELETE".into();
+        assert_eq!(dispatch(&delete, &token(&[], true), &vault).status, 405);
+        assert_eq!(
+            dispatch(&get("/v2/x", &[]), &token(&[], true), &vault).status,
+            404
+        );
+    }
+
+    #[test]
+    fn rejects_browser_and_rebound_requests_before_authenticating() {
+        let mut request = get("/v1/status", &[]);
+        request.headers = vec![("host".into(), "evil.example:17380".into())];
+        let (reply, token) = respond(&request, 17380);
+        assert_eq!(reply.status, 403);
+        assert!(token.is_none());
+
+        request.headers = vec![
+            ("host".into(), "127.0.0.1:17380".into()),
+            ("origin".into(), "https://evil.example".into()),
+        ];
+        assert_eq!(respond(&request, 17380).0.status, 403);
+
+        request.headers = vec![("host".into(), "localhost:17380".into())];
+        assert_eq!(respond(&request, 17380).0.status, 401);
+    }
+
+    #[test]
+    fn duplicates_outside_the_token_scope_are_hidden() {
+        let duplicate = BrowserWriteResult {
+            request_id: "local-api-1".into(),
+            completed_at_ms: 0,
+            ok: false,
+            result: Some(json!({
+                "candidates": [
+                    { "entryId": "work-mail", "title": "Work mail", "folderId": "work" },
+                    { "entryId": "home-mail", "title": "Home mail", "username": "alice" }
+                ]
+            })),
+            error: Some(BridgeError {
+                code: "DUPLICATE_CANDIDATE".into(),
+                message: "duplicate".into(),
+            }),
+        };
+
+        let visible = visible_duplicates(&duplicate, &token(&["work"], true)).unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0]["entryId"], "work-mail");
+        assert!(visible_duplicates(&duplicate, &token(&["other"], true))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            visible_duplicates(&duplicate, &token(&[], true)).map(|all| all.len()),
+            Some(2)
+        );
+
+        let failed = BrowserWriteResult {
+            error: Some(BridgeError {
+                code: "WRITE_FAILED".into(),
+                message: "failed".into(),
+            }),
+            ..duplicate
+        };
+        assert!(visible_duplicates(&failed, &token(&["work"], true)).is_none());
+    }
+}
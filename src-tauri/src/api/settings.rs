@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+use crate::bridge::path::local_api_settings_path;
+
+pub const DEFAULT_PORT: u16 = 17_380;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_port")]
+    pub port: u16,
+}
+
+impl Default for LocalApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_port(),
+        }
+    }
+}
+
+fn default_port() -> u16 {
+    DEFAULT_PORT
+}
+
+pub fn load_settings() -> io::Result<LocalApiSettings> {
+    let path = local_api_settings_path()?;
+    if !path.exists() {
+        return Ok(LocalApiSettings::default());
+    }
+
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str::<LocalApiSettings>(&content)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+pub fn save_settings(settings: &LocalApiSettings) -> io::Result<()> {
+    let path = local_api_settings_path()?;
+    write_json_atomically(&path, settings)
+}
+
+fn write_json_atomically<T: Serialize>(path: &PathBuf, value: &T) -> io::Result<()> {
+    let temp = path.with_extension("tmp");
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    fs::write(&temp, json)?;
+    fs::rename(temp, path)?;
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chacha20poly1305::aead::{rand_core::RngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs, io,
+    path::PathBuf,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::bridge::{path::local_api_token_store_path, vault::VaultEntry};
+
+const TOKEN_PREFIX: &str = "cpat_";
+
+/// Serializes read-modify-write cycles of the token store between the app
+/// and the server threads.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// A token as shown in the app. The secret itself is only returned once, by
+/// `issue_token`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    /// Folders the token can see. Empty means every entry, including entries
+    /// outside any folder.
+    #[serde(default)]
+    pub folder_ids: Vec<String>,
+    /// Whether the token may create entries, not only read them.
+    #[serde(default)]
+    pub write: bool,
+    pub created_at_ms: u64,
+    #[serde(default)]
+    pub last_used_at_ms: Option<u64>,
+}
+
+impl ApiToken {
+    pub fn allows_folder(&self, folder_id: Option<&str>) -> bool {
+        self.folder_ids.is_empty()
+            || folder_id.is_some_and(|folder_id| self.folder_ids.iter().any(|id| id == folder_id))
+    }
+
+    pub fn allows_entry(&self, entry: &VaultEntry) -> bool {
+        self.allows_folder(entry.folder.as_ref().map(|folder| folder.id.as_str()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuedApiToken {
+    pub token: String,
+    #[serde(flatten)]
+    pub info: ApiToken,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredToken {
+    #[serde(flatten)]
+    info: ApiToken,
+    token_hash: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenStore {
+    #[serde(default)]
+    tokens: Vec<StoredToken>,
+}
+
+pub fn issue_token(name: &str, folder_ids: Vec<String>, write: bool) -> io::Result<IssuedApiToken> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Token name is required",
+        ));
+    }
+
+    let mut secret = [0u8; 32];
+    OsRng.fill_bytes(&mut secret);
+    let token = format!("{TOKEN_PREFIX}{}", URL_SAFE_NO_PAD.encode(secret));
+    let now = now_ms();
+    let info = ApiToken {
+        id: format!("token-{now}-{:08x}", OsRng.next_u32()),
+        name: name.to_string(),
+        folder_ids,
+        write,
+        created_at_ms: now,
+        last_used_at_ms: None,
+    };
+
+    let _guard = lock_store();
+    let path = local_api_token_store_path()?;
+    let mut store = load_store(&path)?;
+    store.tokens.push(StoredToken {
+        info: info.clone(),
+        token_hash: hash_token(&token),
+    });
+    write_json_atomically(&path, &store)?;
+    Ok(IssuedApiToken { token, info })
+}
+
+pub fn revoke_token(token_id: &str) -> io::Result<bool> {
+    let _guard = lock_store();
+    let path = local_api_token_store_path()?;
+    let mut store = load_store(&path)?;
+    let before = store.tokens.len();
+    store.tokens.retain(|token| token.info.id != token_id);
+    if store.tokens.len() == before {
+        return Ok(false);
+    }
+    write_json_atomically(&path, &store)?;
+    Ok(true)
+}
+
+pub fn list_tokens() -> io::Result<Vec<ApiToken>> {
+    let _guard = lock_store();
+    Ok(load_store(&local_api_token_store_path()?)?
+        .tokens
+        .into_iter()
+        .map(|token| token.info)
+        .collect())
+}
+
+/// Resolves the bearer `token` and records its use.
+pub fn authenticate(token: &str) -> io::Result<Option<ApiToken>> {
+    if !token.starts_with(TOKEN_PREFIX) {
+        return Ok(None);
+    }
+
+    let hash = hash_token(token);
+    let _guard = lock_store();
+    let path = local_api_token_store_path()?;
+    let mut store = load_store(&path)?;
+    let Some(stored) = store
+        .tokens
+        .iter_mut()
+        .find(|stored| stored.token_hash == hash)
+    else {
+        return Ok(None);
+    };
+
+    stored.info.last_used_at_ms = Some(now_ms());
+    let info = stored.info.clone();
+    write_json_atomically(&path, &store)?;
+    Ok(Some(info))
+}
+
+fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn lock_store() -> std::sync::MutexGuard<'static, ()> {
+    STORE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn load_store(path: &PathBuf) -> io::Result<TokenStore> {
+    if !path.exists() {
+        return Ok(TokenStore::default());
+    }
+
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str::<TokenStore>(&content)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn write_json_atomically<T: Serialize>(path: &PathBuf, value: &T) -> io::Result<()> {
+    let temp = path.with_extension("tmp");
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    fs::write(&temp, json)?;
+    fs::rename(temp, path)?;
+    Ok(())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
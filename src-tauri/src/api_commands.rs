@@ -0,0 +1,105 @@
+use serde::Serialize;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::api::{
+    audit::{self, AuditRecord},
+    server::LocalApiServer,
+    settings::{self, LocalApiSettings},
+    tokens::{self, ApiToken, IssuedApiToken},
+};
+
+const DEFAULT_AUDIT_LIMIT: usize = 200;
+
+static RUNNING: Mutex<Option<LocalApiServer>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+}
+
+fn running() -> MutexGuard<'static, Option<LocalApiServer>> {
+    RUNNING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn start(port: u16) -> std::io::Result<()> {
+    let mut running = running();
+    if running.as_ref().map(LocalApiServer::port) != Some(port) {
+        running.take();
+        *running = Some(LocalApiServer::start(port)?);
+    }
+    Ok(())
+}
+
+/// Starts the API at launch if the user turned it on.
+pub fn start_if_enabled() {
+    match settings::load_settings() {
+        Ok(settings) if settings.enabled => {
+            if let Err(error) = start(settings.port) {
+                eprintln!("Failed to start the local API: {error}");
+            }
+        }
+        Ok(_) => {}
+        Err(error) => eprintln!("Failed to load local API settings: {error}"),
+    }
+}
+
+#[tauri::command]
+pub fn local_api_get_status() -> Result<LocalApiStatus, String> {
+    let settings = settings::load_settings()
+        .map_err(|err| format!("Failed to load local API settings: {err}"))?;
+    Ok(LocalApiStatus {
+        enabled: settings.enabled,
+        running: running().is_some(),
+        port: settings.port,
+    })
+}
+
+/// Turning the API on fails, and is not saved, if the port is taken.
+#[tauri::command]
+pub fn local_api_set_enabled(enabled: bool, port: Option<u16>) -> Result<LocalApiStatus, String> {
+    let current = settings::load_settings()
+        .map_err(|err| format!("Failed to load local API settings: {err}"))?;
+    let port = port.unwrap_or(current.port);
+    if enabled {
+        start(port)
+            .map_err(|err| format!("Failed to start the local API on port {port}: {err}"))?;
+    } else {
+        running().take();
+    }
+
+    settings::save_settings(&LocalApiSettings { enabled, port })
+        .map_err(|err| format!("Failed to save local API settings: {err}"))?;
+    local_api_get_status()
+}
+
+#[tauri::command]
+pub fn local_api_list_tokens() -> Result<Vec<ApiToken>, String> {
+    tokens::list_tokens().map_err(|err| format!("Failed to load local API tokens: {err}"))
+}
+
+#[tauri::command]
+pub fn local_api_issue_token(
+    name: String,
+    folder_ids: Vec<String>,
+    write: bool,
+) -> Result<IssuedApiToken, String> {
+    tokens::issue_token(&name, folder_ids, write)
+        .map_err(|err| format!("Failed to issue local API token: {err}"))
+}
+
+#[tauri::command]
+pub fn local_api_revoke_token(token_id: String) -> Result<bool, String> {
+    tokens::revoke_token(&token_id)
+        .map_err(|err| format!("Failed to revoke local API token: {err}"))
+}
+
+#[tauri::command]
+pub fn local_api_list_audit(limit: Option<usize>) -> Result<Vec<AuditRecord>, String> {
+    audit::recent(limit.unwrap_or(DEFAULT_AUDIT_LIMIT))
+        .map_err(|err| format!("Failed to load the local API audit log: {err}"))
+}
@@ -0,0 +1,14 @@
+fn main() {
+    match app_lib::cli::run() {
+        Ok(code) => std::process::exit(code),
+        Err(error) => {
+            eprintln!("clavispass: {error}");
+            let code = if error.kind() == std::io::ErrorKind::InvalidInput {
+                2
+            } else {
+                1
+            };
+            std::process::exit(code);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+fn main() {
+    match app_lib::cli::git_credential::run() {
+        Ok(code) => std::process::exit(code),
+        Err(error) => {
+            eprintln!("git-credential-clavispass: {error}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,575 @@
+use serde::Serialize;
+
+use super::vault::{first_string, text_match_score, VaultData, VaultEntry, VaultModule};
+
+const DEFAULT_RESULT_LIMIT: usize = 20;
+const MAX_RESULT_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardSuggestion {
+    pub entry_id: String,
+    pub module_id: Option<String>,
+    pub title: String,
+    pub fav: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub brand: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_four: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_month: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_year: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardFillData {
+    pub entry_id: String,
+    pub module_id: Option<String>,
+    pub title: String,
+    pub number: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_month: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_year: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cvc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub brand: Option<String>,
+    pub luhn_valid: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentitySuggestion {
+    pub entry_id: String,
+    pub title: String,
+    pub fav: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityFillData {
+    pub entry_id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub honorific: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub middle_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub department: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address_line1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address_line2: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+}
+
+pub fn search_cards(vault: &VaultData, query: &str, limit: Option<usize>) -> Vec<CardSuggestion> {
+    let mut matches = Vec::<(u32, CardSuggestion)>::new();
+
+    for entry in &vault.values {
+        for module in entry.modules.iter().filter(|module| module.module == "CREDIT_CARD") {
+            let card = parse_card(entry, module);
+            if card.number.is_empty() {
+                continue;
+            }
+
+            let haystack = [
+                Some(entry.title.as_str()),
+                card.holder.as_deref(),
+                card.brand.as_deref(),
+                module.field("bankName").as_deref(),
+            ]
+            .into_iter()
+            .flatten()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>();
+
+            let Some(score) = query_score(query, &haystack) else {
+                continue;
+            };
+
+            let digits = card.number.clone();
+            matches.push((
+                score,
+                CardSuggestion {
+                    entry_id: card.entry_id,
+                    module_id: card.module_id,
+                    title: card.title,
+                    fav: entry.fav,
+                    holder: card.holder,
+                    brand: card.brand,
+                    last_four: (digits.len() >= 4).then(|| digits[digits.len() - 4..].to_string()),
+                    expiry_month: card.expiry_month,
+                    expiry_year: card.expiry_year,
+                },
+            ));
+        }
+    }
+
+    sort_and_limit(matches, limit, |suggestion| (suggestion.fav, suggestion.title.as_str()))
+}
+
+pub fn card_fill_data(
+    vault: &VaultData,
+    entry_id: &str,
+    module_id: Option<&str>,
+) -> Option<CardFillData> {
+    let entry = vault.values.iter().find(|entry| entry.id == entry_id)?;
+    let module = entry.modules.iter().find(|module| {
+        module.module == "CREDIT_CARD"
+            && module_id.map_or(true, |id| module.id.as_deref() == Some(id))
+    })?;
+
+    let card = parse_card(entry, module);
+    if card.number.is_empty() {
+        return None;
+    }
+
+    Some(card)
+}
+
+pub fn search_identities(
+    vault: &VaultData,
+    query: &str,
+    limit: Option<usize>,
+) -> Vec<IdentitySuggestion> {
+    let mut matches = Vec::<(u32, IdentitySuggestion)>::new();
+
+    for entry in vault.values.iter().filter(|entry| is_identity_entry(entry)) {
+        let identity = parse_identity(entry);
+        let haystack = [
+            Some(entry.title.as_str()),
+            identity.full_name.as_deref(),
+            identity.email.as_deref(),
+            identity.organization.as_deref(),
+            identity.city.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+
+        let Some(score) = query_score(query, &haystack) else {
+            continue;
+        };
+
+        matches.push((
+            score,
+            IdentitySuggestion {
+                entry_id: identity.entry_id,
+                title: identity.title,
+                fav: entry.fav,
+                full_name: identity.full_name,
+                email: identity.email,
+                city: identity.city,
+            },
+        ));
+    }
+
+    sort_and_limit(matches, limit, |suggestion| (suggestion.fav, suggestion.title.as_str()))
+}
+
+pub fn identity_fill_data(vault: &VaultData, entry_id: &str) -> Option<IdentityFillData> {
+    let entry = vault.values.iter().find(|entry| entry.id == entry_id)?;
+    if !is_identity_entry(entry) {
+        return None;
+    }
+
+    Some(parse_identity(entry))
+}
+
+/// Known issuer prefixes, mirroring `detectCardBrand` in the app's
+/// credit card module.
+pub fn detect_card_brand(number: &str) -> Option<&'static str> {
+    let digits = digits_only(number);
+    let starts_with_range = |length: usize, from: u32, to: u32| {
+        digits
+            .get(..length)
+            .and_then(|prefix| prefix.parse::<u32>().ok())
+            .is_some_and(|prefix| (from..=to).contains(&prefix))
+    };
+
+    if digits.starts_with('4') {
+        Some("Visa")
+    } else if starts_with_range(2, 51, 55) || starts_with_range(2, 22, 27) {
+        Some("Mastercard")
+    } else if digits.starts_with("34") || digits.starts_with("37") {
+        Some("American Express")
+    } else if digits.starts_with("6011") || digits.starts_with("65") || starts_with_range(3, 644, 649)
+    {
+        Some("Discover")
+    } else if starts_with_range(3, 300, 305) || digits.starts_with("36") || digits.starts_with("38")
+    {
+        Some("Diners Club")
+    } else if digits.starts_with("35") {
+        Some("JCB")
+    } else if digits.starts_with("62") {
+        Some("UnionPay")
+    } else {
+        None
+    }
+}
+
+pub fn passes_luhn(number: &str) -> bool {
+    let digits = digits_only(number);
+    if digits.len() < 12 {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .chars()
+        .rev()
+        .filter_map(|ch| ch.to_digit(10))
+        .enumerate()
+        .map(|(index, digit)| {
+            if index % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum();
+
+    sum % 10 == 0
+}
+
+/// Splits card expiry input into a two-digit month and a four-digit year.
+/// Accepts the separate `expiryMonth`/`expiryYear` fields as well as
+/// combined values such as `04/27`, `4-2027` or `2027-04`.
+fn parse_expiry(month: Option<&str>, year: Option<&str>) -> (Option<String>, Option<String>) {
+    if let (Some(month), None) = (month, year) {
+        if let Some((left, right)) = month.split_once(['/', '-', '.', ' ']) {
+            return if left.trim().len() == 4 {
+                (normalize_month(right), normalize_year(left))
+            } else {
+                (normalize_month(left), normalize_year(right))
+            };
+        }
+    }
+
+    (
+        month.and_then(normalize_month),
+        year.and_then(normalize_year),
+    )
+}
+
+fn normalize_month(value: &str) -> Option<String> {
+    let month = value.trim().parse::<u8>().ok()?;
+    (1..=12).contains(&month).then(|| format!("{month:02}"))
+}
+
+fn normalize_year(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    let year = trimmed.parse::<u16>().ok()?;
+    match trimmed.len() {
+        2 => Some(format!("{}", 2000 + year)),
+        4 => Some(format!("{year}")),
+        _ => None,
+    }
+}
+
+fn parse_card(entry: &VaultEntry, module: &VaultModule) -> CardFillData {
+    let number = module.field("number").map(|value| digits_only(&value)).unwrap_or_default();
+    let (expiry_month, expiry_year) = parse_expiry(
+        module.field("expiryMonth").as_deref(),
+        module.field("expiryYear").as_deref(),
+    );
+    let brand = module
+        .field("brand")
+        .or_else(|| detect_card_brand(&number).map(str::to_string));
+
+    CardFillData {
+        entry_id: entry.id.clone(),
+        module_id: module.id.clone(),
+        title: entry.title.clone(),
+        luhn_valid: passes_luhn(&number),
+        number,
+        holder: module.field("cardholderName"),
+        expiry_month,
+        expiry_year,
+        cvc: module.field("securityCode"),
+        brand,
+    }
+}
+
+fn is_identity_entry(entry: &VaultEntry) -> bool {
+    entry
+        .modules
+        .iter()
+        .any(|module| module.module == "PERSON" || module.module == "ADDRESS")
+}
+
+fn parse_identity(entry: &VaultEntry) -> IdentityFillData {
+    let person = find_module(entry, "PERSON");
+    let address = find_module(entry, "ADDRESS");
+    let company = find_module(entry, "COMPANY");
+    let person_field = |key: &str| person.and_then(|module| module.field(key));
+    let address_field = |key: &str| address.and_then(|module| module.field(key));
+    let company_field = |key: &str| company.and_then(|module| module.field(key));
+
+    let first_name = person_field("firstName");
+    let middle_name = person_field("middleName");
+    let last_name = person_field("lastName");
+    let full_name = person_field("displayName").or_else(|| {
+        let parts = [&first_name, &middle_name, &last_name]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    });
+
+    IdentityFillData {
+        entry_id: entry.id.clone(),
+        title: entry.title.clone(),
+        honorific: person
+            .and_then(|module| module.title.as_deref())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string),
+        first_name,
+        middle_name,
+        last_name,
+        full_name,
+        username: person_field("username").or_else(|| first_string(entry, "USERNAME")),
+        email: first_string(entry, "E_MAIL"),
+        phone: first_string(entry, "PHONE_NUMBER"),
+        organization: company_field("name"),
+        department: company_field("department"),
+        job_title: company_field("jobTitle"),
+        address_line1: address_field("street1"),
+        address_line2: address_field("street2"),
+        postal_code: address_field("postalCode"),
+        city: address_field("city"),
+        state: address_field("state"),
+        country: address_field("country"),
+    }
+}
+
+fn find_module<'a>(entry: &'a VaultEntry, module_name: &str) -> Option<&'a VaultModule> {
+    entry.modules.iter().find(|module| module.module == module_name)
+}
+
+/// Scores a free-text query against lowercase candidate texts. An empty query
+/// matches everything; otherwise every term has to match some candidate.
+fn query_score(query: &str, haystack: &[String]) -> Option<u32> {
+    let mut total = 0u32;
+
+    for term in query.split_whitespace().map(str::to_lowercase) {
+        let best = haystack
+            .iter()
+            .map(|text| text_match_score(&term, text))
+            .max()
+            .unwrap_or(0);
+        if best == 0 {
+            return None;
+        }
+        total += best;
+    }
+
+    Some(total)
+}
+
+fn sort_and_limit<T>(
+    mut matches: Vec<(u32, T)>,
+    limit: Option<usize>,
+    sort_key: impl Fn(&T) -> (bool, &str),
+) -> Vec<T> {
+    matches.sort_by(|left, right| {
+        let (left_fav, left_title) = sort_key(&left.1);
+        let (right_fav, right_title) = sort_key(&right.1);
+        right
+            .0
+            .cmp(&left.0)
+            .then_with(|| right_fav.cmp(&left_fav))
+            .then_with(|| left_title.to_lowercase().cmp(&right_title.to_lowercase()))
+    });
+    matches.truncate(limit.unwrap_or(DEFAULT_RESULT_LIMIT).clamp(1, MAX_RESULT_LIMIT));
+    matches.into_iter().map(|(_, item)| item).collect()
+}
+
+fn digits_only(value: &str) -> String {
+    value.chars().filter(char::is_ascii_digit).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn card_entry(id: &str, module: serde_json::Value) -> VaultEntry {
+        VaultEntry {
+            id: id.to_string(),
+            title: format!("Card {id}"),
+            modules: vec![serde_json::from_value(module).unwrap()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn card_fill_data_parses_number_expiry_and_brand() {
+        let vault = VaultData {
+            values: vec![card_entry(
+                "visa",
+                json!({
+                    "id": "m1",
+                    "module": "CREDIT_CARD",
+                    "cardholderName": "Alice Example",
+                    "number": "4111 1111 1111 1111",
+                    "brand": "",
+                    "expiryMonth": "4",
+                    "expiryYear": "27",
+                    "securityCode": "123"
+                }),
+            )],
+            ..Default::default()
+        };
+
+        let card = card_fill_data(&vault, "visa", None).unwrap();
+
+        assert_eq!(card.number, "4111111111111111");
+        assert_eq!(card.holder.as_deref(), Some("Alice Example"));
+        assert_eq!(card.expiry_month.as_deref(), Some("04"));
+        assert_eq!(card.expiry_year.as_deref(), Some("2027"));
+        assert_eq!(card.cvc.as_deref(), Some("123"));
+        assert_eq!(card.brand.as_deref(), Some("Visa"));
+        assert!(card.luhn_valid);
+    }
+
+    #[test]
+    fn combined_expiry_values_are_split() {
+        assert_eq!(
+            parse_expiry(Some("09/2031"), None),
+            (Some("09".to_string()), Some("2031".to_string()))
+        );
+        assert_eq!(
+            parse_expiry(Some("2029-11"), None),
+            (Some("11".to_string()), Some("2029".to_string()))
+        );
+        assert_eq!(parse_expiry(Some("13"), Some("2")), (None, None));
+    }
+
+    #[test]
+    fn luhn_and_brand_detection() {
+        assert!(passes_luhn("5555 5555 5555 4444"));
+        assert!(!passes_luhn("5555 5555 5555 4445"));
+        assert!(!passes_luhn("4242"));
+        assert_eq!(detect_card_brand("378282246310005"), Some("American Express"));
+        assert_eq!(detect_card_brand("2221000000000009"), Some("Mastercard"));
+        assert_eq!(detect_card_brand("6011111111111117"), Some("Discover"));
+        assert_eq!(detect_card_brand("9999"), None);
+    }
+
+    #[test]
+    fn search_cards_skips_cards_without_number_and_reports_last_four() {
+        let vault = VaultData {
+            values: vec![
+                card_entry("empty", json!({ "id": "m1", "module": "CREDIT_CARD", "number": "" })),
+                card_entry(
+                    "amex",
+                    json!({ "id": "m2", "module": "CREDIT_CARD", "number": "3782 822463 10005" }),
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let results = search_cards(&vault, "", None);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry_id, "amex");
+        assert_eq!(results[0].last_four.as_deref(), Some("0005"));
+        assert_eq!(results[0].brand.as_deref(), Some("American Express"));
+        assert!(search_cards(&vault, "visa", None).is_empty());
+    }
+
+    #[test]
+    fn identity_fill_data_combines_person_address_and_contact_modules() {
+        let entry = VaultEntry {
+            id: "me".to_string(),
+            title: "Personal".to_string(),
+            modules: serde_json::from_value(json!([
+                {
+                    "id": "p",
+                    "module": "PERSON",
+                    "firstName": "Ada",
+                    "middleName": "",
+                    "lastName": "Lovelace",
+                    "title": "Dr."
+                },
+                {
+                    "id": "a",
+                    "module": "ADDRESS",
+                    "street1": "12 Example Road",
+                    "postalCode": "10115",
+                    "city": "Berlin",
+                    "country": "DE"
+                },
+                { "id": "t", "module": "PHONE_NUMBER", "value": "+49 30 1234567" },
+                { "id": "e", "module": "E_MAIL", "value": "ada@example.com" },
+                { "id": "c", "module": "COMPANY", "name": "Analytical Engines" }
+            ]))
+            .unwrap(),
+            ..Default::default()
+        };
+        let vault = VaultData {
+            values: vec![entry],
+            ..Default::default()
+        };
+
+        let identity = identity_fill_data(&vault, "me").unwrap();
+
+        assert_eq!(identity.honorific.as_deref(), Some("Dr."));
+        assert_eq!(identity.full_name.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(identity.middle_name, None);
+        assert_eq!(identity.address_line1.as_deref(), Some("12 Example Road"));
+        assert_eq!(identity.postal_code.as_deref(), Some("10115"));
+        assert_eq!(identity.country.as_deref(), Some("DE"));
+        assert_eq!(identity.phone.as_deref(), Some("+49 30 1234567"));
+        assert_eq!(identity.organization.as_deref(), Some("Analytical Engines"));
+
+        let results = search_identities(&vault, "lovelace", None);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].city.as_deref(), Some("Berlin"));
+    }
+}
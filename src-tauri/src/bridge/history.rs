@@ -0,0 +1,308 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::{path::password_history_settings_path, session::load_session};
+
+pub const DEFAULT_MAX_ITEMS: usize = 10;
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// A password that was replaced on an entry. The list is stored on the vault
+/// entry itself (`passwordHistory`) so it is encrypted together with the vault.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordHistoryItem {
+    pub password: String,
+    pub replaced_at_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordHistoryRetention {
+    #[serde(default = "default_max_items")]
+    pub max_items: usize,
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for PasswordHistoryRetention {
+    fn default() -> Self {
+        Self {
+            max_items: DEFAULT_MAX_ITEMS,
+            max_age_days: None,
+        }
+    }
+}
+
+/// Summary of the history change reported back to the browser. It never
+/// contains the replaced password.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordHistoryReport {
+    pub recorded: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_at_ms: Option<u64>,
+    pub retained: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordHistoryUpdate {
+    pub history: Vec<PasswordHistoryItem>,
+    pub report: PasswordHistoryReport,
+}
+
+pub fn load_retention() -> io::Result<PasswordHistoryRetention> {
+    let path = password_history_settings_path()?;
+    if !path.exists() {
+        return Ok(PasswordHistoryRetention::default());
+    }
+
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str::<PasswordHistoryRetention>(&content)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+pub fn save_retention(retention: &PasswordHistoryRetention) -> io::Result<()> {
+    let path = password_history_settings_path()?;
+    write_json_atomically(&path, retention)
+}
+
+/// Lists the retained history of an entry from the published session,
+/// newest first. Returns `None` when the vault is locked or the entry is gone.
+pub fn list_password_history(entry_id: &str) -> io::Result<Option<Vec<PasswordHistoryItem>>> {
+    let Some(snapshot) = load_session()? else {
+        return Ok(None);
+    };
+    let retention = load_retention()?;
+
+    Ok(snapshot
+        .vault
+        .values
+        .iter()
+        .find(|entry| entry.id == entry_id)
+        .map(|entry| apply_retention(entry.password_history.clone(), &retention, now_ms())))
+}
+
+/// Applies the stored retention to a password change made by the app and
+/// returns the new history together with the report for the write result.
+pub fn record_password_change(
+    mut history: Vec<PasswordHistoryItem>,
+    previous: Option<&str>,
+    next: &str,
+    client: Option<&str>,
+) -> io::Result<PasswordHistoryUpdate> {
+    let retention = load_retention()?;
+    let recorded =
+        record_replaced_password(&mut history, previous, next, client, &retention, now_ms());
+
+    Ok(PasswordHistoryUpdate {
+        report: PasswordHistoryReport {
+            recorded: recorded.is_some(),
+            replaced_at_ms: recorded.map(|item| item.replaced_at_ms),
+            retained: history.len(),
+        },
+        history,
+    })
+}
+
+/// Puts `item` back as the entry's password: the current password takes its
+/// place in the history and the stored retention is applied.
+pub fn restore_password(
+    history: Vec<PasswordHistoryItem>,
+    current: Option<&str>,
+    item: &PasswordHistoryItem,
+) -> io::Result<PasswordHistoryUpdate> {
+    let retention = load_retention()?;
+    restore_from_history(history, current, item, &retention, now_ms())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Password history item not found"))
+}
+
+/// Takes the first item equal to `item` out of `history` and records
+/// `current` instead. Items are compared whole, so two passwords replaced in
+/// the same millisecond are told apart.
+pub fn restore_from_history(
+    mut history: Vec<PasswordHistoryItem>,
+    current: Option<&str>,
+    item: &PasswordHistoryItem,
+    retention: &PasswordHistoryRetention,
+    now: u64,
+) -> Option<PasswordHistoryUpdate> {
+    let index = history.iter().position(|other| other == item)?;
+    history.remove(index);
+    let recorded =
+        record_replaced_password(&mut history, current, &item.password, None, retention, now);
+    if recorded.is_none() {
+        history = apply_retention(history, retention, now);
+    }
+
+    Some(PasswordHistoryUpdate {
+        report: PasswordHistoryReport {
+            recorded: recorded.is_some(),
+            replaced_at_ms: recorded.map(|item| item.replaced_at_ms),
+            retained: history.len(),
+        },
+        history,
+    })
+}
+
+/// Pushes `previous` onto `history` when it differs from `next`, then prunes
+/// the list. Returns the recorded item, if any.
+pub fn record_replaced_password(
+    history: &mut Vec<PasswordHistoryItem>,
+    previous: Option<&str>,
+    next: &str,
+    client: Option<&str>,
+    retention: &PasswordHistoryRetention,
+    now: u64,
+) -> Option<PasswordHistoryItem> {
+    let previous = previous.filter(|value| !value.is_empty() && *value != next)?;
+    let item = PasswordHistoryItem {
+        password: previous.to_string(),
+        replaced_at_ms: now,
+        client: client.map(str::to_string),
+    };
+
+    history.push(item.clone());
+    *history = apply_retention(std::mem::take(history), retention, now);
+    history.contains(&item).then_some(item)
+}
+
+/// Sorts newest first, drops items older than `max_age_days` and keeps at most
+/// `max_items`.
+pub fn apply_retention(
+    mut history: Vec<PasswordHistoryItem>,
+    retention: &PasswordHistoryRetention,
+    now: u64,
+) -> Vec<PasswordHistoryItem> {
+    history.sort_by_key(|item| Reverse(item.replaced_at_ms));
+
+    if let Some(days) = retention.max_age_days {
+        let cutoff = now.saturating_sub(u64::from(days) * DAY_MS);
+        history.retain(|item| item.replaced_at_ms >= cutoff);
+    }
+
+    history.truncate(retention.max_items);
+    history
+}
+
+fn default_max_items() -> usize {
+    DEFAULT_MAX_ITEMS
+}
+
+fn write_json_atomically<T: Serialize>(path: &PathBuf, value: &T) -> io::Result<()> {
+    let temp = path.with_extension("tmp");
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    fs::write(&temp, json)?;
+    fs::rename(temp, path)?;
+    Ok(())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        apply_retention, record_replaced_password, restore_from_history, PasswordHistoryItem,
+        PasswordHistoryRetention, DAY_MS,
+    };
+
+    fn item(password: &str, replaced_at_ms: u64) -> PasswordHistoryItem {
+        PasswordHistoryItem {
+            password: password.into(),
+            replaced_at_ms,
+            client: None,
+        }
+    }
+
+    #[test]
+    fn records_only_changed_passwords() {
+        let retention = PasswordHistoryRetention::default();
+        let mut history = Vec::new();
+
+        assert!(
+            record_replaced_password(&mut history, Some("same"), "same", None, &retention, 1)
+                .is_none()
+        );
+        assert!(record_replaced_password(&mut history, None, "new", None, &retention, 1).is_none());
+
+        let recorded = record_replaced_password(
+            &mut history,
+            Some("old"),
+            "new",
+            Some("Firefox"),
+            &retention,
+            5,
+        )
+        .expect("recorded");
+        assert_eq!(recorded.client.as_deref(), Some("Firefox"));
+        assert_eq!(history, vec![recorded]);
+    }
+
+    #[test]
+    fn retention_limits_count_and_age() {
+        let now = 40 * DAY_MS;
+        let history = vec![
+            item("a", now - 35 * DAY_MS),
+            item("b", now - 2 * DAY_MS),
+            item("c", now - DAY_MS),
+            item("d", now),
+        ];
+        let retention = PasswordHistoryRetention {
+            max_items: 2,
+            max_age_days: Some(30),
+        };
+
+        let kept = apply_retention(history, &retention, now);
+        let passwords = kept
+            .iter()
+            .map(|item| item.password.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(passwords, vec!["d", "c"]);
+    }
+
+    #[test]
+    fn restores_only_the_chosen_item_and_keeps_retention() {
+        let now = 10 * DAY_MS;
+        let history = vec![
+            item("a", now - DAY_MS),
+            item("b", now - DAY_MS),
+            item("c", 0),
+        ];
+        let retention = PasswordHistoryRetention {
+            max_items: 2,
+            max_age_days: None,
+        };
+
+        let update = restore_from_history(
+            history.clone(),
+            Some("current"),
+            &history[1],
+            &retention,
+            now,
+        )
+        .unwrap();
+        let passwords = update
+            .history
+            .iter()
+            .map(|item| item.password.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(passwords, vec!["current", "a"]);
+        assert!(update.report.recorded);
+        assert_eq!(update.report.retained, 2);
+
+        assert!(restore_from_history(history, None, &item("z", 1), &retention, now).is_none());
+    }
+}
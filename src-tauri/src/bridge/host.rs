@@ -1,12 +1,17 @@
 use std::io::{self, Read, Write};
 
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use super::{
+    autofill,
+    keepassxc::KeePassXcSession,
     pairing::{self, PairingStatus},
-    protocol::{BridgeRequest, BridgeResponse, HOST_NAME, PROTOCOL_VERSION},
+    protocol::{BridgeClientInfo, BridgeRequest, BridgeResponse, HOST_NAME, PROTOCOL_VERSION},
     session,
-    vault::{self, FillDataResult, SearchEntrySuggestion},
+    vault::{
+        self, FillDataResult, FillOrigin, HttpAuthChallenge, HttpAuthCredential,
+        SearchEntrySuggestion,
+    },
     write::{self, CreateEntryFromBrowserPayload, UpdateEntryFromBrowserPayload},
 };
 
@@ -43,19 +48,66 @@ struct SearchPayload {
     domain: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchEntriesPayload {
+    query: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct FillPayload {
     entry_id: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OriginFillPayload {
+    entry_id: String,
+    #[serde(flatten)]
+    origin: FillOrigin,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OptionalQueryPayload {
+    #[serde(default)]
+    query: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CardFillPayload {
+    entry_id: String,
+    #[serde(default)]
+    module_id: Option<String>,
+}
+
 pub fn run_native_host() -> io::Result<()> {
     let stdin = io::stdin();
     let stdout = io::stdout();
     let mut reader = stdin.lock();
     let mut writer = stdout.lock();
 
+    let mut keepassxc = None::<KeePassXcSession>;
+
     while let Some(message) = read_frame(&mut reader)? {
+        // KeePassXC-Browser messages name an `action` instead of a `command`.
+        if let Ok(message) = serde_json::from_slice::<serde_json::Value>(&message) {
+            if message.get("action").is_some() {
+                let response = keepassxc
+                    .get_or_insert_with(KeePassXcSession::default)
+                    .handle_message(message);
+                write_frame(&mut writer, &response)?;
+                writer.flush()?;
+                continue;
+            }
+        }
+
         let response = match serde_json::from_slice::<BridgeRequest>(&message) {
             Ok(request) => handle_request(request),
             Err(error) => BridgeResponse::error(
@@ -103,18 +155,54 @@ fn handle_request(request: BridgeRequest) -> BridgeResponse {
             }
             respond_search(request.id, request.payload)
         }
+        "searchEntries" => {
+            if pairing_status != PairingStatus::Paired {
+                return pairing_required(request.id);
+            }
+            respond_search_entries(request.id, request.payload)
+        }
         "getFillDataForEntry" => {
             if pairing_status != PairingStatus::Paired {
                 return pairing_required(request.id);
             }
             respond_fill(request.id, request.payload)
         }
+        "getHttpAuthCredentials" => {
+            if pairing_status != PairingStatus::Paired {
+                return pairing_required(request.id);
+            }
+            respond_http_auth(request.id, request.payload)
+        }
+        "searchCards" => {
+            if pairing_status != PairingStatus::Paired {
+                return pairing_required(request.id);
+            }
+            respond_search_cards(request.id, request.payload)
+        }
+        "getCardFillData" => {
+            if pairing_status != PairingStatus::Paired {
+                return pairing_required(request.id);
+            }
+            respond_card_fill(request.id, request.payload)
+        }
+        "searchIdentities" => {
+            if pairing_status != PairingStatus::Paired {
+                return pairing_required(request.id);
+            }
+            respond_search_identities(request.id, request.payload)
+        }
+        "getIdentityFillData" => {
+            if pairing_status != PairingStatus::Paired {
+                return pairing_required(request.id);
+            }
+            respond_identity_fill(request.id, request.payload)
+        }
         "createEntryFromBrowser" => {
             if pairing_status != PairingStatus::Paired {
                 return pairing_required(request.id);
             }
             if let Err(response) = ensure_ready(&request.id) {
-                return response;
+                return respond_locked_write(request, response);
             }
             respond_create(request)
         }
@@ -123,10 +211,28 @@ fn handle_request(request: BridgeRequest) -> BridgeResponse {
                 return pairing_required(request.id);
             }
             if let Err(response) = ensure_ready(&request.id) {
-                return response;
+                return respond_locked_write(request, response);
             }
             respond_update(request)
         }
+        "deleteEntryFromBrowser" | "moveEntryToFolder" | "setFavorite" => {
+            if pairing_status != PairingStatus::Paired {
+                return pairing_required(request.id);
+            }
+            if let Err(response) = ensure_capability(&request) {
+                return response;
+            }
+            if let Err(response) = ensure_ready(&request.id) {
+                return response;
+            }
+            match request.command.as_str() {
+                "deleteEntryFromBrowser" => {
+                    respond_entry_write(request, write::queue_delete_request)
+                }
+                "moveEntryToFolder" => respond_entry_write(request, write::queue_move_request),
+                _ => respond_entry_write(request, write::queue_set_favorite_request),
+            }
+        }
         other => BridgeResponse::error(
             request.id,
             "UNKNOWN_COMMAND",
@@ -143,6 +249,27 @@ fn pairing_required(id: String) -> BridgeResponse {
     )
 }
 
+/// Commands that change or remove existing entries need their own capability,
+/// granted per client in the app.
+fn ensure_capability(request: &BridgeRequest) -> Result<(), BridgeResponse> {
+    match pairing::has_capability(&request.client, &request.command) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(BridgeResponse::error(
+            request.id.clone(),
+            "CAPABILITY_REQUIRED",
+            format!(
+                "Extension is not allowed to use {} yet. Grant it in the desktop app.",
+                request.command
+            ),
+        )),
+        Err(error) => Err(BridgeResponse::error(
+            request.id.clone(),
+            "PAIRING_STORE_ERROR",
+            format!("Failed to evaluate pairing: {error}"),
+        )),
+    }
+}
+
 fn ensure_ready(id: &str) -> Result<(), BridgeResponse> {
     match session::load_session() {
         Ok(Some(_)) => Ok(()),
@@ -159,6 +286,22 @@ fn ensure_ready(id: &str) -> Result<(), BridgeResponse> {
     }
 }
 
+fn load_unlocked_session(id: &str) -> Result<session::BridgeSessionSnapshot, BridgeResponse> {
+    match session::load_session() {
+        Ok(Some(snapshot)) => Ok(snapshot),
+        Ok(None) => Err(BridgeResponse::error(
+            id.to_string(),
+            "APP_LOCKED",
+            "Desktop app has no published unlocked bridge session.",
+        )),
+        Err(error) => Err(BridgeResponse::error(
+            id.to_string(),
+            "SESSION_STORE_ERROR",
+            format!("Failed to load bridge session: {error}"),
+        )),
+    }
+}
+
 fn respond_status(id: String, pairing_status: PairingStatus, extension_id: &str) -> BridgeResponse {
     let session_snapshot = match session::load_session() {
         Ok(value) => value,
@@ -242,8 +385,43 @@ fn respond_search(id: String, payload: serde_json::Value) -> BridgeResponse {
     BridgeResponse::success(id, results)
 }
 
+fn respond_search_entries(id: String, payload: serde_json::Value) -> BridgeResponse {
+    let payload = match serde_json::from_value::<SearchEntriesPayload>(payload) {
+        Ok(value) => value,
+        Err(error) => {
+            return BridgeResponse::error(
+                id,
+                "INVALID_PAYLOAD",
+                format!("searchEntries payload is invalid: {error}"),
+            )
+        }
+    };
+
+    let session_snapshot = match session::load_session() {
+        Ok(Some(snapshot)) => snapshot,
+        Ok(None) => {
+            return BridgeResponse::error(
+                id,
+                "APP_LOCKED",
+                "Desktop app has no published unlocked bridge session.",
+            )
+        }
+        Err(error) => {
+            return BridgeResponse::error(
+                id,
+                "SESSION_STORE_ERROR",
+                format!("Failed to load bridge session: {error}"),
+            )
+        }
+    };
+
+    let results: Vec<SearchEntrySuggestion> =
+        vault::search_entries(&session_snapshot.vault, &payload.query, payload.limit);
+    BridgeResponse::success(id, results)
+}
+
 fn respond_fill(id: String, payload: serde_json::Value) -> BridgeResponse {
-    let payload = match serde_json::from_value::<FillPayload>(payload) {
+    let payload = match serde_json::from_value::<OriginFillPayload>(payload) {
         Ok(value) => value,
         Err(error) => {
             return BridgeResponse::error(
@@ -272,10 +450,145 @@ fn respond_fill(id: String, payload: serde_json::Value) -> BridgeResponse {
         }
     };
 
-    let Some(result): Option<FillDataResult> =
-        vault::fill_data_for_entry(&session_snapshot.vault, &payload.entry_id)
+    let result: FillDataResult = match vault::fill_data_for_origin(
+        &session_snapshot.vault,
+        &payload.entry_id,
+        &payload.origin,
+    ) {
+        Ok(result) => result,
+        Err(refusal) => return BridgeResponse::error(id, refusal.code(), refusal.message()),
+    };
+
+    BridgeResponse::success(id, result)
+}
+
+fn respond_http_auth(id: String, payload: serde_json::Value) -> BridgeResponse {
+    let challenge = match serde_json::from_value::<HttpAuthChallenge>(payload) {
+        Ok(value) => value,
+        Err(error) => {
+            return BridgeResponse::error(
+                id,
+                "INVALID_PAYLOAD",
+                format!("getHttpAuthCredentials payload is invalid: {error}"),
+            )
+        }
+    };
+
+    if !matches!(
+        challenge.scheme.to_ascii_lowercase().as_str(),
+        "basic" | "digest"
+    ) {
+        return BridgeResponse::error(
+            id,
+            "INVALID_PAYLOAD",
+            format!("Unsupported HTTP authentication scheme: {}", challenge.scheme),
+        );
+    }
+
+    let session_snapshot = match load_unlocked_session(&id) {
+        Ok(snapshot) => snapshot,
+        Err(response) => return response,
+    };
+
+    let results: Vec<HttpAuthCredential> =
+        vault::http_auth_credentials(&session_snapshot.vault, &challenge);
+    BridgeResponse::success(id, results)
+}
+
+fn respond_search_cards(id: String, payload: serde_json::Value) -> BridgeResponse {
+    let payload = match serde_json::from_value::<OptionalQueryPayload>(payload) {
+        Ok(value) => value,
+        Err(error) => {
+            return BridgeResponse::error(
+                id,
+                "INVALID_PAYLOAD",
+                format!("searchCards payload is invalid: {error}"),
+            )
+        }
+    };
+
+    let session_snapshot = match load_unlocked_session(&id) {
+        Ok(snapshot) => snapshot,
+        Err(response) => return response,
+    };
+
+    BridgeResponse::success(
+        id,
+        autofill::search_cards(&session_snapshot.vault, &payload.query, payload.limit),
+    )
+}
+
+fn respond_card_fill(id: String, payload: serde_json::Value) -> BridgeResponse {
+    let payload = match serde_json::from_value::<CardFillPayload>(payload) {
+        Ok(value) => value,
+        Err(error) => {
+            return BridgeResponse::error(
+                id,
+                "INVALID_PAYLOAD",
+                format!("getCardFillData payload is invalid: {error}"),
+            )
+        }
+    };
+
+    let session_snapshot = match load_unlocked_session(&id) {
+        Ok(snapshot) => snapshot,
+        Err(response) => return response,
+    };
+
+    let Some(result) = autofill::card_fill_data(
+        &session_snapshot.vault,
+        &payload.entry_id,
+        payload.module_id.as_deref(),
+    ) else {
+        return BridgeResponse::error(id, "ENTRY_NOT_FOUND", "Card could not be resolved.");
+    };
+
+    BridgeResponse::success(id, result)
+}
+
+fn respond_search_identities(id: String, payload: serde_json::Value) -> BridgeResponse {
+    let payload = match serde_json::from_value::<OptionalQueryPayload>(payload) {
+        Ok(value) => value,
+        Err(error) => {
+            return BridgeResponse::error(
+                id,
+                "INVALID_PAYLOAD",
+                format!("searchIdentities payload is invalid: {error}"),
+            )
+        }
+    };
+
+    let session_snapshot = match load_unlocked_session(&id) {
+        Ok(snapshot) => snapshot,
+        Err(response) => return response,
+    };
+
+    BridgeResponse::success(
+        id,
+        autofill::search_identities(&session_snapshot.vault, &payload.query, payload.limit),
+    )
+}
+
+fn respond_identity_fill(id: String, payload: serde_json::Value) -> BridgeResponse {
+    let payload = match serde_json::from_value::<FillPayload>(payload) {
+        Ok(value) => value,
+        Err(error) => {
+            return BridgeResponse::error(
+                id,
+                "INVALID_PAYLOAD",
+                format!("getIdentityFillData payload is invalid: {error}"),
+            )
+        }
+    };
+
+    let session_snapshot = match load_unlocked_session(&id) {
+        Ok(snapshot) => snapshot,
+        Err(response) => return response,
+    };
+
+    let Some(result) = autofill::identity_fill_data(&session_snapshot.vault, &payload.entry_id)
     else {
-        return BridgeResponse::error(id, "ENTRY_NOT_FOUND", "Entry could not be resolved.");
+        return BridgeResponse::error(id, "ENTRY_NOT_FOUND", "Identity could not be resolved.");
     };
 
     BridgeResponse::success(id, result)
@@ -341,6 +654,84 @@ fn respond_update(request: BridgeRequest) -> BridgeResponse {
     }
 }
 
+fn respond_entry_write<T: DeserializeOwned>(
+    request: BridgeRequest,
+    queue: fn(String, BridgeClientInfo, T) -> io::Result<write::BrowserWriteResult>,
+) -> BridgeResponse {
+    let payload = match serde_json::from_value::<T>(request.payload) {
+        Ok(value) => value,
+        Err(error) => {
+            return BridgeResponse::error(
+                request.id,
+                "INVALID_PAYLOAD",
+                format!("{} payload is invalid: {error}", request.command),
+            )
+        }
+    };
+
+    match queue(request.id.clone(), request.client, payload) {
+        Ok(result) => bridge_result_to_response(request.id, result),
+        Err(error) if error.kind() == io::ErrorKind::InvalidInput => {
+            BridgeResponse::error(request.id, "INVALID_PAYLOAD", error.to_string())
+        }
+        Err(error) if error.kind() == io::ErrorKind::TimedOut => BridgeResponse::error(
+            request.id,
+            "WRITE_TIMEOUT",
+            format!("Desktop app did not complete {} in time.", request.command),
+        ),
+        Err(error) => BridgeResponse::error(
+            request.id,
+            "WRITE_REQUEST_ERROR",
+            format!("Failed to queue {}: {error}", request.command),
+        ),
+    }
+}
+
+/// Seals a create or update into the locked-vault inbox instead of failing
+/// with `APP_LOCKED`. Other errors, and locked apps that never published an
+/// inbox key, keep the original `not_ready` response.
+fn respond_locked_write(request: BridgeRequest, not_ready: BridgeResponse) -> BridgeResponse {
+    let is_locked = not_ready
+        .error
+        .as_ref()
+        .is_some_and(|error| error.code == "APP_LOCKED");
+    if !is_locked {
+        return not_ready;
+    }
+
+    let queued = if request.command == "createEntryFromBrowser" {
+        serde_json::from_value::<CreateEntryFromBrowserPayload>(request.payload)
+            .map_err(|error| error.to_string())
+            .map(|payload| {
+                write::queue_locked_create_request(request.id.clone(), request.client, payload)
+            })
+    } else {
+        serde_json::from_value::<UpdateEntryFromBrowserPayload>(request.payload)
+            .map_err(|error| error.to_string())
+            .map(|payload| {
+                write::queue_locked_update_request(request.id.clone(), request.client, payload)
+            })
+    };
+
+    match queued {
+        Err(error) => BridgeResponse::error(
+            request.id,
+            "INVALID_PAYLOAD",
+            format!("{} payload is invalid: {error}", request.command),
+        ),
+        Ok(Ok(Some(result))) => bridge_result_to_response(request.id, result),
+        Ok(Ok(None)) => not_ready,
+        Ok(Err(error)) if error.kind() == io::ErrorKind::InvalidInput => {
+            BridgeResponse::error(request.id, "INVALID_PAYLOAD", error.to_string())
+        }
+        Ok(Err(error)) => BridgeResponse::error(
+            request.id,
+            "WRITE_REQUEST_ERROR",
+            format!("Failed to queue browser write while locked: {error}"),
+        ),
+    }
+}
+
 fn bridge_result_to_response(id: String, result: write::BrowserWriteResult) -> BridgeResponse {
     if result.ok {
         BridgeResponse {
@@ -353,7 +744,7 @@ fn bridge_result_to_response(id: String, result: write::BrowserWriteResult) -> B
         BridgeResponse {
             id,
             ok: false,
-            result: None,
+            result: result.result,
             error: Some(error),
         }
     } else {
@@ -376,7 +767,7 @@ fn read_frame<R: Read>(reader: &mut R) -> io::Result<Option<Vec<u8>>> {
     Ok(Some(payload))
 }
 
-fn write_frame<W: Write>(writer: &mut W, response: &BridgeResponse) -> io::Result<()> {
+fn write_frame<W: Write, T: Serialize>(writer: &mut W, response: &T) -> io::Result<()> {
     let json =
         serde_json::to_vec(response).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
     let length = json.len() as u32;
@@ -0,0 +1,279 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use crypto_box::{aead::OsRng, PublicKey, SecretKey};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::{
+    path::{inbox_public_key_path, inbox_store_path},
+    write::BrowserWriteRequest,
+};
+
+/// How long items that the current key cannot open are kept, in case the
+/// vault they were sealed for is unlocked again.
+const UNOPENED_RETENTION_MS: u64 = 14 * 24 * 60 * 60 * 1000;
+
+/// Public half of the inbox key. The secret half lives inside the vault, so
+/// sealed writes can only be opened after the app is unlocked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InboxPublicKey {
+    pub version: u32,
+    pub public_key: String,
+    pub updated_at_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SealedWrite {
+    id: String,
+    created_at_ms: u64,
+    sealed: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InboxStore {
+    #[serde(default)]
+    items: Vec<SealedWrite>,
+}
+
+/// Creates a new inbox secret key, base64 encoded, for the app to keep in the vault.
+pub fn generate_inbox_secret() -> String {
+    STANDARD.encode(SecretKey::generate(&mut OsRng).to_bytes())
+}
+
+/// Publishes the public half of `secret` for the native host. Does nothing if
+/// the same key is already published.
+pub fn publish_public_key(secret: &str) -> io::Result<()> {
+    let public_key = STANDARD.encode(parse_secret(secret)?.public_key().as_bytes());
+    let path = inbox_public_key_path()?;
+
+    if load_public_key(&path)?.is_some_and(|current| current.public_key == public_key) {
+        return Ok(());
+    }
+
+    write_json_atomically(
+        &path,
+        &InboxPublicKey {
+            version: 1,
+            public_key,
+            updated_at_ms: now_ms(),
+        },
+    )
+}
+
+/// Seals `request` to the published inbox key. Returns `false` if the app has
+/// never published a key, in which case nothing is stored.
+pub fn seal_request(request: &BrowserWriteRequest) -> io::Result<bool> {
+    let Some(published) = load_public_key(&inbox_public_key_path()?)? else {
+        return Ok(false);
+    };
+
+    let public_key = STANDARD
+        .decode(published.public_key)
+        .ok()
+        .and_then(|bytes| PublicKey::from_slice(&bytes).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid inbox public key"))?;
+    let plaintext =
+        serde_json::to_vec(request).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    let sealed = public_key
+        .seal(&mut OsRng, &plaintext)
+        .map_err(|_| io::Error::new(io::ErrorKind::Other, "Failed to seal inbox request"))?;
+
+    let path = inbox_store_path()?;
+    let mut store = load_store(&path)?;
+    store.items.retain(|item| item.id != request.id);
+    store.items.push(SealedWrite {
+        id: request.id.clone(),
+        created_at_ms: request.created_at_ms,
+        sealed: STANDARD.encode(sealed),
+    });
+    write_json_atomically(&path, &store)?;
+    Ok(true)
+}
+
+/// Opens every inbox item sealed to `secret`. Items sealed to another key,
+/// such as one that was rotated since, are skipped and dropped once they are
+/// older than the retention. Opened items stay in the inbox until
+/// `remove_sealed_requests`.
+pub fn open_sealed_requests(secret: &str) -> io::Result<Vec<BrowserWriteRequest>> {
+    let secret = parse_secret(secret)?;
+    let path = inbox_store_path()?;
+    let mut store = load_store(&path)?;
+
+    let before = store.items.len();
+    let opened = open_items(&secret, &mut store.items, now_ms());
+    if store.items.len() != before {
+        write_json_atomically(&path, &store)?;
+    }
+    Ok(opened)
+}
+
+pub fn remove_sealed_requests(ids: &[String]) -> io::Result<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let path = inbox_store_path()?;
+    let mut store = load_store(&path)?;
+    store.items.retain(|item| !ids.contains(&item.id));
+    write_json_atomically(&path, &store)
+}
+
+fn open_items(
+    secret: &SecretKey,
+    items: &mut Vec<SealedWrite>,
+    now: u64,
+) -> Vec<BrowserWriteRequest> {
+    let mut opened = Vec::new();
+    items.retain(|item| match open_item(secret, item) {
+        Some(request) => {
+            opened.push(request);
+            true
+        }
+        None => now.saturating_sub(item.created_at_ms) < UNOPENED_RETENTION_MS,
+    });
+    opened
+}
+
+fn open_item(secret: &SecretKey, item: &SealedWrite) -> Option<BrowserWriteRequest> {
+    let sealed = STANDARD.decode(&item.sealed).ok()?;
+    let plaintext = secret.unseal(&sealed).ok()?;
+    serde_json::from_slice(&plaintext).ok()
+}
+
+fn parse_secret(secret: &str) -> io::Result<SecretKey> {
+    STANDARD
+        .decode(secret.trim())
+        .ok()
+        .and_then(|bytes| SecretKey::from_slice(&bytes).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid inbox secret key"))
+}
+
+fn load_public_key(path: &PathBuf) -> io::Result<Option<InboxPublicKey>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str::<InboxPublicKey>(&content)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn load_store(path: &PathBuf) -> io::Result<InboxStore> {
+    if !path.exists() {
+        return Ok(InboxStore::default());
+    }
+
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str::<InboxStore>(&content)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn write_json_atomically<T: Serialize>(path: &PathBuf, value: &T) -> io::Result<()> {
+    let temp = path.with_extension("tmp");
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    fs::write(&temp, json)?;
+    fs::rename(temp, path)?;
+    Ok(())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        generate_inbox_secret, open_item, open_items, parse_secret, SealedWrite, STANDARD,
+        UNOPENED_RETENTION_MS,
+    };
+    use crate::bridge::{
+        protocol::BridgeClientInfo,
+        write::{BrowserWriteKind, BrowserWriteRequest, BrowserWriteState},
+    };
+    use base64::Engine as _;
+    use crypto_box::aead::OsRng;
+    use serde_json::json;
+
+    fn sealed_item(secret: &str, request: &BrowserWriteRequest) -> SealedWrite {
+        let public_key = parse_secret(secret).unwrap().public_key();
+        let sealed = public_key
+            .seal(&mut OsRng, &serde_json::to_vec(request).unwrap())
+            .unwrap();
+        SealedWrite {
+            id: request.id.clone(),
+            created_at_ms: request.created_at_ms,
+            sealed: STANDARD.encode(sealed),
+        }
+    }
+
+    fn request(id: &str) -> BrowserWriteRequest {
+        BrowserWriteRequest {
+            id: id.into(),
+            created_at_ms: 1,
+            kind: BrowserWriteKind::CreateEntryFromBrowser,
+            client: BridgeClientInfo {
+                extension_id: "ext".into(),
+                name: None,
+                version: None,
+                instance_id: None,
+            },
+            payload: json!({ "title": "Example", "password": "secret" }),
+            claimed_at_ms: None,
+            claimed_by_pid: None,
+            queued_while_locked: true,
+            state: BrowserWriteState::Queued,
+            completed_at_ms: None,
+            expires_at_ms: None,
+            confirmed_at_ms: None,
+        }
+    }
+
+    #[test]
+    fn sealed_requests_open_only_with_the_matching_secret() {
+        let secret = generate_inbox_secret();
+        let other = generate_inbox_secret();
+        let request = request("req-1");
+
+        let item = sealed_item(&secret, &request);
+        assert!(open_item(&parse_secret(&other).unwrap(), &item).is_none());
+
+        let opened = open_item(&parse_secret(&secret).unwrap(), &item).expect("opened");
+        assert_eq!(opened.id, "req-1");
+        assert_eq!(opened.payload["password"], "secret");
+    }
+
+    #[test]
+    fn items_for_another_key_are_dropped_after_the_retention() {
+        let secret = generate_inbox_secret();
+        let rotated = generate_inbox_secret();
+        let mut items = vec![
+            sealed_item(&secret, &request("current")),
+            sealed_item(&rotated, &request("rotated")),
+        ];
+
+        let opened = open_items(&parse_secret(&secret).unwrap(), &mut items, 2);
+        assert_eq!(opened.len(), 1);
+        assert_eq!(items.len(), 2);
+
+        let later = 1 + UNOPENED_RETENTION_MS;
+        open_items(&parse_secret(&secret).unwrap(), &mut items, later);
+        assert_eq!(
+            items
+                .iter()
+                .map(|item| item.id.as_str())
+                .collect::<Vec<_>>(),
+            ["current"]
+        );
+    }
+}
@@ -0,0 +1,688 @@
+//! KeePassXC-Browser compatibility for the native host. The extension talks
+//! to `org.keepassxc.keepassxc_browser`; when that manifest points at the
+//! ClavisPass host, its messages are answered here instead of by the
+//! ClavisPass protocol in `host`.
+//!
+//! After `change-public-keys` every message is a NaCl box (X25519,
+//! XSalsa20-Poly1305) between the extension's key and a key pair created for
+//! the connection. Replies use the request nonce incremented by one.
+//! Associations are pairings of the `keepassxc-browser` client whose instance
+//! id is derived from the association key, so they are approved, listed and
+//! removed like any other browser.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use crypto_box::{
+    aead::{generic_array::GenericArray, rand_core::RngCore, Aead, OsRng},
+    PublicKey, SalsaBox, SecretKey,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::{
+    io, thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::{
+    pairing::{self, PairingStatus},
+    protocol::{BridgeClientInfo, HOST_NAME},
+    session,
+    vault::{self, FillOrigin, VaultData},
+    write::{self, CreateEntryFromBrowserPayload, UpdateEntryFromBrowserPayload},
+};
+
+pub const KEEPASSXC_EXTENSION_ID: &str = "keepassxc-browser";
+
+/// Reported as the KeePassXC version; the extension enables features by it.
+const COMPATIBLE_VERSION: &str = "2.7.9";
+const NONCE_LEN: usize = 24;
+const ASSOCIATE_TIMEOUT_MS: u64 = 60_000;
+const GENERATED_PASSWORD_LEN: usize = 24;
+const PASSWORD_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&*+-=?@_~";
+
+/// Error codes of the KeePassXC-Browser protocol that the adapter reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeePassXcError {
+    DatabaseNotOpened = 1,
+    ClientPublicKeyNotReceived = 3,
+    CannotDecryptMessage = 4,
+    TimeoutOrNotConnected = 5,
+    ActionCancelledOrDenied = 6,
+    CannotEncryptMessage = 7,
+    AssociationFailed = 8,
+    KeyChangeFailed = 9,
+    IncorrectAction = 12,
+    EmptyMessageReceived = 13,
+    NoUrlProvided = 14,
+    NoLoginsFound = 15,
+}
+
+impl KeePassXcError {
+    fn message(self) -> &'static str {
+        match self {
+            KeePassXcError::DatabaseNotOpened => "Database not opened",
+            KeePassXcError::ClientPublicKeyNotReceived => "Client public key not received",
+            KeePassXcError::CannotDecryptMessage => "Cannot decrypt message",
+            KeePassXcError::TimeoutOrNotConnected => "Timeout or cannot connect to ClavisPass",
+            KeePassXcError::ActionCancelledOrDenied => "Action cancelled or denied",
+            KeePassXcError::CannotEncryptMessage => "Message encryption failed.",
+            KeePassXcError::AssociationFailed => "ClavisPass association failed, try again",
+            KeePassXcError::KeyChangeFailed => "Key exchange was not successful",
+            KeePassXcError::IncorrectAction => "Incorrect action",
+            KeePassXcError::EmptyMessageReceived => "Empty message received",
+            KeePassXcError::NoUrlProvided => "No URL provided",
+            KeePassXcError::NoLoginsFound => "No logins found",
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+    #[serde(default)]
+    action: String,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    nonce: Option<String>,
+    #[serde(default, rename = "clientID")]
+    client_id: Option<String>,
+    #[serde(default)]
+    public_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AssociatePayload {
+    key: String,
+    id_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssociationKey {
+    id: String,
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GetLoginsPayload {
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    keys: Vec<AssociationKey>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SetLoginPayload {
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    login: Option<String>,
+    password: String,
+    #[serde(default)]
+    uuid: Option<String>,
+}
+
+/// State of one native messaging connection.
+pub struct KeePassXcSession {
+    secret_key: SecretKey,
+    client_key: Option<PublicKey>,
+    client_id: Option<String>,
+    /// Association the extension proved it holds on this connection.
+    associated_id: Option<String>,
+}
+
+impl Default for KeePassXcSession {
+    fn default() -> Self {
+        Self {
+            secret_key: SecretKey::generate(&mut OsRng),
+            client_key: None,
+            client_id: None,
+            associated_id: None,
+        }
+    }
+}
+
+impl KeePassXcSession {
+    /// Answers one KeePassXC-Browser message.
+    pub fn handle_message(&mut self, message: Value) -> Value {
+        let envelope = serde_json::from_value::<Envelope>(message).unwrap_or_default();
+        if envelope.action.is_empty() {
+            return error_reply("", KeePassXcError::EmptyMessageReceived);
+        }
+        if envelope.action == "change-public-keys" {
+            return self.change_public_keys(&envelope);
+        }
+
+        let Some(client_key) = self.client_key.clone() else {
+            return error_reply(&envelope.action, KeePassXcError::ClientPublicKeyNotReceived);
+        };
+        let Some(nonce) = envelope.nonce.as_deref().and_then(decode_nonce) else {
+            return error_reply(&envelope.action, KeePassXcError::CannotDecryptMessage);
+        };
+        let Some(request) = envelope
+            .message
+            .as_deref()
+            .and_then(|message| self.open(&client_key, &nonce, message))
+        else {
+            return error_reply(&envelope.action, KeePassXcError::CannotDecryptMessage);
+        };
+
+        let result = match envelope.action.as_str() {
+            "get-databasehash" => database_hash().map(|hash| json!({ "hash": hash })),
+            "associate" => self.associate(request),
+            "test-associate" => self.test_associate(request),
+            "get-logins" => self.get_logins(request),
+            "set-login" => self.set_login(request),
+            "generate-password" => Ok(generate_password()),
+            _ => Err(KeePassXcError::IncorrectAction),
+        };
+
+        match result {
+            Ok(reply) => self
+                .seal_reply(&envelope.action, &client_key, &nonce, reply)
+                .unwrap_or_else(|error| error_reply(&envelope.action, error)),
+            Err(error) => error_reply(&envelope.action, error),
+        }
+    }
+
+    /// Starts a new key exchange. The connection gets a fresh key pair, so
+    /// replies to the previous client key can no longer be read.
+    fn change_public_keys(&mut self, envelope: &Envelope) -> Value {
+        let client_key = envelope.public_key.as_deref().and_then(decode_public_key);
+        let nonce = envelope.nonce.as_deref().and_then(decode_nonce);
+        let (Some(client_key), Some(nonce)) = (client_key, nonce) else {
+            return error_reply(&envelope.action, KeePassXcError::KeyChangeFailed);
+        };
+
+        self.secret_key = SecretKey::generate(&mut OsRng);
+        self.client_key = Some(client_key);
+        self.client_id = envelope.client_id.clone();
+        self.associated_id = None;
+
+        json!({
+            "action": envelope.action,
+            "version": COMPATIBLE_VERSION,
+            "publicKey": STANDARD.encode(self.secret_key.public_key().as_bytes()),
+            "nonce": STANDARD.encode(increment_nonce(&nonce)),
+            "success": "true",
+        })
+    }
+
+    /// Creates a pairing request for the association key and waits until
+    /// the user approves or rejects it in the app.
+    fn associate(&mut self, request: Value) -> Result<Value, KeePassXcError> {
+        let payload = serde_json::from_value::<AssociatePayload>(request)
+            .map_err(|_| KeePassXcError::AssociationFailed)?;
+        let session_key = self
+            .client_key
+            .as_ref()
+            .map(|key| STANDARD.encode(key.as_bytes()));
+        if session_key.as_deref() != Some(payload.key.as_str()) {
+            return Err(KeePassXcError::AssociationFailed);
+        }
+        let id_key = decode_public_key(&payload.id_key).ok_or(KeePassXcError::AssociationFailed)?;
+        let id = association_id(&id_key);
+
+        match await_pairing(&client_info(&id)) {
+            Ok(PairingStatus::Paired) => {
+                self.associated_id = Some(id.clone());
+                Ok(json!({ "id": id, "hash": database_hash()? }))
+            }
+            Ok(PairingStatus::Pending) => Err(KeePassXcError::TimeoutOrNotConnected),
+            Ok(PairingStatus::Unpaired) => Err(KeePassXcError::ActionCancelledOrDenied),
+            Err(error) => {
+                eprintln!("KeePassXC-Browser association failed: {error}");
+                Err(KeePassXcError::AssociationFailed)
+            }
+        }
+    }
+
+    fn test_associate(&mut self, request: Value) -> Result<Value, KeePassXcError> {
+        let key = serde_json::from_value::<AssociationKey>(request)
+            .map_err(|_| KeePassXcError::AssociationFailed)?;
+        self.verify_associations(std::slice::from_ref(&key))?;
+        Ok(json!({ "id": key.id, "hash": database_hash()? }))
+    }
+
+    fn get_logins(&mut self, request: Value) -> Result<Value, KeePassXcError> {
+        let payload = serde_json::from_value::<GetLoginsPayload>(request)
+            .map_err(|_| KeePassXcError::CannotDecryptMessage)?;
+        self.verify_associations(&payload.keys)?;
+        let url = payload
+            .url
+            .filter(|url| !url.trim().is_empty())
+            .ok_or(KeePassXcError::NoUrlProvided)?;
+
+        let snapshot = unlocked_session()?;
+        let entries = logins_for_url(&snapshot.vault, &url);
+        if entries.is_empty() {
+            return Err(KeePassXcError::NoLoginsFound);
+        }
+        Ok(json!({
+            "count": entries.len(),
+            "entries": entries,
+            "hash": database_hash()?,
+        }))
+    }
+
+    /// Saves through the same queue as `createEntryFromBrowser` and
+    /// `updateEntryFromBrowser`. The extension already asked whether to
+    /// update or add, so new logins skip the duplicate check.
+    fn set_login(&mut self, request: Value) -> Result<Value, KeePassXcError> {
+        let Some(id) = self.associated_id.clone() else {
+            return Err(KeePassXcError::AssociationFailed);
+        };
+        let payload = serde_json::from_value::<SetLoginPayload>(request)
+            .map_err(|_| KeePassXcError::CannotDecryptMessage)?;
+        let url = payload
+            .url
+            .filter(|url| !url.trim().is_empty())
+            .ok_or(KeePassXcError::NoUrlProvided)?;
+        unlocked_session()?;
+
+        let request_id = format!("keepassxc-{}", now_ms());
+        let matched_host = host_of(&url);
+        let username = payload.login.filter(|login| !login.is_empty());
+        let queued = match payload.uuid.filter(|uuid| !uuid.is_empty()) {
+            Some(entry_id) => write::queue_update_request(
+                request_id,
+                client_info(&id),
+                UpdateEntryFromBrowserPayload {
+                    entry_id,
+                    title: None,
+                    username,
+                    email: None,
+                    password: payload.password,
+                    url: None,
+                    matched_host,
+                    totp: None,
+                    last_updated: None,
+                },
+            ),
+            None => write::queue_create_request(
+                request_id,
+                client_info(&id),
+                CreateEntryFromBrowserPayload {
+                    title: matched_host.clone().unwrap_or_else(|| url.clone()),
+                    username,
+                    email: None,
+                    password: payload.password,
+                    url: Some(url),
+                    matched_host,
+                    folder_id: None,
+                    totp: None,
+                    force: true,
+                },
+            ),
+        };
+
+        match queued {
+            Ok(result) if result.ok => Ok(json!({
+                "count": null,
+                "entries": null,
+                "error": "",
+                "hash": database_hash()?,
+            })),
+            Ok(_) => Err(KeePassXcError::ActionCancelledOrDenied),
+            Err(error) if error.kind() == io::ErrorKind::TimedOut => {
+                Err(KeePassXcError::TimeoutOrNotConnected)
+            }
+            Err(error) => {
+                eprintln!("KeePassXC-Browser save failed: {error}");
+                Err(KeePassXcError::ActionCancelledOrDenied)
+            }
+        }
+    }
+
+    /// Succeeds if any of `keys` is an approved association. Remembers it for
+    /// `set-login`, which only sends the association id.
+    fn verify_associations(&mut self, keys: &[AssociationKey]) -> Result<(), KeePassXcError> {
+        let paired = pairing::list_paired_clients().map_err(|error| {
+            eprintln!("KeePassXC-Browser could not read pairings: {error}");
+            KeePassXcError::AssociationFailed
+        })?;
+
+        let approved = keys.iter().find(|candidate| {
+            decode_public_key(&candidate.key)
+                .is_some_and(|key| association_id(&key) == candidate.id)
+                && paired.iter().any(|peer| {
+                    peer.extension_id == KEEPASSXC_EXTENSION_ID
+                        && peer.client_instance_id.as_deref() == Some(candidate.id.as_str())
+                })
+        });
+        let approved = approved.ok_or(KeePassXcError::AssociationFailed)?;
+        self.associated_id = Some(approved.id.clone());
+        Ok(())
+    }
+
+    fn open(
+        &self,
+        client_key: &PublicKey,
+        nonce: &[u8; NONCE_LEN],
+        message: &str,
+    ) -> Option<Value> {
+        let ciphertext = STANDARD.decode(message).ok()?;
+        let plaintext = SalsaBox::new(client_key, &self.secret_key)
+            .decrypt(GenericArray::from_slice(nonce), ciphertext.as_slice())
+            .ok()?;
+        serde_json::from_slice(&plaintext).ok()
+    }
+
+    fn seal_reply(
+        &self,
+        action: &str,
+        client_key: &PublicKey,
+        nonce: &[u8; NONCE_LEN],
+        mut reply: Value,
+    ) -> Result<Value, KeePassXcError> {
+        let nonce = increment_nonce(nonce);
+        let encoded_nonce = STANDARD.encode(nonce);
+        if let Some(fields) = reply.as_object_mut() {
+            fields.insert("version".into(), COMPATIBLE_VERSION.into());
+            fields.insert("success".into(), "true".into());
+            fields.insert("nonce".into(), encoded_nonce.clone().into());
+        }
+
+        let plaintext =
+            serde_json::to_vec(&reply).map_err(|_| KeePassXcError::CannotEncryptMessage)?;
+        let ciphertext = SalsaBox::new(client_key, &self.secret_key)
+            .encrypt(GenericArray::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| KeePassXcError::CannotEncryptMessage)?;
+
+        let mut sealed = json!({
+            "action": action,
+            "message": STANDARD.encode(ciphertext),
+            "nonce": encoded_nonce,
+        });
+        if let Some(client_id) = &self.client_id {
+            sealed["clientID"] = client_id.clone().into();
+        }
+        Ok(sealed)
+    }
+}
+
+/// Logins in the shape of KeePassXC entries, for entries that may fill `url`.
+fn logins_for_url(vault: &VaultData, url: &str) -> Vec<Value> {
+    let origin = FillOrigin {
+        top_level_origin: Some(url.to_string()),
+        frame_origin: None,
+    };
+
+    vault::search_entries_by_domain(vault, url)
+        .into_iter()
+        .filter_map(|suggestion| {
+            let fill = vault::fill_data_for_origin(vault, &suggestion.entry_id, &origin).ok()?;
+            let group = vault
+                .values
+                .iter()
+                .find(|entry| entry.id == fill.entry_id)
+                .and_then(|entry| entry.folder.as_ref())
+                .map(|folder| folder.name.clone())
+                .unwrap_or_default();
+            Some(json!({
+                "uuid": fill.entry_id,
+                "name": fill.title,
+                "login": fill.username.unwrap_or_default(),
+                "password": fill.password,
+                "group": group,
+                "expired": "false",
+                "stringFields": [],
+            }))
+        })
+        .collect()
+}
+
+/// Older extensions read the password from `entries`, newer ones from
+/// `password`.
+fn generate_password() -> Value {
+    let mut password = String::with_capacity(GENERATED_PASSWORD_LEN);
+    let limit = u8::MAX - u8::MAX % PASSWORD_ALPHABET.len() as u8;
+    while password.len() < GENERATED_PASSWORD_LEN {
+        let mut byte = [0u8; 1];
+        OsRng.fill_bytes(&mut byte);
+        if byte[0] < limit {
+            password.push(PASSWORD_ALPHABET[byte[0] as usize % PASSWORD_ALPHABET.len()] as char);
+        }
+    }
+
+    let entropy = (GENERATED_PASSWORD_LEN as f64 * (PASSWORD_ALPHABET.len() as f64).log2()) as u32;
+    json!({
+        "password": password,
+        "entries": [{ "login": entropy, "password": password }],
+    })
+}
+
+/// Registers the association as a pending pairing if it is new, then polls
+/// the pairing store until the user decided or the timeout passed.
+fn await_pairing(client: &BridgeClientInfo) -> io::Result<PairingStatus> {
+    let deadline = now_ms() + ASSOCIATE_TIMEOUT_MS;
+    let mut status = pairing::evaluate_pairing(client)?;
+
+    while status == PairingStatus::Pending && now_ms() < deadline {
+        thread::sleep(Duration::from_millis(250));
+        let store = pairing::load_pairing_store()?;
+        let instance_id = client.instance_id.as_deref();
+        let is_client = |extension_id: &str, stored: Option<&str>| {
+            extension_id == client.extension_id && stored == instance_id
+        };
+        status = if store
+            .paired
+            .iter()
+            .any(|peer| is_client(&peer.extension_id, peer.client_instance_id.as_deref()))
+        {
+            PairingStatus::Paired
+        } else if store
+            .pending
+            .iter()
+            .any(|peer| is_client(&peer.extension_id, peer.client_instance_id.as_deref()))
+        {
+            PairingStatus::Pending
+        } else {
+            PairingStatus::Unpaired
+        };
+    }
+
+    Ok(status)
+}
+
+fn unlocked_session() -> Result<session::BridgeSessionSnapshot, KeePassXcError> {
+    match session::load_session() {
+        Ok(Some(snapshot)) => Ok(snapshot),
+        Ok(None) => Err(KeePassXcError::DatabaseNotOpened),
+        Err(error) => {
+            eprintln!("KeePassXC-Browser could not load the bridge session: {error}");
+            Err(KeePassXcError::DatabaseNotOpened)
+        }
+    }
+}
+
+/// The extension keys its associations by this hash. ClavisPass offers one
+/// vault through the bridge, so it does not change between vaults.
+fn database_hash() -> Result<String, KeePassXcError> {
+    unlocked_session()?;
+    Ok(hex_sha256(HOST_NAME.as_bytes()))
+}
+
+fn association_id(id_key: &PublicKey) -> String {
+    hex_sha256(id_key.as_bytes())
+}
+
+fn client_info(association_id: &str) -> BridgeClientInfo {
+    BridgeClientInfo {
+        extension_id: KEEPASSXC_EXTENSION_ID.to_string(),
+        name: Some("KeePassXC-Browser".to_string()),
+        version: None,
+        instance_id: Some(association_id.to_string()),
+    }
+}
+
+fn error_reply(action: &str, error: KeePassXcError) -> Value {
+    json!({
+        "action": action,
+        "errorCode": (error as u8).to_string(),
+        "error": error.message(),
+    })
+}
+
+/// Little-endian increment with carry, like `sodium_increment`.
+fn increment_nonce(nonce: &[u8; NONCE_LEN]) -> [u8; NONCE_LEN] {
+    let mut next = *nonce;
+    for byte in next.iter_mut() {
+        let (value, overflow) = byte.overflowing_add(1);
+        *byte = value;
+        if !overflow {
+            break;
+        }
+    }
+    next
+}
+
+fn decode_nonce(value: &str) -> Option<[u8; NONCE_LEN]> {
+    STANDARD.decode(value).ok()?.try_into().ok()
+}
+
+fn decode_public_key(value: &str) -> Option<PublicKey> {
+    let bytes = STANDARD.decode(value).ok()?;
+    PublicKey::from_slice(&bytes).ok()
+}
+
+fn host_of(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default().to_lowercase();
+    (!host.is_empty()).then_some(host)
+}
+
+fn hex_sha256(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{increment_nonce, logins_for_url, KeePassXcSession, STANDARD};
+    use crate::bridge::vault::VaultData;
+    use base64::Engine as _;
+    use crypto_box::{
+        aead::{generic_array::GenericArray, Aead, OsRng},
+        PublicKey, SalsaBox, SecretKey,
+    };
+    use serde_json::{json, Value};
+
+    #[test]
+    fn increments_nonces_little_endian() {
+        let mut nonce = [0u8; 24];
+        nonce[0] = 0xff;
+        nonce[1] = 0x01;
+        let next = increment_nonce(&nonce);
+        assert_eq!(&next[..3], &[0x00, 0x02, 0x00]);
+        assert_eq!(increment_nonce(&[0xff; 24]), [0u8; 24]);
+    }
+
+    #[test]
+    fn exchanges_keys_and_answers_boxed_messages() {
+        let mut session = KeePassXcSession::default();
+        let client_secret = SecretKey::generate(&mut OsRng);
+        let nonce = [7u8; 24];
+
+        let reply = session.handle_message(json!({
+            "action": "change-public-keys",
+            "publicKey": STANDARD.encode(client_secret.public_key().as_bytes()),
+            "nonce": STANDARD.encode(nonce),
+            "clientID": "client-1",
+        }));
+        assert_eq!(reply["success"], "true");
+        assert_eq!(reply["nonce"], STANDARD.encode(increment_nonce(&nonce)));
+        let host_key = STANDARD
+            .decode(reply["publicKey"].as_str().unwrap())
+            .unwrap();
+        let shared = SalsaBox::new(&PublicKey::from_slice(&host_key).unwrap(), &client_secret);
+
+        let nonce = [9u8; 24];
+        let message = shared
+            .encrypt(
+                GenericArray::from_slice(&nonce),
+                br#"{"action":"generate-password"}"#.as_slice(),
+            )
+            .unwrap();
+        let reply = session.handle_message(json!({
+            "action": "generate-password",
+            "message": STANDARD.encode(message),
+            "nonce": STANDARD.encode(nonce),
+            "clientID": "client-1",
+        }));
+        assert_eq!(reply["clientID"], "client-1");
+        let reply_nonce = increment_nonce(&nonce);
+        assert_eq!(reply["nonce"], STANDARD.encode(reply_nonce));
+
+        let opened = shared
+            .decrypt(
+                GenericArray::from_slice(&reply_nonce),
+                STANDARD
+                    .decode(reply["message"].as_str().unwrap())
+                    .unwrap()
+                    .as_slice(),
+            )
+            .unwrap();
+        let opened = serde_json::from_slice::<Value>(&opened).unwrap();
+        assert_eq!(opened["success"], "true");
+        assert_eq!(opened["password"].as_str().unwrap().len(), 24);
+        assert_eq!(opened["entries"][0]["password"], opened["password"]);
+
+        let tampered = session.handle_message(json!({
+            "action": "get-logins",
+            "message": STANDARD.encode(b"not a box"),
+            "nonce": STANDARD.encode(nonce),
+        }));
+        assert_eq!(tampered["errorCode"], "4");
+    }
+
+    #[test]
+    fn lists_logins_that_may_fill_the_url() {
+        let vault = serde_json::from_value::<VaultData>(json!({
+            "values": [
+                {
+                    "id": "entry-1",
+                    "title": "Example",
+                    "folder": { "id": "folder-1", "name": "Work" },
+                    "modules": [
+                        { "module": "URL", "value": "https://example.com/login" },
+                        { "module": "USERNAME", "value": "alice" },
+                        { "module": "PASSWORD", "value": "secret" }
+                    ]
+                },
+                {
+                    "id": "entry-2",
+                    "title": "Other",
+                    "modules": [
+                        { "module": "URL", "value": "https://other.test" },
+                        { "module": "PASSWORD", "value": "other" }
+                    ]
+                }
+            ]
+        }))
+        .unwrap();
+
+        let logins = logins_for_url(&vault, "https://example.com/account");
+        assert_eq!(logins.len(), 1);
+        assert_eq!(logins[0]["uuid"], "entry-1");
+        assert_eq!(logins[0]["login"], "alice");
+        assert_eq!(logins[0]["password"], "secret");
+        assert_eq!(logins[0]["group"], "Work");
+        assert!(logins_for_url(&vault, "https://unknown.test").is_empty());
+    }
+}
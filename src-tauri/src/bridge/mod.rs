@@ -1,4 +1,8 @@
+pub mod autofill;
+pub mod history;
 pub mod host;
+pub mod inbox;
+pub mod keepassxc;
 pub mod pairing;
 pub mod path;
 pub mod protocol;
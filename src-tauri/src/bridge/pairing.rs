@@ -210,7 +210,13 @@ pub fn approve_pairing(
         capabilities: vec![
             "getStatus".to_string(),
             "searchEntriesByDomain".to_string(),
+            "searchEntries".to_string(),
             "getFillDataForEntry".to_string(),
+            "getHttpAuthCredentials".to_string(),
+            "searchCards".to_string(),
+            "getCardFillData".to_string(),
+            "searchIdentities".to_string(),
+            "getIdentityFillData".to_string(),
         ],
         shared_secret_id: None,
         public_key_fingerprint: None,
@@ -308,6 +314,48 @@ pub fn revoke_pairing(extension_id: &str, client_instance_id: Option<&str>) -> i
     Ok(changed)
 }
 
+/// Whether the paired client was granted `capability`. Capabilities beyond the
+/// defaults granted at pairing are opt-in via `set_client_capabilities`.
+pub fn has_capability(client: &BridgeClientInfo, capability: &str) -> io::Result<bool> {
+    let store = load_pairing_store()?;
+    Ok(store
+        .paired
+        .iter()
+        .find(|peer| {
+            same_client_identity(
+                peer.extension_id.as_str(),
+                peer.client_instance_id.as_deref(),
+                client,
+            )
+        })
+        .is_some_and(|peer| {
+            peer.capabilities
+                .iter()
+                .any(|granted| granted == capability)
+        }))
+}
+
+pub fn set_client_capabilities(
+    extension_id: &str,
+    client_instance_id: Option<&str>,
+    capabilities: Vec<String>,
+) -> io::Result<Option<PairedClient>> {
+    let mut store = load_pairing_store()?;
+    let Some(peer) = store.paired.iter_mut().find(|peer| {
+        peer.extension_id == extension_id
+            && optional_identity_matches(peer.client_instance_id.as_deref(), client_instance_id)
+    }) else {
+        return Ok(None);
+    };
+
+    peer.capabilities = capabilities;
+    peer.capabilities.sort();
+    peer.capabilities.dedup();
+    let updated = peer.clone();
+    save_pairing_store(&store)?;
+    Ok(Some(updated))
+}
+
 pub fn list_pending_pairings() -> io::Result<Vec<PendingPairing>> {
     Ok(load_pairing_store()?.pending)
 }
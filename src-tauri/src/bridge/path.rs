@@ -36,6 +36,49 @@ pub fn write_result_store_path() -> io::Result<PathBuf> {
     Ok(bridge_dir()?.join("browser-write-results.json"))
 }
 
+pub fn password_history_settings_path() -> io::Result<PathBuf> {
+    Ok(bridge_dir()?.join("password-history.json"))
+}
+
+pub fn inbox_public_key_path() -> io::Result<PathBuf> {
+    Ok(bridge_dir()?.join("inbox-public-key.json"))
+}
+
+pub fn inbox_store_path() -> io::Result<PathBuf> {
+    Ok(bridge_dir()?.join("browser-write-inbox.json"))
+}
+
+/// Unix socket of the local control channel used by the `clavispass` CLI.
+pub fn control_socket_path() -> io::Result<PathBuf> {
+    Ok(bridge_dir()?.join("control.sock"))
+}
+
+/// Unix socket of the SSH agent, for use as `SSH_AUTH_SOCK`.
+pub fn ssh_agent_socket_path() -> io::Result<PathBuf> {
+    Ok(bridge_dir()?.join("ssh-agent.sock"))
+}
+
+pub fn ssh_agent_settings_path() -> io::Result<PathBuf> {
+    Ok(bridge_dir()?.join("ssh-agent.json"))
+}
+
+pub fn secret_service_settings_path() -> io::Result<PathBuf> {
+    Ok(bridge_dir()?.join("secret-service.json"))
+}
+
+pub fn local_api_settings_path() -> io::Result<PathBuf> {
+    Ok(bridge_dir()?.join("local-api.json"))
+}
+
+pub fn local_api_token_store_path() -> io::Result<PathBuf> {
+    Ok(bridge_dir()?.join("local-api-tokens.json"))
+}
+
+/// One JSON record per line for every call to the local API.
+pub fn local_api_audit_log_path() -> io::Result<PathBuf> {
+    Ok(bridge_dir()?.join("local-api-audit.jsonl"))
+}
+
 fn ensure_dir(path: &Path) -> io::Result<()> {
     if !path.exists() {
         fs::create_dir_all(path)?;
@@ -19,7 +19,10 @@ pub struct BridgeSessionSnapshot {
     pub vault: VaultData,
 }
 
-pub fn publish_session(vault: VaultData) -> io::Result<BridgeSessionSnapshot> {
+pub fn publish_session(mut vault: VaultData) -> io::Result<BridgeSessionSnapshot> {
+    // Entries in the trash are never offered to the browser.
+    vault.values.retain(|entry| entry.deleted_at.is_none());
+
     let updated_at_ms = now_ms();
     let snapshot = BridgeSessionSnapshot {
         version: 1,
@@ -1,5 +1,17 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
+
+use super::history::PasswordHistoryItem;
+
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 100;
+const MIN_FUZZY_TERM_LENGTH: usize = 3;
+const FAVORITE_BONUS: u32 = 10;
+const HTTP_REALM_FIELD_NAME: &str = "httpRealm";
+const TITLE_WEIGHT: u32 = 5;
+const ACCOUNT_WEIGHT: u32 = 4;
+const URL_WEIGHT: u32 = 3;
+const FOLDER_WEIGHT: u32 = 2;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -22,6 +34,20 @@ pub struct VaultModule {
     pub wifi_name: Option<String>,
     #[serde(default, rename = "wifiType")]
     pub wifi_type: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl VaultModule {
+    /// Reads a non-empty string from the module-specific fields, e.g. the
+    /// `number` of a `CREDIT_CARD` or the `city` of an `ADDRESS` module.
+    pub fn field(&self, key: &str) -> Option<String> {
+        self.extra
+            .get(key)
+            .and_then(value_as_string)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -39,6 +65,13 @@ pub struct VaultEntry {
     pub folder: Option<FolderRef>,
     #[serde(default)]
     pub modules: Vec<VaultModule>,
+    #[serde(default)]
+    pub allow_cross_origin_fill: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub password_history: Vec<PasswordHistoryItem>,
+    /// Set when the entry was moved to the trash.
+    #[serde(default, rename = "deletedAt", skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -52,7 +85,7 @@ pub struct VaultData {
     pub values: Vec<VaultEntry>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchEntrySuggestion {
     pub entry_id: String,
@@ -66,8 +99,12 @@ pub struct SearchEntrySuggestion {
     pub email: Option<String>,
     pub has_password: bool,
     pub has_totp: bool,
+    pub has_custom_fields: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub matched_host: Option<String>,
+    /// Version marker to send back with `updateEntryFromBrowser`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -80,6 +117,85 @@ pub struct FillDataResult {
     pub password: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub totp: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub custom_fields: Vec<CustomFillField>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFillField {
+    pub title: String,
+    pub value: String,
+    /// Name or id of the form input this value belongs to, if the user set one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_type: Option<String>,
+}
+
+/// Where a fill is headed: the origin of the top-level page and, when the
+/// form lives in an iframe, the origin of that frame.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillOrigin {
+    #[serde(default)]
+    pub top_level_origin: Option<String>,
+    #[serde(default)]
+    pub frame_origin: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillRefusal {
+    EntryNotFound,
+    MissingOrigin,
+    OriginMismatch,
+    CrossOriginFrame,
+}
+
+impl FillRefusal {
+    pub fn code(self) -> &'static str {
+        match self {
+            FillRefusal::EntryNotFound => "ENTRY_NOT_FOUND",
+            FillRefusal::MissingOrigin => "ORIGIN_REQUIRED",
+            FillRefusal::OriginMismatch => "ORIGIN_MISMATCH",
+            FillRefusal::CrossOriginFrame => "CROSS_ORIGIN_FILL_BLOCKED",
+        }
+    }
+
+    pub fn message(self) -> &'static str {
+        match self {
+            FillRefusal::EntryNotFound => "Entry could not be resolved.",
+            FillRefusal::MissingOrigin => "The origin of the requesting page is required.",
+            FillRefusal::OriginMismatch => "Entry does not match the requesting origin.",
+            FillRefusal::CrossOriginFrame => {
+                "Entry does not allow filling into cross-origin frames."
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpAuthChallenge {
+    pub host: String,
+    #[serde(default)]
+    pub port: Option<u16>,
+    pub scheme: String,
+    #[serde(default)]
+    pub realm: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpAuthCredential {
+    pub entry_id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    pub password: String,
+    pub matched_host: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub realm: Option<String>,
 }
 
 pub fn search_entries_by_domain(vault: &VaultData, domain: &str) -> Vec<SearchEntrySuggestion> {
@@ -107,18 +223,175 @@ pub fn search_entries_by_domain(vault: &VaultData, domain: &str) -> Vec<SearchEn
             continue;
         }
 
+        matches.push((best_score, suggestion_for_entry(entry, matched_host)));
+    }
+
+    matches.sort_by(|left, right| {
+        right
+            .0
+            .cmp(&left.0)
+            .then_with(|| right.1.fav.cmp(&left.1.fav))
+            .then_with(|| left.1.title.to_lowercase().cmp(&right.1.title.to_lowercase()))
+    });
+
+    matches.into_iter().map(|(_, suggestion)| suggestion).collect()
+}
+
+/// Every entry of the vault, sorted by title.
+pub fn list_entries(vault: &VaultData) -> Vec<SearchEntrySuggestion> {
+    let mut entries = vault
+        .values
+        .iter()
+        .map(|entry| suggestion_for_entry(entry, None))
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|suggestion| suggestion.title.to_lowercase());
+    entries
+}
+
+pub fn search_entries(
+    vault: &VaultData,
+    query: &str,
+    limit: Option<usize>,
+) -> Vec<SearchEntrySuggestion> {
+    let limit = limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+    let terms = query
+        .split_whitespace()
+        .map(|term| term.to_lowercase())
+        .collect::<Vec<_>>();
+
+    if terms.is_empty() {
+        let mut favorites = vault
+            .values
+            .iter()
+            .filter(|entry| entry.fav)
+            .map(|entry| suggestion_for_entry(entry, None))
+            .collect::<Vec<_>>();
+        favorites.sort_by_key(|suggestion| suggestion.title.to_lowercase());
+        favorites.truncate(limit);
+        return favorites;
+    }
+
+    let mut matches = Vec::<(u32, SearchEntrySuggestion)>::new();
+
+    for entry in &vault.values {
+        let fields = search_fields(entry);
+        let mut total_score = 0u32;
+        let mut matched_host = None::<String>;
+
+        for term in &terms {
+            let mut best_score = 0u32;
+            let mut best_host = None::<&String>;
+
+            for field in &fields {
+                let score = field.weight * text_match_score(term, &field.text);
+                if score > best_score {
+                    best_score = score;
+                    best_host = field.host.as_ref();
+                }
+            }
+
+            if best_score == 0 {
+                total_score = 0;
+                break;
+            }
+
+            total_score += best_score;
+            if matched_host.is_none() {
+                matched_host = best_host.cloned();
+            }
+        }
+
+        if total_score == 0 {
+            continue;
+        }
+
+        if entry.fav {
+            total_score += FAVORITE_BONUS;
+        }
+
+        matches.push((total_score, suggestion_for_entry(entry, matched_host)));
+    }
+
+    matches.sort_by(|left, right| {
+        right
+            .0
+            .cmp(&left.0)
+            .then_with(|| right.1.fav.cmp(&left.1.fav))
+            .then_with(|| left.1.title.to_lowercase().cmp(&right.1.title.to_lowercase()))
+    });
+    matches.truncate(limit);
+
+    matches.into_iter().map(|(_, suggestion)| suggestion).collect()
+}
+
+/// Looks up credentials for an HTTP Basic/Digest authentication challenge.
+/// Only entries whose `URL` module points at exactly the challenged host are
+/// considered; an explicit port in the stored URL has to match as well. If an
+/// entry stores a realm it must equal the challenge realm, and such entries
+/// rank above entries without a stored realm.
+pub fn http_auth_credentials(
+    vault: &VaultData,
+    challenge: &HttpAuthChallenge,
+) -> Vec<HttpAuthCredential> {
+    let target_host = normalize_domain(&challenge.host);
+    if target_host.is_empty() {
+        return Vec::new();
+    }
+
+    let challenge_realm = challenge
+        .realm
+        .as_deref()
+        .map(str::trim)
+        .filter(|realm| !realm.is_empty());
+    let mut matches = Vec::<(u8, HttpAuthCredential)>::new();
+
+    for entry in &vault.values {
+        let Some(password) = first_string(entry, "PASSWORD") else {
+            continue;
+        };
+
+        let url_matches = entry
+            .modules
+            .iter()
+            .filter(|module| module.module == "URL")
+            .filter_map(|module| module.value.as_ref().and_then(value_as_string))
+            .any(|url| {
+                let (scheme, port) = url_scheme_and_port(&url);
+                if normalize_domain(&url) != target_host {
+                    return false;
+                }
+
+                match (port, challenge.port) {
+                    (Some(port), Some(requested)) => port == requested,
+                    (None, Some(requested)) => scheme
+                        .as_deref()
+                        .and_then(default_port)
+                        .map_or(requested == 80 || requested == 443, |port| port == requested),
+                    (_, None) => true,
+                }
+            });
+        if !url_matches {
+            continue;
+        }
+
+        let stored_realm = http_realm(entry);
+        let score = match (stored_realm.as_deref(), challenge_realm) {
+            (Some(stored), Some(requested)) if stored == requested => 2,
+            (Some(_), _) => continue,
+            (None, _) => 1,
+        };
+
         matches.push((
-            best_score,
-            SearchEntrySuggestion {
+            score,
+            HttpAuthCredential {
                 entry_id: entry.id.clone(),
                 title: entry.title.clone(),
-                fav: entry.fav,
-                folder_id: entry.folder.as_ref().map(|folder| folder.id.clone()),
-                username: first_string(entry, "USERNAME").or_else(|| wifi_name(entry)),
-                email: first_string(entry, "E_MAIL"),
-                has_password: password_for_entry(entry).is_some(),
-                has_totp: first_string(entry, "TOTP").is_some(),
-                matched_host,
+                username: first_string(entry, "USERNAME").or_else(|| first_string(entry, "E_MAIL")),
+                password,
+                matched_host: target_host.clone(),
+                realm: stored_realm,
             },
         ));
     }
@@ -127,11 +400,73 @@ pub fn search_entries_by_domain(vault: &VaultData, domain: &str) -> Vec<SearchEn
         right
             .0
             .cmp(&left.0)
-            .then_with(|| right.1.fav.cmp(&left.1.fav))
             .then_with(|| left.1.title.to_lowercase().cmp(&right.1.title.to_lowercase()))
     });
 
-    matches.into_iter().map(|(_, suggestion)| suggestion).collect()
+    matches.into_iter().map(|(_, credential)| credential).collect()
+}
+
+/// Finds entries that already hold a login for the same registrable domain
+/// and account name, so a browser save can offer updating one of them instead
+/// of creating a duplicate.
+pub fn find_duplicate_candidates(
+    vault: &VaultData,
+    url: &str,
+    usernames: &[&str],
+) -> Vec<SearchEntrySuggestion> {
+    let target = registrable_domain(&normalize_domain(url));
+    let usernames = usernames
+        .iter()
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .collect::<Vec<_>>();
+    if target.is_empty() || usernames.is_empty() {
+        return Vec::new();
+    }
+
+    vault
+        .values
+        .iter()
+        .filter_map(|entry| {
+            let matched_host = url_hosts(entry)
+                .into_iter()
+                .find(|host| registrable_domain(host) == target)?;
+            let same_account = ["USERNAME", "E_MAIL"]
+                .iter()
+                .filter_map(|module_name| first_string(entry, module_name))
+                .any(|value| usernames.contains(&value.trim().to_lowercase()));
+
+            same_account.then(|| suggestion_for_entry(entry, Some(matched_host)))
+        })
+        .collect()
+}
+
+/// Approximates the registrable domain (eTLD+1) of a normalized host. IP
+/// addresses and single-label hosts are returned unchanged.
+pub fn registrable_domain(host: &str) -> String {
+    const MULTI_LABEL_SUFFIXES: &[&str] = &[
+        "co.uk", "org.uk", "ac.uk", "gov.uk", "me.uk", "com.au", "net.au", "org.au", "co.nz",
+        "co.jp", "ne.jp", "or.jp", "com.br", "com.cn", "com.mx", "co.in", "co.za", "com.tr",
+        "co.kr", "com.sg", "co.at", "or.at", "github.io", "gitlab.io",
+    ];
+
+    let host = host.trim().trim_end_matches('.');
+    if host.parse::<std::net::IpAddr>().is_ok() {
+        return host.to_string();
+    }
+
+    let labels = host.split('.').collect::<Vec<_>>();
+    let suffix_labels = MULTI_LABEL_SUFFIXES
+        .iter()
+        .find(|suffix| domain_contains(host, suffix) && host != **suffix)
+        .map(|suffix| suffix.split('.').count())
+        .unwrap_or(1);
+
+    if labels.len() <= suffix_labels {
+        return host.to_string();
+    }
+
+    labels[labels.len() - suffix_labels - 1..].join(".")
 }
 
 pub fn fill_data_for_entry(vault: &VaultData, entry_id: &str) -> Option<FillDataResult> {
@@ -146,10 +481,264 @@ pub fn fill_data_for_entry(vault: &VaultData, entry_id: &str) -> Option<FillData
             .or_else(|| wifi_name(entry)),
         password,
         totp: first_string(entry, "TOTP"),
+        custom_fields: custom_fields(entry),
     })
 }
 
-fn url_hosts(entry: &VaultEntry) -> Vec<String> {
+/// Resolves fill data for an entry after checking it against the origin the
+/// data is going to. The entry has to match the frame origin (or the top-level
+/// origin for top-level fills) the same way `search_entries_by_domain` matches,
+/// and frames whose origin differs from the top-level page are refused unless
+/// the entry sets `allowCrossOriginFill`. Requests without a top-level origin
+/// are refused.
+pub fn fill_data_for_origin(
+    vault: &VaultData,
+    entry_id: &str,
+    origin: &FillOrigin,
+) -> Result<FillDataResult, FillRefusal> {
+    let entry = vault
+        .values
+        .iter()
+        .find(|entry| entry.id == entry_id)
+        .ok_or(FillRefusal::EntryNotFound)?;
+
+    if origin
+        .top_level_origin
+        .as_deref()
+        .map_or(true, |value| value.trim().is_empty())
+    {
+        return Err(FillRefusal::MissingOrigin);
+    }
+    let top_level = origin.top_level_origin.as_deref().and_then(parse_origin);
+    // Opaque frames (`null`, `about:blank`, ...) never share the page's origin.
+    let frame = match origin
+        .frame_origin
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+    {
+        Some(value) => Some(parse_origin(value).ok_or(FillRefusal::CrossOriginFrame)?),
+        None => None,
+    };
+
+    let Some((_, host, _)) = frame.as_ref().or(top_level.as_ref()) else {
+        return Err(FillRefusal::OriginMismatch);
+    };
+    let target = normalize_domain(host);
+    if !url_hosts(entry)
+        .iter()
+        .any(|candidate| match_domain_score(&target, candidate) > 0)
+    {
+        return Err(FillRefusal::OriginMismatch);
+    }
+
+    if let (Some(top_level), Some(frame)) = (&top_level, &frame) {
+        if top_level != frame && !entry.allow_cross_origin_fill {
+            return Err(FillRefusal::CrossOriginFrame);
+        }
+    }
+
+    fill_data_for_entry(vault, entry_id).ok_or(FillRefusal::EntryNotFound)
+}
+
+fn parse_origin(input: &str) -> Option<(String, String, Option<u16>)> {
+    let value = input.trim().to_lowercase();
+    let (scheme, rest) = value.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port.parse::<u16>().ok()?)),
+        None => (authority, None),
+    };
+    if host.is_empty() {
+        return None;
+    }
+
+    let port = port.or_else(|| default_port(scheme));
+    Some((scheme.to_string(), host.trim_end_matches('.').to_string(), port))
+}
+
+fn suggestion_for_entry(entry: &VaultEntry, matched_host: Option<String>) -> SearchEntrySuggestion {
+    SearchEntrySuggestion {
+        entry_id: entry.id.clone(),
+        title: entry.title.clone(),
+        fav: entry.fav,
+        folder_id: entry.folder.as_ref().map(|folder| folder.id.clone()),
+        username: first_string(entry, "USERNAME").or_else(|| wifi_name(entry)),
+        email: first_string(entry, "E_MAIL"),
+        has_password: password_for_entry(entry).is_some(),
+        has_totp: first_string(entry, "TOTP").is_some(),
+        has_custom_fields: !custom_fields(entry).is_empty(),
+        matched_host,
+        last_updated: entry.last_updated.clone(),
+    }
+}
+
+struct SearchField {
+    weight: u32,
+    text: String,
+    host: Option<String>,
+}
+
+impl SearchField {
+    fn new(weight: u32, text: &str) -> Self {
+        Self {
+            weight,
+            text: text.to_lowercase(),
+            host: None,
+        }
+    }
+}
+
+fn search_fields(entry: &VaultEntry) -> Vec<SearchField> {
+    let mut fields = vec![SearchField::new(TITLE_WEIGHT, &entry.title)];
+
+    for module in &entry.modules {
+        match module.module.as_str() {
+            "USERNAME" | "E_MAIL" => {
+                if let Some(value) = module.value.as_ref().and_then(value_as_string) {
+                    fields.push(SearchField::new(ACCOUNT_WEIGHT, &value));
+                }
+            }
+            "URL" => {
+                let host = module
+                    .value
+                    .as_ref()
+                    .and_then(value_as_string)
+                    .map(|value| normalize_domain(&value))
+                    .filter(|host| !host.is_empty());
+                if let Some(host) = host {
+                    fields.push(SearchField {
+                        weight: URL_WEIGHT,
+                        text: host.clone(),
+                        host: Some(host),
+                    });
+                }
+            }
+            "WIFI" => {
+                if let Some(name) = module.wifi_name.as_ref().filter(|name| !name.is_empty()) {
+                    fields.push(SearchField::new(ACCOUNT_WEIGHT, name));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(folder) = entry.folder.as_ref().filter(|folder| !folder.name.is_empty()) {
+        fields.push(SearchField::new(FOLDER_WEIGHT, &folder.name));
+    }
+
+    fields
+}
+
+/// Scores how well a lowercase `term` matches a lowercase `text`.
+/// Exact matches rank above prefixes, word prefixes, substrings and finally
+/// in-order subsequence ("fuzzy") matches. Returns 0 when nothing matches.
+pub(super) fn text_match_score(term: &str, text: &str) -> u32 {
+    if term.is_empty() || text.is_empty() {
+        return 0;
+    }
+
+    if text == term {
+        return 100;
+    }
+
+    if text.starts_with(term) {
+        return 80;
+    }
+
+    if text
+        .split(|ch: char| !ch.is_alphanumeric())
+        .any(|word| word.starts_with(term))
+    {
+        return 60;
+    }
+
+    if text.contains(term) {
+        return 40;
+    }
+
+    fuzzy_match_score(term, text)
+}
+
+fn fuzzy_match_score(term: &str, text: &str) -> u32 {
+    if term.chars().count() < MIN_FUZZY_TERM_LENGTH {
+        return 0;
+    }
+
+    let mut text_chars = text.chars().enumerate();
+    let mut first_index = None::<usize>;
+    let mut last_index = 0usize;
+
+    for needle in term.chars() {
+        let Some((index, _)) = text_chars.find(|(_, ch)| *ch == needle) else {
+            return 0;
+        };
+        first_index.get_or_insert(index);
+        last_index = index;
+    }
+
+    let span = last_index - first_index.unwrap_or(0) + 1;
+    let gaps = span.saturating_sub(term.chars().count()) as u32;
+    20u32.saturating_sub(gaps).max(5)
+}
+
+pub(crate) fn custom_fields(entry: &VaultEntry) -> Vec<CustomFillField> {
+    entry
+        .modules
+        .iter()
+        .filter(|module| module.module == "CUSTOM_FIELD")
+        .filter_map(|module| {
+            let title = module.title.as_deref().map(str::trim).unwrap_or_default();
+            let value = module.value.as_ref().and_then(value_as_string)?;
+            if title.is_empty() {
+                return None;
+            }
+
+            Some(CustomFillField {
+                title: title.to_string(),
+                value,
+                field_name: module.field("fieldName"),
+                input_type: module.field("inputType"),
+            })
+        })
+        .collect()
+}
+
+/// The realm an entry is bound to, stored as a custom field with the field
+/// name hint `httpRealm`.
+fn http_realm(entry: &VaultEntry) -> Option<String> {
+    custom_fields(entry)
+        .into_iter()
+        .find(|field| field.field_name.as_deref() == Some(HTTP_REALM_FIELD_NAME))
+        .map(|field| field.value.trim().to_string())
+        .filter(|realm| !realm.is_empty())
+}
+
+fn url_scheme_and_port(input: &str) -> (Option<String>, Option<u16>) {
+    let value = input.trim().to_lowercase();
+    let (scheme, rest) = match value.find("://") {
+        Some(index) => (Some(value[..index].to_string()), &value[(index + 3)..]),
+        None => (None, value.as_str()),
+    };
+    let authority = rest
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default();
+    let port = authority
+        .rsplit_once(':')
+        .and_then(|(_, port)| port.parse::<u16>().ok());
+
+    (scheme, port)
+}
+
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http" => Some(80),
+        "https" => Some(443),
+        _ => None,
+    }
+}
+
+pub(crate) fn url_hosts(entry: &VaultEntry) -> Vec<String> {
     entry
         .modules
         .iter()
@@ -161,7 +750,7 @@ fn url_hosts(entry: &VaultEntry) -> Vec<String> {
         .collect()
 }
 
-fn first_string(entry: &VaultEntry, module_name: &str) -> Option<String> {
+pub(crate) fn first_string(entry: &VaultEntry, module_name: &str) -> Option<String> {
     entry
         .modules
         .iter()
@@ -341,4 +930,267 @@ mod tests {
         assert_eq!(results[1].entry_id, "google");
         assert_eq!(results[1].matched_host.as_deref(), Some("accounts.google.com"));
     }
+
+    #[test]
+    fn fill_data_includes_custom_fields_with_field_name_hint() {
+        let mut entry = login_entry("bank", "Bank", "https://bank.example", "alice", false);
+        entry.modules.extend(
+            serde_json::from_value::<Vec<VaultModule>>(json!([
+                {
+                    "id": "c1",
+                    "module": "CUSTOM_FIELD",
+                    "title": "Customer number",
+                    "value": "123456",
+                    "fieldName": "customerNo"
+                },
+                {
+                    "id": "c2",
+                    "module": "CUSTOM_FIELD",
+                    "title": "Branch code",
+                    "value": "0815",
+                    "inputType": "secret"
+                },
+                { "id": "c3", "module": "CUSTOM_FIELD", "title": "Empty", "value": "" }
+            ]))
+            .unwrap(),
+        );
+        let vault = VaultData {
+            values: vec![entry],
+            ..Default::default()
+        };
+
+        let fill = fill_data_for_entry(&vault, "bank").unwrap();
+
+        assert_eq!(fill.custom_fields.len(), 2);
+        assert_eq!(fill.custom_fields[0].title, "Customer number");
+        assert_eq!(fill.custom_fields[0].field_name.as_deref(), Some("customerNo"));
+        assert_eq!(fill.custom_fields[1].field_name, None);
+        assert_eq!(fill.custom_fields[1].input_type.as_deref(), Some("secret"));
+        assert!(search_entries_by_domain(&vault, "bank.example")[0].has_custom_fields);
+    }
+
+    #[test]
+    fn http_auth_credentials_match_host_port_and_realm() {
+        let mut with_realm =
+            login_entry("realm", "Intranet Admin", "http://intranet.local:8080", "admin", false);
+        with_realm.modules.push(
+            serde_json::from_value(json!({
+                "id": "r",
+                "module": "CUSTOM_FIELD",
+                "title": "Realm",
+                "value": "Admin Area",
+                "fieldName": "httpRealm"
+            }))
+            .unwrap(),
+        );
+        let vault = VaultData {
+            values: vec![
+                login_entry("plain", "Intranet", "http://intranet.local:8080/wiki", "me", false),
+                with_realm,
+                login_entry("tls", "Intranet TLS", "https://intranet.local", "tls", false),
+                login_entry("other", "Other", "http://other.local:8080", "other", false),
+            ],
+            ..Default::default()
+        };
+        let challenge = |port: u16, realm: &str| HttpAuthChallenge {
+            host: "intranet.local".to_string(),
+            port: Some(port),
+            scheme: "basic".to_string(),
+            realm: Some(realm.to_string()),
+        };
+
+        let admin = http_auth_credentials(&vault, &challenge(8080, "Admin Area"));
+        let ids = admin.iter().map(|item| item.entry_id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["realm", "plain"]);
+        assert_eq!(admin[0].realm.as_deref(), Some("Admin Area"));
+
+        let wiki = http_auth_credentials(&vault, &challenge(8080, "Wiki"));
+        assert_eq!(wiki.len(), 1);
+        assert_eq!(wiki[0].entry_id, "plain");
+
+        let tls = http_auth_credentials(&vault, &challenge(443, "Wiki"));
+        assert_eq!(tls.len(), 1);
+        assert_eq!(tls[0].entry_id, "tls");
+    }
+
+    #[test]
+    fn fill_data_for_origin_checks_entry_against_requesting_origin() {
+        let vault = VaultData {
+            values: vec![login_entry("shop", "Shop", "https://shop.example", "alice", false)],
+            ..Default::default()
+        };
+        let origin = |top: Option<&str>, frame: Option<&str>| FillOrigin {
+            top_level_origin: top.map(str::to_string),
+            frame_origin: frame.map(str::to_string),
+        };
+
+        assert_eq!(
+            fill_data_for_origin(&vault, "shop", &FillOrigin::default()).err(),
+            Some(FillRefusal::MissingOrigin)
+        );
+        assert_eq!(
+            fill_data_for_origin(&vault, "shop", &origin(None, Some("https://shop.example"))).err(),
+            Some(FillRefusal::MissingOrigin)
+        );
+        assert!(fill_data_for_origin(&vault, "shop", &origin(Some("https://www.shop.example"), None)).is_ok());
+        assert!(fill_data_for_origin(
+            &vault,
+            "shop",
+            &origin(Some("https://shop.example"), Some("https://shop.example:443"))
+        )
+        .is_ok());
+        assert_eq!(
+            fill_data_for_origin(&vault, "shop", &origin(Some("https://evil.example"), None)).err(),
+            Some(FillRefusal::OriginMismatch)
+        );
+        assert_eq!(
+            fill_data_for_origin(
+                &vault,
+                "shop",
+                &origin(Some("https://shop.example"), Some("https://evil.example"))
+            )
+            .err(),
+            Some(FillRefusal::OriginMismatch)
+        );
+        assert_eq!(
+            fill_data_for_origin(&vault, "missing", &FillOrigin::default()).err(),
+            Some(FillRefusal::EntryNotFound)
+        );
+    }
+
+    #[test]
+    fn fill_data_for_origin_requires_opt_in_for_cross_origin_frames() {
+        let mut entry = login_entry("pay", "Payments", "https://pay.example", "alice", false);
+        let cross_origin = FillOrigin {
+            top_level_origin: Some("https://shop.example".to_string()),
+            frame_origin: Some("https://pay.example".to_string()),
+        };
+
+        let vault = VaultData {
+            values: vec![entry.clone()],
+            ..Default::default()
+        };
+        assert_eq!(
+            fill_data_for_origin(&vault, "pay", &cross_origin).err(),
+            Some(FillRefusal::CrossOriginFrame)
+        );
+
+        entry.allow_cross_origin_fill = true;
+        let vault = VaultData {
+            values: vec![entry],
+            ..Default::default()
+        };
+        assert!(fill_data_for_origin(&vault, "pay", &cross_origin).is_ok());
+
+        // A sandboxed frame on the entry's own page is still refused.
+        let opaque_frame = FillOrigin {
+            top_level_origin: Some("https://pay.example".to_string()),
+            frame_origin: Some("null".to_string()),
+        };
+        assert_eq!(
+            fill_data_for_origin(&vault, "pay", &opaque_frame).err(),
+            Some(FillRefusal::CrossOriginFrame)
+        );
+    }
+
+    #[test]
+    fn registrable_domain_handles_multi_label_suffixes() {
+        assert_eq!(registrable_domain("login.example.com"), "example.com");
+        assert_eq!(registrable_domain("shop.example.co.uk"), "example.co.uk");
+        assert_eq!(registrable_domain("example.co.uk"), "example.co.uk");
+        assert_eq!(registrable_domain("192.168.0.10"), "192.168.0.10");
+        assert_eq!(registrable_domain("localhost"), "localhost");
+    }
+
+    #[test]
+    fn find_duplicate_candidates_matches_registrable_domain_and_account() {
+        let mut by_email = login_entry("mail", "Example Mail", "https://mail.example.com", "", false);
+        by_email.modules.push(VaultModule {
+            module: "E_MAIL".to_string(),
+            value: Some(json!("Alice@Example.com")),
+            ..Default::default()
+        });
+        let vault = VaultData {
+            values: vec![
+                login_entry("same", "Example", "https://www.example.com", "alice", false),
+                login_entry("other-user", "Example", "https://example.com", "bob", false),
+                login_entry("other-site", "Elsewhere", "https://example.org", "alice", false),
+                by_email,
+            ],
+            ..Default::default()
+        };
+
+        let candidates =
+            find_duplicate_candidates(&vault, "https://login.example.com/signin", &["alice"]);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].entry_id, "same");
+
+        let candidates =
+            find_duplicate_candidates(&vault, "https://example.com", &["", "alice@example.com"]);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].entry_id, "mail");
+
+        assert!(find_duplicate_candidates(&vault, "https://example.com", &[]).is_empty());
+    }
+
+    #[test]
+    fn search_entries_ranks_title_prefix_above_fuzzy_and_folder_matches() {
+        let mut in_folder = login_entry("folder", "Router", "https://192.168.0.1", "admin", false);
+        in_folder.folder = Some(FolderRef {
+            id: "f1".to_string(),
+            name: "Github Work".to_string(),
+        });
+        let vault = VaultData {
+            values: vec![
+                login_entry("fuzzy", "Gitea Hub", "https://gitea.local", "me", false),
+                in_folder,
+                login_entry("prefix", "GitHub", "https://github.com", "octo", false),
+            ],
+            ..Default::default()
+        };
+
+        let results = search_entries(&vault, "github", None);
+
+        let ids = results.iter().map(|item| item.entry_id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["prefix", "folder", "fuzzy"]);
+        assert_eq!(results[0].matched_host, None);
+    }
+
+    #[test]
+    fn search_entries_requires_every_term_and_reports_matched_url_host() {
+        let vault = VaultData {
+            values: vec![
+                login_entry("work", "Mail", "https://mail.example.com", "alice", false),
+                login_entry("home", "Mail", "https://mail.home.net", "bob", false),
+            ],
+            ..Default::default()
+        };
+
+        let results = search_entries(&vault, "example ali", None);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry_id, "work");
+        assert_eq!(results[0].matched_host.as_deref(), Some("mail.example.com"));
+    }
+
+    #[test]
+    fn search_entries_prefers_favorites_and_applies_limit() {
+        let vault = VaultData {
+            values: vec![
+                login_entry("a", "Shop A", "https://a.example", "a", false),
+                login_entry("b", "Shop B", "https://b.example", "b", true),
+                login_entry("c", "Shop C", "https://c.example", "c", false),
+            ],
+            ..Default::default()
+        };
+
+        let results = search_entries(&vault, "shop", Some(2));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].entry_id, "b");
+        assert_eq!(results[1].entry_id, "a");
+
+        let favorites = search_entries(&vault, "  ", None);
+        assert_eq!(favorites.len(), 1);
+        assert_eq!(favorites[0].entry_id, "b");
+    }
 }
@@ -8,13 +8,22 @@ use std::{
 };
 
 use super::{
+    history::PasswordHistoryReport,
+    inbox,
     path::{write_request_store_path, write_result_store_path},
     protocol::{BridgeClientInfo, BridgeError},
+    session::load_session,
+    vault::{find_duplicate_candidates, SearchEntrySuggestion, VaultData},
 };
 
 const WRITE_TIMEOUT_MS: u64 = 12_000;
-const RESULT_RETENTION_LIMIT: usize = 100;
+const RESULT_RETENTION_MS: u64 = 24 * 60 * 60 * 1000;
 const CLAIM_STALE_AFTER_MS: u64 = 30_000;
+const REQUEST_EXPIRE_AFTER_MS: u64 = 5 * 60 * 1000;
+const CONFIRMATION_TIMEOUT_MS: u64 = 60_000;
+/// Confirmations this close to the deadline are refused, so the app never
+/// applies a request the native host is about to report as timed out.
+const CONFIRMATION_MARGIN_MS: u64 = 2_000;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -33,6 +42,9 @@ pub struct CreateEntryFromBrowserPayload {
     pub folder_id: Option<String>,
     #[serde(default)]
     pub totp: Option<String>,
+    /// Skips the duplicate check and always queues a new entry.
+    #[serde(default)]
+    pub force: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +64,10 @@ pub struct UpdateEntryFromBrowserPayload {
     pub matched_host: Option<String>,
     #[serde(default)]
     pub totp: Option<String>,
+    /// `lastUpdated` of the entry as the client saw it. When set, the update
+    /// fails with `ENTRY_CONFLICT` if the entry changed in the meantime.
+    #[serde(default)]
+    pub last_updated: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +85,45 @@ pub struct UpdateEntryFromBrowserResult {
     pub updated_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_history: Option<PasswordHistoryReport>,
+}
+
+/// Moves an entry into the trash. Always confirmed by the user in the app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteEntryFromBrowserPayload {
+    pub entry_id: String,
+    #[serde(default)]
+    pub last_updated: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveEntryToFolderPayload {
+    pub entry_id: String,
+    /// Target folder; `None` moves the entry out of any folder.
+    #[serde(default)]
+    pub folder_id: Option<String>,
+    #[serde(default)]
+    pub last_updated: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFavoritePayload {
+    pub entry_id: String,
+    pub fav: bool,
+}
+
+/// Current state of an entry, returned alongside `ENTRY_CONFLICT`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryConflictMetadata {
+    pub entry_id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,6 +131,27 @@ pub struct UpdateEntryFromBrowserResult {
 pub enum BrowserWriteKind {
     CreateEntryFromBrowser,
     UpdateEntryFromBrowser,
+    DeleteEntryFromBrowser,
+    MoveEntryToFolder,
+    SetFavorite,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserWriteState {
+    #[default]
+    Queued,
+    Claimed,
+    /// Claimed by the app, which is waiting for the user to confirm it.
+    AwaitingUser,
+    Completed,
+    Expired,
+}
+
+impl BrowserWriteState {
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Completed | Self::Expired)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,6 +164,42 @@ pub struct BrowserWriteRequest {
     pub payload: Value,
     #[serde(default)]
     pub claimed_at_ms: Option<u64>,
+    /// The app process that claimed the request. Requests still held by an
+    /// earlier app process are handed out again after a restart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claimed_by_pid: Option<u32>,
+    /// Set for requests captured while the vault was locked and held in the
+    /// sealed inbox until the next unlock.
+    #[serde(default)]
+    pub queued_while_locked: bool,
+    #[serde(default)]
+    pub state: BrowserWriteState,
+    #[serde(default)]
+    pub completed_at_ms: Option<u64>,
+    /// When the native host stops waiting for a request the user has to
+    /// confirm. Later confirmations are refused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmed_at_ms: Option<u64>,
+}
+
+/// Write request as shown in the app, without its payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowserWriteRequestSummary {
+    pub id: String,
+    pub kind: BrowserWriteKind,
+    pub state: BrowserWriteState,
+    pub client: BridgeClientInfo,
+    pub created_at_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claimed_at_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at_ms: Option<u64>,
+    pub queued_while_locked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<BridgeError>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -154,6 +266,27 @@ pub fn queue_create_request(
     payload: CreateEntryFromBrowserPayload,
 ) -> io::Result<BrowserWriteResult> {
     validate_create_payload(&payload).map_err(as_io_error)?;
+
+    if let Some(result) = replayed_result(&id)? {
+        return Ok(result);
+    }
+
+    if !payload.force {
+        let candidates = duplicate_candidates(&payload)?;
+        if !candidates.is_empty() {
+            return Ok(BrowserWriteResult {
+                request_id: id,
+                completed_at_ms: now_ms(),
+                ok: false,
+                result: Some(serde_json::json!({ "candidates": candidates })),
+                error: Some(BridgeError {
+                    code: "DUPLICATE_CANDIDATE".to_string(),
+                    message: "An entry with the same site and username already exists.".to_string(),
+                }),
+            });
+        }
+    }
+
     queue_request(id, BrowserWriteKind::CreateEntryFromBrowser, client, payload)
 }
 
@@ -163,30 +296,133 @@ pub fn queue_update_request(
     payload: UpdateEntryFromBrowserPayload,
 ) -> io::Result<BrowserWriteResult> {
     validate_update_payload(&payload).map_err(as_io_error)?;
+
+    if let Some(result) = replayed_result(&id)? {
+        return Ok(result);
+    }
+
+    let conflict = conflicting_entry(&payload.entry_id, payload.last_updated.as_deref())?;
+    if let Some(current) = conflict {
+        return Ok(conflict_result(id, current));
+    }
+
     queue_request(id, BrowserWriteKind::UpdateEntryFromBrowser, client, payload)
 }
 
-pub fn claim_pending_requests() -> io::Result<Vec<BrowserWriteRequest>> {
+/// Queues a soft delete. The app asks the user before applying it, so this
+/// waits longer than other writes.
+pub fn queue_delete_request(
+    id: String,
+    client: BridgeClientInfo,
+    payload: DeleteEntryFromBrowserPayload,
+) -> io::Result<BrowserWriteResult> {
+    queue_entry_request(
+        EntryWrite {
+            id,
+            kind: BrowserWriteKind::DeleteEntryFromBrowser,
+            client,
+            entry_id: payload.entry_id.clone(),
+            last_updated: payload.last_updated.clone(),
+            confirm_within_ms: Some(CONFIRMATION_TIMEOUT_MS),
+        },
+        payload,
+    )
+}
+
+pub fn queue_move_request(
+    id: String,
+    client: BridgeClientInfo,
+    payload: MoveEntryToFolderPayload,
+) -> io::Result<BrowserWriteResult> {
+    queue_entry_request(
+        EntryWrite {
+            id,
+            kind: BrowserWriteKind::MoveEntryToFolder,
+            client,
+            entry_id: payload.entry_id.clone(),
+            last_updated: payload.last_updated.clone(),
+            confirm_within_ms: None,
+        },
+        payload,
+    )
+}
+
+pub fn queue_set_favorite_request(
+    id: String,
+    client: BridgeClientInfo,
+    payload: SetFavoritePayload,
+) -> io::Result<BrowserWriteResult> {
+    queue_entry_request(
+        EntryWrite {
+            id,
+            kind: BrowserWriteKind::SetFavorite,
+            client,
+            entry_id: payload.entry_id.clone(),
+            last_updated: None,
+            confirm_within_ms: None,
+        },
+        payload,
+    )
+}
+
+/// Seals a create request into the locked-vault inbox. Returns `Ok(None)` if
+/// the app has not published an inbox key yet.
+pub fn queue_locked_create_request(
+    id: String,
+    client: BridgeClientInfo,
+    payload: CreateEntryFromBrowserPayload,
+) -> io::Result<Option<BrowserWriteResult>> {
+    validate_create_payload(&payload).map_err(as_io_error)?;
+    queue_locked_request(id, BrowserWriteKind::CreateEntryFromBrowser, client, payload)
+}
+
+/// Seals an update request into the locked-vault inbox. Returns `Ok(None)` if
+/// the app has not published an inbox key yet.
+pub fn queue_locked_update_request(
+    id: String,
+    client: BridgeClientInfo,
+    payload: UpdateEntryFromBrowserPayload,
+) -> io::Result<Option<BrowserWriteResult>> {
+    validate_update_payload(&payload).map_err(as_io_error)?;
+    queue_locked_request(id, BrowserWriteKind::UpdateEntryFromBrowser, client, payload)
+}
+
+/// Claims pending requests for the app. With `inbox_secret`, requests sealed
+/// while the vault was locked are opened and claimed as well.
+pub fn claim_pending_requests(inbox_secret: Option<&str>) -> io::Result<Vec<BrowserWriteRequest>> {
     let path = write_request_store_path()?;
     let mut store = load_request_store(&path)?;
     let now = now_ms();
     let mut claimed = Vec::new();
 
-    for request in &mut store.requests {
-        let can_claim = request
-            .claimed_at_ms
-            .map(|claimed_at_ms| now.saturating_sub(claimed_at_ms) >= CLAIM_STALE_AFTER_MS)
-            .unwrap_or(true);
+    let mut opened_ids = Vec::new();
+    if let Some(secret) = inbox_secret {
+        inbox::publish_public_key(secret)?;
+        for request in inbox::open_sealed_requests(secret)? {
+            opened_ids.push(request.id.clone());
+            // A retry sealed while the first copy was handled is dropped.
+            if !store.requests.iter().any(|item| item.id == request.id) {
+                store.requests.push(request);
+            }
+        }
+    }
 
-        if can_claim {
+    let pid = std::process::id();
+    let mut changed = refresh_lifecycle(&mut store, now) || !opened_ids.is_empty();
+    for request in &mut store.requests {
+        if is_claimable(request, now, pid) {
+            request.state = BrowserWriteState::Claimed;
             request.claimed_at_ms = Some(now);
+            request.claimed_by_pid = Some(pid);
             claimed.push(request.clone());
+            changed = true;
         }
     }
 
-    if !claimed.is_empty() {
+    if changed {
         write_json_atomically(&path, &store)?;
     }
+    inbox::remove_sealed_requests(&opened_ids)?;
 
     Ok(claimed)
 }
@@ -200,30 +436,124 @@ pub fn complete_request(
     let requests_path = write_request_store_path()?;
     let results_path = write_result_store_path()?;
 
+    let now = now_ms();
+
     let mut request_store = load_request_store(&requests_path)?;
-    request_store.requests.retain(|request| request.id != request_id);
+    if let Some(request) = request_store
+        .requests
+        .iter_mut()
+        .find(|request| request.id == request_id)
+    {
+        request.state = BrowserWriteState::Completed;
+        request.completed_at_ms = Some(now);
+        request.payload = Value::Null;
+    }
+    refresh_lifecycle(&mut request_store, now);
     write_json_atomically(&requests_path, &request_store)?;
 
     let mut result_store = load_result_store(&results_path)?;
-    result_store.results.retain(|item| item.request_id != request_id);
+    result_store.results.retain(|item| {
+        item.request_id != request_id
+            && now.saturating_sub(item.completed_at_ms) < RESULT_RETENTION_MS
+    });
     result_store.results.push(BrowserWriteResult {
         request_id: request_id.to_string(),
-        completed_at_ms: now_ms(),
+        completed_at_ms: now,
         ok,
         result,
         error,
     });
 
-    if result_store.results.len() > RESULT_RETENTION_LIMIT {
-        let overflow = result_store.results.len() - RESULT_RETENTION_LIMIT;
-        result_store.results.drain(0..overflow);
+    write_json_atomically(&results_path, &result_store)
+}
+
+/// Records the user's confirmation of a request. Returns `false` if the request
+/// is no longer waiting for the user or its deadline has (nearly) passed; the
+/// request is then expired and must not be applied.
+pub fn confirm_request(request_id: &str) -> io::Result<bool> {
+    let path = write_request_store_path()?;
+    let mut store = load_request_store(&path)?;
+    let now = now_ms();
+    let Some(request) = store.requests.iter_mut().find(|request| {
+        request.id == request_id && request.state == BrowserWriteState::AwaitingUser
+    }) else {
+        return Ok(false);
+    };
+
+    let confirmed = confirm(request, now);
+    write_json_atomically(&path, &store)?;
+    Ok(confirmed)
+}
+
+fn confirm(request: &mut BrowserWriteRequest, now: u64) -> bool {
+    let in_time = request.expires_at_ms.map_or(true, |expires_at_ms| {
+        now + CONFIRMATION_MARGIN_MS < expires_at_ms
+    });
+    if in_time {
+        request.state = BrowserWriteState::Claimed;
+        request.claimed_at_ms = Some(now);
+        request.confirmed_at_ms = Some(now);
+    } else {
+        expire(request, now);
+    }
+    in_time
+}
+
+/// Marks a claimed request as waiting for confirmation in the app, so it is
+/// not handed out again while the user decides.
+pub fn mark_awaiting_user(request_id: &str) -> io::Result<bool> {
+    let path = write_request_store_path()?;
+    let mut store = load_request_store(&path)?;
+    let Some(request) = store
+        .requests
+        .iter_mut()
+        .find(|request| request.id == request_id && request.state == BrowserWriteState::Claimed)
+    else {
+        return Ok(false);
+    };
+
+    request.state = BrowserWriteState::AwaitingUser;
+    write_json_atomically(&path, &store)?;
+    Ok(true)
+}
+
+pub fn list_requests() -> io::Result<Vec<BrowserWriteRequestSummary>> {
+    let path = write_request_store_path()?;
+    let mut store = load_request_store(&path)?;
+    if refresh_lifecycle(&mut store, now_ms()) {
+        write_json_atomically(&path, &store)?;
     }
 
-    write_json_atomically(&results_path, &result_store)
+    let results = load_result_store(&write_result_store_path()?)?.results;
+    Ok(store
+        .requests
+        .into_iter()
+        .map(|request| {
+            let error = results
+                .iter()
+                .find(|result| result.request_id == request.id)
+                .and_then(|result| result.error.clone());
+            BrowserWriteRequestSummary {
+                id: request.id,
+                kind: request.kind,
+                state: request.state,
+                client: request.client,
+                created_at_ms: request.created_at_ms,
+                claimed_at_ms: request.claimed_at_ms,
+                completed_at_ms: request.completed_at_ms,
+                queued_while_locked: request.queued_while_locked,
+                error,
+            }
+        })
+        .collect())
 }
 
 pub fn wait_for_result(request_id: &str) -> io::Result<BrowserWriteResult> {
-    let deadline = now_ms() + WRITE_TIMEOUT_MS;
+    wait_for_result_within(request_id, WRITE_TIMEOUT_MS)
+}
+
+fn wait_for_result_within(request_id: &str, timeout_ms: u64) -> io::Result<BrowserWriteResult> {
+    let deadline = now_ms() + timeout_ms;
 
     loop {
         if let Some(result) = find_result(request_id)? {
@@ -247,11 +577,150 @@ fn queue_request<T: Serialize>(
     client: BridgeClientInfo,
     payload: T,
 ) -> io::Result<BrowserWriteResult> {
+    enqueue_request(id.clone(), kind, client, payload)?;
+    wait_for_result(&id)
+}
+
+struct EntryWrite {
+    id: String,
+    kind: BrowserWriteKind,
+    client: BridgeClientInfo,
+    entry_id: String,
+    last_updated: Option<String>,
+    /// Set for requests the user confirms in the app.
+    confirm_within_ms: Option<u64>,
+}
+
+fn queue_entry_request<T: Serialize>(
+    write: EntryWrite,
+    payload: T,
+) -> io::Result<BrowserWriteResult> {
+    if write.entry_id.trim().is_empty() {
+        return Err(as_io_error(validation_error("entryId is required")));
+    }
+
+    if let Some(result) = replayed_result(&write.id)? {
+        return Ok(result);
+    }
+
+    if let Some(current) = conflicting_entry(&write.entry_id, write.last_updated.as_deref())? {
+        return Ok(conflict_result(write.id, current));
+    }
+
+    let Some(confirm_within_ms) = write.confirm_within_ms else {
+        return queue_request(write.id, write.kind, write.client, payload);
+    };
+
+    let expires_at_ms = now_ms() + confirm_within_ms;
+    enqueue_request_until(
+        write.id.clone(),
+        write.kind,
+        write.client,
+        payload,
+        Some(expires_at_ms),
+    )?;
+    match wait_for_result_within(&write.id, confirm_within_ms) {
+        // A request confirmed just before the deadline is being applied.
+        Err(error) if error.kind() == io::ErrorKind::TimedOut => {
+            if expire_unless_confirmed(&write.id)? {
+                wait_for_result(&write.id)
+            } else {
+                Err(error)
+            }
+        }
+        other => other,
+    }
+}
+
+/// Expires a request the user has not confirmed. Returns whether it was
+/// confirmed and is still being applied.
+fn expire_unless_confirmed(request_id: &str) -> io::Result<bool> {
+    let path = write_request_store_path()?;
+    let mut store = load_request_store(&path)?;
+    let Some(request) = store
+        .requests
+        .iter_mut()
+        .find(|request| request.id == request_id && !request.state.is_terminal())
+    else {
+        return Ok(false);
+    };
+
+    if request.confirmed_at_ms.is_some() {
+        return Ok(true);
+    }
+    expire(request, now_ms());
+    write_json_atomically(&path, &store)?;
+    Ok(false)
+}
+
+fn enqueue_request<T: Serialize>(
+    id: String,
+    kind: BrowserWriteKind,
+    client: BridgeClientInfo,
+    payload: T,
+) -> io::Result<()> {
+    enqueue_request_until(id, kind, client, payload, None)
+}
+
+fn enqueue_request_until<T: Serialize>(
+    id: String,
+    kind: BrowserWriteKind,
+    client: BridgeClientInfo,
+    payload: T,
+    expires_at_ms: Option<u64>,
+) -> io::Result<()> {
     let path = write_request_store_path()?;
     let mut store = load_request_store(&path)?;
 
-    store.requests.retain(|request| request.id != id);
+    // A retry of a request that is still pending keeps its place in the queue.
+    if store.requests.iter().any(|request| request.id == id) {
+        return Ok(());
+    }
+
     store.requests.push(BrowserWriteRequest {
+        id,
+        created_at_ms: now_ms(),
+        kind,
+        client,
+        payload: serde_json::to_value(payload)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?,
+        claimed_at_ms: None,
+        claimed_by_pid: None,
+        queued_while_locked: false,
+        state: BrowserWriteState::Queued,
+        completed_at_ms: None,
+        expires_at_ms,
+        confirmed_at_ms: None,
+    });
+
+    write_json_atomically(&path, &store)
+}
+
+/// A retried request id is answered with its stored outcome, and is not
+/// sealed again while the app still handles the first copy.
+fn queue_locked_request<T: Serialize>(
+    id: String,
+    kind: BrowserWriteKind,
+    client: BridgeClientInfo,
+    payload: T,
+) -> io::Result<Option<BrowserWriteResult>> {
+    if let Some(result) = replayed_result(&id)? {
+        return Ok(Some(result));
+    }
+
+    let queued = BrowserWriteResult {
+        request_id: id.clone(),
+        completed_at_ms: now_ms(),
+        ok: true,
+        result: Some(serde_json::json!({ "queued": true, "requestId": id })),
+        error: None,
+    };
+    let store = load_request_store(&write_request_store_path()?)?;
+    if store.requests.iter().any(|request| request.id == id) {
+        return Ok(Some(queued));
+    }
+
+    let request = BrowserWriteRequest {
         id: id.clone(),
         created_at_ms: now_ms(),
         kind,
@@ -259,10 +728,170 @@ fn queue_request<T: Serialize>(
         payload: serde_json::to_value(payload)
             .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?,
         claimed_at_ms: None,
+        claimed_by_pid: None,
+        queued_while_locked: true,
+        state: BrowserWriteState::Queued,
+        completed_at_ms: None,
+        expires_at_ms: None,
+        confirmed_at_ms: None,
+    };
+
+    if !inbox::seal_request(&request)? {
+        return Ok(None);
+    }
+
+    Ok(Some(queued))
+}
+
+fn duplicate_candidates(
+    payload: &CreateEntryFromBrowserPayload,
+) -> io::Result<Vec<SearchEntrySuggestion>> {
+    let Some(snapshot) = load_session()? else {
+        return Ok(Vec::new());
+    };
+    let Some(url) = effective_url(payload.url.as_deref(), payload.matched_host.as_deref()) else {
+        return Ok(Vec::new());
+    };
+
+    let usernames = [payload.username.as_deref(), payload.email.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    Ok(find_duplicate_candidates(&snapshot.vault, &url, &usernames))
+}
+
+/// Checks the observed `lastUpdated` against the published session. The app
+/// repeats the check when it applies the request, since the vault may change
+/// while the request is queued.
+fn conflicting_entry(
+    entry_id: &str,
+    last_updated: Option<&str>,
+) -> io::Result<Option<EntryConflictMetadata>> {
+    let Some(expected) = last_updated else {
+        return Ok(None);
+    };
+    let Some(snapshot) = load_session()? else {
+        return Ok(None);
+    };
+
+    Ok(entry_conflict(&snapshot.vault, entry_id, expected))
+}
+
+fn conflict_result(request_id: String, current: EntryConflictMetadata) -> BrowserWriteResult {
+    BrowserWriteResult {
+        request_id,
+        completed_at_ms: now_ms(),
+        ok: false,
+        result: serde_json::to_value(current).ok(),
+        error: Some(entry_conflict_error()),
+    }
+}
+
+fn entry_conflict(
+    vault: &VaultData,
+    entry_id: &str,
+    expected_last_updated: &str,
+) -> Option<EntryConflictMetadata> {
+    vault
+        .values
+        .iter()
+        .find(|entry| entry.id == entry_id)
+        .filter(|entry| entry.last_updated.as_deref() != Some(expected_last_updated))
+        .map(|entry| EntryConflictMetadata {
+            entry_id: entry.id.clone(),
+            title: entry.title.clone(),
+            last_updated: entry.last_updated.clone(),
+        })
+}
+
+fn entry_conflict_error() -> BridgeError {
+    BridgeError {
+        code: "ENTRY_CONFLICT".to_string(),
+        message: "The entry was changed since the browser last read it.".to_string(),
+    }
+}
+
+/// Returns the stored outcome of a request id that was already handled, so a
+/// retried request is answered instead of being queued twice.
+fn replayed_result(request_id: &str) -> io::Result<Option<BrowserWriteResult>> {
+    if let Some(result) = find_result(request_id)? {
+        return Ok(Some(result));
+    }
+
+    let store = load_request_store(&write_request_store_path()?)?;
+    Ok(store
+        .requests
+        .into_iter()
+        .find(|request| request.id == request_id && request.state == BrowserWriteState::Expired)
+        .map(|request| BrowserWriteResult {
+            request_id: request.id,
+            completed_at_ms: request.completed_at_ms.unwrap_or(request.created_at_ms),
+            ok: false,
+            result: None,
+            error: Some(BridgeError {
+                code: "REQUEST_EXPIRED".to_string(),
+                message: "Desktop app did not pick up the browser write request in time."
+                    .to_string(),
+            }),
+        }))
+}
+
+/// Whether the app process `pid` may claim `request`. Claims of an earlier
+/// app process, including ones waiting for the user, are released.
+fn is_claimable(request: &BrowserWriteRequest, now: u64, pid: u32) -> bool {
+    let claimed_elsewhere = request.claimed_by_pid != Some(pid);
+    match request.state {
+        BrowserWriteState::Queued => true,
+        BrowserWriteState::Claimed => {
+            claimed_elsewhere
+                || request
+                    .claimed_at_ms
+                    .map(|claimed_at_ms| now.saturating_sub(claimed_at_ms) >= CLAIM_STALE_AFTER_MS)
+                    .unwrap_or(true)
+        }
+        BrowserWriteState::AwaitingUser => claimed_elsewhere,
+        _ => false,
+    }
+}
+
+/// Expires requests nobody picked up or confirmed in time and drops finished
+/// requests once they are older than the result retention. Returns whether
+/// anything changed.
+fn refresh_lifecycle(store: &mut BrowserWriteRequestStore, now: u64) -> bool {
+    let mut changed = false;
+
+    for request in &mut store.requests {
+        if request.state.is_terminal() {
+            continue;
+        }
+        // Saves from the locked inbox wait for the next unlock, however long that takes.
+        let abandoned = !request.queued_while_locked
+            && now.saturating_sub(request.created_at_ms) >= REQUEST_EXPIRE_AFTER_MS;
+        let unconfirmed = request.confirmed_at_ms.is_none()
+            && request
+                .expires_at_ms
+                .is_some_and(|expires_at_ms| now >= expires_at_ms);
+        if abandoned || unconfirmed {
+            expire(request, now);
+            changed = true;
+        }
+    }
+
+    let before = store.requests.len();
+    store.requests.retain(|request| {
+        !request.state.is_terminal()
+            || request.completed_at_ms.map_or(true, |completed_at_ms| {
+                now.saturating_sub(completed_at_ms) < RESULT_RETENTION_MS
+            })
     });
 
-    write_json_atomically(&path, &store)?;
-    wait_for_result(&id)
+    changed || store.requests.len() != before
+}
+
+fn expire(request: &mut BrowserWriteRequest, now: u64) {
+    request.state = BrowserWriteState::Expired;
+    request.completed_at_ms = Some(now);
+    request.payload = Value::Null;
 }
 
 fn find_result(request_id: &str) -> io::Result<Option<BrowserWriteResult>> {
@@ -336,9 +965,38 @@ fn as_io_error(error: BridgeError) -> io::Error {
 #[cfg(test)]
 mod tests {
     use super::{
-        effective_url, validate_create_payload, validate_update_payload,
-        CreateEntryFromBrowserPayload, UpdateEntryFromBrowserPayload,
+        confirm, effective_url, entry_conflict, is_claimable, refresh_lifecycle,
+        validate_create_payload, validate_update_payload, BrowserWriteKind, BrowserWriteRequest,
+        BrowserWriteRequestStore, BrowserWriteState, CreateEntryFromBrowserPayload,
+        MoveEntryToFolderPayload, UpdateEntryFromBrowserPayload, CLAIM_STALE_AFTER_MS,
+        CONFIRMATION_MARGIN_MS, REQUEST_EXPIRE_AFTER_MS, RESULT_RETENTION_MS,
     };
+    use crate::bridge::{
+        protocol::BridgeClientInfo,
+        vault::{VaultData, VaultEntry},
+    };
+
+    fn request(id: &str, state: BrowserWriteState, created_at_ms: u64) -> BrowserWriteRequest {
+        BrowserWriteRequest {
+            id: id.into(),
+            created_at_ms,
+            kind: BrowserWriteKind::CreateEntryFromBrowser,
+            client: BridgeClientInfo {
+                extension_id: "ext".into(),
+                name: None,
+                version: None,
+                instance_id: None,
+            },
+            payload: serde_json::json!({ "password": "secret" }),
+            claimed_at_ms: None,
+            claimed_by_pid: None,
+            queued_while_locked: false,
+            state,
+            completed_at_ms: None,
+            expires_at_ms: None,
+            confirmed_at_ms: None,
+        }
+    }
 
     #[test]
     fn create_payload_requires_password() {
@@ -351,6 +1009,7 @@ mod tests {
             matched_host: Some("example.com".into()),
             folder_id: None,
             totp: None,
+            force: false,
         };
 
         assert!(validate_create_payload(&payload).is_err());
@@ -367,6 +1026,7 @@ mod tests {
             url: Some("https://example.com".into()),
             matched_host: None,
             totp: None,
+            last_updated: None,
         };
 
         assert!(validate_update_payload(&payload).is_err());
@@ -379,4 +1039,132 @@ mod tests {
             Some("https://example.com".to_string())
         );
     }
+
+    #[test]
+    fn entry_conflict_reports_current_metadata() {
+        let vault = VaultData {
+            values: vec![VaultEntry {
+                id: "a".into(),
+                title: "Example".into(),
+                last_updated: Some("2024-02-01T00:00:00.000Z".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert!(entry_conflict(&vault, "a", "2024-02-01T00:00:00.000Z").is_none());
+        assert!(entry_conflict(&vault, "missing", "2024-01-01T00:00:00.000Z").is_none());
+
+        let conflict = entry_conflict(&vault, "a", "2024-01-01T00:00:00.000Z").expect("conflict");
+        assert_eq!(conflict.title, "Example");
+        assert_eq!(
+            conflict.last_updated.as_deref(),
+            Some("2024-02-01T00:00:00.000Z")
+        );
+    }
+
+    #[test]
+    fn claimed_requests_are_handed_out_again_only_when_stale() {
+        let now = 100_000;
+        let mut claimed = request("a", BrowserWriteState::Claimed, now);
+        claimed.claimed_at_ms = Some(now - 1_000);
+        claimed.claimed_by_pid = Some(7);
+        assert!(!is_claimable(&claimed, now, 7));
+        assert!(is_claimable(&claimed, now + CLAIM_STALE_AFTER_MS, 7));
+
+        let queued = request("b", BrowserWriteState::Queued, now);
+        let mut waiting = request("c", BrowserWriteState::AwaitingUser, now);
+        waiting.claimed_by_pid = Some(7);
+        assert!(is_claimable(&queued, now, 7));
+        assert!(!is_claimable(&waiting, now + CLAIM_STALE_AFTER_MS, 7));
+    }
+
+    #[test]
+    fn claims_of_an_earlier_app_process_are_released() {
+        let now = 100_000;
+        let mut claimed = request("a", BrowserWriteState::Claimed, now);
+        claimed.claimed_at_ms = Some(now);
+        claimed.claimed_by_pid = Some(7);
+        let mut waiting = request("b", BrowserWriteState::AwaitingUser, now);
+        waiting.claimed_by_pid = Some(7);
+
+        assert!(is_claimable(&claimed, now, 8));
+        assert!(is_claimable(&waiting, now, 8));
+    }
+
+    #[test]
+    fn lifecycle_expires_stale_requests_and_prunes_old_results() {
+        let now = RESULT_RETENTION_MS * 2;
+        let mut locked = request("locked", BrowserWriteState::Queued, 0);
+        locked.queued_while_locked = true;
+        let mut locked_waiting = request("locked-waiting", BrowserWriteState::AwaitingUser, 0);
+        locked_waiting.queued_while_locked = true;
+        let mut unconfirmed = request("unconfirmed", BrowserWriteState::AwaitingUser, now - 1);
+        unconfirmed.expires_at_ms = Some(now);
+        let mut old_completed = request("old", BrowserWriteState::Completed, 0);
+        old_completed.completed_at_ms = Some(now - RESULT_RETENTION_MS);
+        let mut store = BrowserWriteRequestStore {
+            requests: vec![
+                request(
+                    "stale",
+                    BrowserWriteState::Queued,
+                    now - REQUEST_EXPIRE_AFTER_MS,
+                ),
+                request("fresh", BrowserWriteState::Queued, now - 1),
+                request("waiting", BrowserWriteState::AwaitingUser, 0),
+                locked,
+                locked_waiting,
+                unconfirmed,
+                old_completed,
+            ],
+        };
+
+        assert!(refresh_lifecycle(&mut store, now));
+
+        let states = store
+            .requests
+            .iter()
+            .map(|request| (request.id.as_str(), request.state))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            states,
+            vec![
+                ("stale", BrowserWriteState::Expired),
+                ("fresh", BrowserWriteState::Queued),
+                ("waiting", BrowserWriteState::Expired),
+                ("locked", BrowserWriteState::Queued),
+                ("locked-waiting", BrowserWriteState::AwaitingUser),
+                ("unconfirmed", BrowserWriteState::Expired),
+            ]
+        );
+        assert!(store.requests[0].payload.is_null());
+    }
+
+    #[test]
+    fn move_payload_defaults_to_no_folder() {
+        let payload = serde_json::from_value::<MoveEntryToFolderPayload>(serde_json::json!({
+            "entryId": "a"
+        }))
+        .unwrap();
+
+        assert_eq!(payload.entry_id, "a");
+        assert!(payload.folder_id.is_none());
+        assert!(payload.last_updated.is_none());
+    }
+
+    #[test]
+    fn confirmations_after_the_deadline_expire_the_request() {
+        let now = 100_000;
+        let mut in_time = request("a", BrowserWriteState::AwaitingUser, now);
+        in_time.expires_at_ms = Some(now + CONFIRMATION_MARGIN_MS + 1);
+        assert!(confirm(&mut in_time, now));
+        assert_eq!(in_time.state, BrowserWriteState::Claimed);
+        assert_eq!(in_time.confirmed_at_ms, Some(now));
+
+        let mut late = request("b", BrowserWriteState::AwaitingUser, now);
+        late.expires_at_ms = Some(now + CONFIRMATION_MARGIN_MS);
+        assert!(!confirm(&mut late, now));
+        assert_eq!(late.state, BrowserWriteState::Expired);
+        assert!(late.payload.is_null());
+    }
 }
@@ -1,9 +1,11 @@
 use serde_json::Value;
 
 use crate::bridge::{
+    history::{self, PasswordHistoryItem, PasswordHistoryRetention, PasswordHistoryUpdate},
+    inbox,
     pairing, session,
     vault::VaultData,
-    write::{self, BrowserWriteRequest},
+    write::{self, BrowserWriteRequest, BrowserWriteRequestSummary},
 };
 
 #[tauri::command]
@@ -17,6 +19,11 @@ pub fn bridge_publish_session(vault: Value) -> Result<(), String> {
 
 #[tauri::command]
 pub fn bridge_clear_session() -> Result<(), String> {
+    // Locking ends every CLI and Secret Service grant and open SSH signature
+    // prompt of this unlock session.
+    crate::control::approval::approvals().revoke_all();
+    crate::secret_service::approvals().revoke_all();
+    crate::ssh::confirm::confirmations().reject_all();
     session::clear_session().map_err(|err| format!("Failed to clear bridge session: {err}"))
 }
 
@@ -65,8 +72,43 @@ pub fn bridge_revoke_pairing(
 }
 
 #[tauri::command]
-pub fn bridge_claim_pending_writes() -> Result<Vec<BrowserWriteRequest>, String> {
-    write::claim_pending_requests().map_err(|err| format!("Failed to claim browser writes: {err}"))
+pub fn bridge_set_client_capabilities(
+    extension_id: String,
+    client_instance_id: Option<String>,
+    capabilities: Vec<String>,
+) -> Result<Option<pairing::PairedClient>, String> {
+    pairing::set_client_capabilities(&extension_id, client_instance_id.as_deref(), capabilities)
+        .map_err(|err| format!("Failed to update client capabilities: {err}"))
+}
+
+#[tauri::command]
+pub fn bridge_claim_pending_writes(
+    inbox_secret: Option<String>,
+) -> Result<Vec<BrowserWriteRequest>, String> {
+    write::claim_pending_requests(inbox_secret.as_deref())
+        .map_err(|err| format!("Failed to claim browser writes: {err}"))
+}
+
+#[tauri::command]
+pub fn bridge_list_write_requests() -> Result<Vec<BrowserWriteRequestSummary>, String> {
+    write::list_requests().map_err(|err| format!("Failed to load browser write requests: {err}"))
+}
+
+#[tauri::command]
+pub fn bridge_mark_write_awaiting_user(request_id: String) -> Result<bool, String> {
+    write::mark_awaiting_user(&request_id)
+        .map_err(|err| format!("Failed to update browser write request: {err}"))
+}
+
+#[tauri::command]
+pub fn bridge_confirm_write_request(request_id: String) -> Result<bool, String> {
+    write::confirm_request(&request_id)
+        .map_err(|err| format!("Failed to update browser write request: {err}"))
+}
+
+#[tauri::command]
+pub fn bridge_generate_inbox_secret() -> String {
+    inbox::generate_inbox_secret()
 }
 
 #[tauri::command]
@@ -89,3 +131,46 @@ pub fn bridge_complete_write_request(
     write::complete_request(&request_id, ok, result, error)
         .map_err(|err| format!("Failed to complete browser write request: {err}"))
 }
+
+#[tauri::command]
+pub fn bridge_list_password_history(
+    entry_id: String,
+) -> Result<Option<Vec<PasswordHistoryItem>>, String> {
+    history::list_password_history(&entry_id)
+        .map_err(|err| format!("Failed to load password history: {err}"))
+}
+
+#[tauri::command]
+pub fn bridge_record_password_change(
+    history: Vec<PasswordHistoryItem>,
+    previous: Option<String>,
+    next: String,
+    client: Option<String>,
+) -> Result<PasswordHistoryUpdate, String> {
+    history::record_password_change(history, previous.as_deref(), &next, client.as_deref())
+        .map_err(|err| format!("Failed to record password history: {err}"))
+}
+
+#[tauri::command]
+pub fn bridge_restore_password_from_history(
+    history: Vec<PasswordHistoryItem>,
+    current: Option<String>,
+    item: PasswordHistoryItem,
+) -> Result<PasswordHistoryUpdate, String> {
+    history::restore_password(history, current.as_deref(), &item)
+        .map_err(|err| format!("Failed to restore password: {err}"))
+}
+
+#[tauri::command]
+pub fn bridge_get_password_history_retention() -> Result<PasswordHistoryRetention, String> {
+    history::load_retention()
+        .map_err(|err| format!("Failed to load password history retention: {err}"))
+}
+
+#[tauri::command]
+pub fn bridge_set_password_history_retention(
+    retention: PasswordHistoryRetention,
+) -> Result<(), String> {
+    history::save_retention(&retention)
+        .map_err(|err| format!("Failed to save password history retention: {err}"))
+}
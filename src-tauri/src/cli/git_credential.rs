@@ -0,0 +1,531 @@
+//! `git-credential-clavispass`, a git credential helper answering from the
+//! running desktop app. Configure it with
+//! `git config credential.helper clavispass`, or `"clavispass --store"` to
+//! also save credentials git reports as working.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    env,
+    io::{self, BufRead, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::usage_error;
+use crate::{
+    bridge::{
+        protocol::BridgeClientInfo,
+        vault::{first_string, search_entries_by_domain, VaultData, VaultEntry},
+        write::{
+            queue_create_request, queue_update_request, BrowserWriteResult,
+            CreateEntryFromBrowserPayload, UpdateEntryFromBrowserPayload,
+        },
+    },
+    control::protocol::CredentialPayload,
+};
+
+#[cfg(unix)]
+use crate::control::{
+    client::ControlClient,
+    protocol::{COMMAND_CREDENTIAL_GET, COMMAND_CREDENTIAL_STORE},
+};
+
+const USAGE: &str = "\
+Usage: git-credential-clavispass [--store] <get|store|erase>
+
+Git credential helper reading logins from the running, unlocked ClavisPass
+app. Enable it with:
+
+  git config --global credential.helper clavispass
+
+Entries match when one of their URL fields points at the requested host with
+the requested protocol; URLs without a scheme count as https. With
+credential.useHttpPath, URLs with a path only match repositories below it.
+
+Options:
+  --store     Save credentials git reports as working: update the password of
+              the matching entry or create a new one
+  -h, --help  Print this help
+";
+
+/// The login answered for a `get`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitCredential {
+    pub entry_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum StoreAction {
+    Unchanged,
+    Updated,
+    Created,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StoredCredential {
+    pub entry_id: Option<String>,
+    pub action: StoreAction,
+}
+
+/// Entry point of the `git-credential-clavispass` binary. Returns the process
+/// exit code.
+pub fn run() -> io::Result<i32> {
+    let mut store = false;
+    let mut action = None;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                print!("{USAGE}");
+                return Ok(0);
+            }
+            "--store" => store = true,
+            _ if action.is_none() => action = Some(arg),
+            other => return Err(usage_error(&format!("Unexpected argument {other}"))),
+        }
+    }
+
+    let action = action.ok_or_else(|| usage_error("Missing <get|store|erase>"))?;
+    let credential = read_credential(io::stdin().lock())?;
+    match action.as_str() {
+        "get" => get(&credential),
+        "store" if store => save(&credential),
+        // Entries are only ever removed in the app, and helpers are expected
+        // to ignore operations they do not know.
+        _ => Ok(()),
+    }
+    .map(|_| 0)
+}
+
+/// Reads `key=value` lines up to a blank line or the end of input. A `url`
+/// attribute is split into protocol, host and path.
+fn read_credential(input: impl BufRead) -> io::Result<CredentialPayload> {
+    let mut credential = CredentialPayload::default();
+    for line in input.lines() {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.to_string();
+        match key {
+            "protocol" => credential.protocol = Some(value),
+            "host" => credential.host = value,
+            "path" => credential.path = Some(value),
+            "username" => credential.username = Some(value),
+            "password" => credential.password = Some(value),
+            "url" => {
+                let (protocol, rest) = value.split_once("://").unwrap_or(("", &value));
+                let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+                let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+                credential.protocol = Some(protocol.to_string()).filter(|value| !value.is_empty());
+                credential.host = host.to_string();
+                credential.path = Some(path.to_string()).filter(|value| !value.is_empty());
+            }
+            _ => {}
+        }
+    }
+    Ok(credential)
+}
+
+fn write_credential(mut output: impl Write, found: &GitCredential) -> io::Result<()> {
+    let mut lines = Vec::new();
+    if let Some(username) = &found.username {
+        lines.push(("username", username));
+    }
+    lines.push(("password", &found.password));
+
+    for (key, value) in lines {
+        if value.contains(['\n', '\0']) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("The {key} of \"{}\" cannot be passed to git", found.title),
+            ));
+        }
+        writeln!(output, "{key}={value}")?;
+    }
+    output.flush()
+}
+
+#[cfg(unix)]
+fn get(credential: &CredentialPayload) -> io::Result<()> {
+    if credential.host.is_empty() {
+        return Ok(());
+    }
+    match ControlClient::connect()?.request::<_, GitCredential>(COMMAND_CREDENTIAL_GET, credential)
+    {
+        Ok(found) => write_credential(io::stdout().lock(), &found),
+        // Nothing stored: git goes on with its next helper or the prompt.
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(unix)]
+fn save(credential: &CredentialPayload) -> io::Result<()> {
+    if credential.host.is_empty() || credential.password.is_none() {
+        return Ok(());
+    }
+    ControlClient::connect()?
+        .request::<_, StoredCredential>(COMMAND_CREDENTIAL_STORE, credential)
+        .map(|_| ())
+}
+
+#[cfg(not(unix))]
+fn get(_credential: &CredentialPayload) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "The git credential helper is only supported on Unix",
+    ))
+}
+
+#[cfg(not(unix))]
+fn save(credential: &CredentialPayload) -> io::Result<()> {
+    get(credential)
+}
+
+/// Finds the login for a git request among entries whose `URL` modules match
+/// its host like a browser fill would. Shared with the control server, which
+/// answers from the unlocked app session.
+pub(crate) fn lookup_credential(
+    vault: &VaultData,
+    credential: &CredentialPayload,
+) -> io::Result<GitCredential> {
+    let entry = matching_entries(vault, credential)
+        .into_iter()
+        .find(|entry| first_string(entry, "PASSWORD").is_some())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No login for {} in the vault", credential.host),
+            )
+        })?;
+
+    Ok(GitCredential {
+        entry_id: entry.id.clone(),
+        title: entry.title.clone(),
+        username: credential
+            .username
+            .clone()
+            .or_else(|| account_names(entry).into_iter().next()),
+        password: first_string(entry, "PASSWORD").unwrap_or_default(),
+    })
+}
+
+/// Saves a login git reports as working through the browser write queue:
+/// the matching entry gets the new password, otherwise a new entry is
+/// created. Waits until the app applied the write.
+pub(crate) fn store_credential(
+    vault: &VaultData,
+    credential: &CredentialPayload,
+) -> io::Result<StoredCredential> {
+    let password = credential
+        .password
+        .clone()
+        .filter(|password| !password.is_empty())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "password is required"))?;
+    let id = format!("git-credential-{}", now_ms());
+
+    if let Some(entry) = matching_entries(vault, credential).into_iter().next() {
+        if first_string(entry, "PASSWORD").as_deref() == Some(password.as_str()) {
+            return Ok(StoredCredential {
+                entry_id: Some(entry.id.clone()),
+                action: StoreAction::Unchanged,
+            });
+        }
+        let result = queue_update_request(
+            id,
+            git_client(),
+            UpdateEntryFromBrowserPayload {
+                entry_id: entry.id.clone(),
+                title: None,
+                username: None,
+                email: None,
+                password,
+                url: None,
+                matched_host: Some(credential.host.clone()),
+                totp: None,
+                last_updated: entry.last_updated.clone(),
+            },
+        )?;
+        return stored(result, StoreAction::Updated);
+    }
+
+    let result = queue_create_request(
+        id,
+        git_client(),
+        CreateEntryFromBrowserPayload {
+            title: credential.host.clone(),
+            username: credential.username.clone(),
+            email: None,
+            password,
+            url: Some(credential_url(credential)),
+            matched_host: Some(credential.host.clone()),
+            folder_id: None,
+            totp: None,
+            force: false,
+        },
+    )?;
+    stored(result, StoreAction::Created)
+}
+
+/// Live entries matching host, protocol, path and username of the request,
+/// best first. Only URLs with the requested scheme count. When git sends a
+/// path, entries whose URL has a different path are left out and entries for
+/// exactly that path rank above host-only URLs.
+fn matching_entries<'a>(
+    vault: &'a VaultData,
+    credential: &CredentialPayload,
+) -> Vec<&'a VaultEntry> {
+    let requested_protocol = requested_protocol(credential);
+    let requested_path = credential.path.as_deref().map(normalize_path);
+    let mut matches = search_entries_by_domain(vault, &credential.host)
+        .into_iter()
+        .filter_map(|suggestion| {
+            vault
+                .values
+                .iter()
+                .find(|entry| entry.id == suggestion.entry_id && entry.deleted_at.is_none())
+        })
+        .filter(|entry| match &credential.username {
+            Some(username) => account_names(entry).contains(username),
+            None => true,
+        })
+        .filter_map(|entry| {
+            let score = entry_urls(entry)
+                .iter()
+                .filter(|(scheme, _)| *scheme == requested_protocol)
+                .map(|(_, path)| match &requested_path {
+                    Some(requested) => path_score(path, requested),
+                    None => 1,
+                })
+                .max()
+                .unwrap_or(0);
+            (score > 0).then_some((score, entry))
+        })
+        .collect::<Vec<_>>();
+
+    // Stable, so equally scored entries keep the domain ranking.
+    matches.sort_by_key(|(score, _)| Reverse(*score));
+    matches.into_iter().map(|(_, entry)| entry).collect()
+}
+
+fn path_score(stored: &str, requested: &str) -> u8 {
+    if stored.is_empty() {
+        1
+    } else if requested == stored || requested.starts_with(&format!("{stored}/")) {
+        2
+    } else {
+        0
+    }
+}
+
+/// Scheme and normalized path of each URL module.
+fn entry_urls(entry: &VaultEntry) -> Vec<(String, String)> {
+    entry
+        .modules
+        .iter()
+        .filter(|module| module.module == "URL")
+        .filter_map(|module| module.value.as_ref().and_then(|value| value.as_str()))
+        .map(|url| {
+            let (scheme, rest) = url
+                .trim()
+                .split_once("://")
+                .unwrap_or(("https", url.trim()));
+            let path = rest.split_once('/').map_or("", |(_, path)| path);
+            (
+                scheme.to_lowercase(),
+                normalize_path(path.split(['?', '#']).next().unwrap_or_default()),
+            )
+        })
+        .collect()
+}
+
+/// `org/repo.git/` and `org/repo` name the same repository.
+fn normalize_path(path: &str) -> String {
+    let path = path.trim().trim_matches('/').to_lowercase();
+    path.strip_suffix(".git")
+        .map(str::to_string)
+        .unwrap_or(path)
+}
+
+fn account_names(entry: &VaultEntry) -> Vec<String> {
+    ["USERNAME", "E_MAIL"]
+        .iter()
+        .filter_map(|module_name| first_string(entry, module_name))
+        .collect()
+}
+
+fn requested_protocol(credential: &CredentialPayload) -> String {
+    credential
+        .protocol
+        .as_deref()
+        .unwrap_or("https")
+        .to_lowercase()
+}
+
+fn credential_url(credential: &CredentialPayload) -> String {
+    let protocol = requested_protocol(credential);
+    match credential.path.as_deref().filter(|path| !path.is_empty()) {
+        Some(path) => format!("{protocol}://{}/{path}", credential.host),
+        None => format!("{protocol}://{}", credential.host),
+    }
+}
+
+fn stored(result: BrowserWriteResult, action: StoreAction) -> io::Result<StoredCredential> {
+    if !result.ok {
+        let message = result
+            .error
+            .map(|error| error.message)
+            .unwrap_or_else(|| "ClavisPass did not save the login".to_string());
+        return Err(io::Error::new(io::ErrorKind::Other, message));
+    }
+    Ok(StoredCredential {
+        entry_id: result
+            .result
+            .as_ref()
+            .and_then(|value| value.get("entryId"))
+            .and_then(|value| value.as_str())
+            .map(str::to_string),
+        action,
+    })
+}
+
+fn git_client() -> BridgeClientInfo {
+    BridgeClientInfo {
+        extension_id: "git-credential".to_string(),
+        name: Some("Git credential helper".to_string()),
+        version: None,
+        instance_id: None,
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lookup_credential, read_credential, write_credential, GitCredential};
+    use crate::{bridge::vault::VaultData, control::protocol::CredentialPayload};
+
+    fn vault() -> VaultData {
+        serde_json::from_value(serde_json::json!({
+            "values": [
+                {
+                    "id": "github",
+                    "title": "GitHub",
+                    "modules": [
+                        { "module": "URL", "value": "https://github.com" },
+                        { "module": "USERNAME", "value": "octocat" },
+                        { "module": "PASSWORD", "value": "personal-token" }
+                    ]
+                },
+                {
+                    "id": "github-work",
+                    "title": "GitHub work repo",
+                    "modules": [
+                        { "module": "URL", "value": "https://github.com/acme/app" },
+                        { "module": "USERNAME", "value": "acme-bot" },
+                        { "module": "PASSWORD", "value": "work-token" }
+                    ]
+                },
+                {
+                    "id": "gitlab-trashed",
+                    "title": "GitLab",
+                    "deletedAt": "2024-01-01T00:00:00.000Z",
+                    "modules": [
+                        { "module": "URL", "value": "https://gitlab.com" },
+                        { "module": "PASSWORD", "value": "old" }
+                    ]
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn request(host: &str, path: Option<&str>, username: Option<&str>) -> CredentialPayload {
+        CredentialPayload {
+            protocol: Some("https".to_string()),
+            host: host.to_string(),
+            path: path.map(str::to_string),
+            username: username.map(str::to_string),
+            password: None,
+        }
+    }
+
+    #[test]
+    fn reads_and_writes_the_git_protocol() {
+        let input =
+            "protocol=https\nhost=github.com\npath=acme/app.git\nwwwauth[]=Basic\n\nignored=1\n";
+        let credential = read_credential(input.as_bytes()).unwrap();
+        assert_eq!(
+            credential,
+            request("github.com", Some("acme/app.git"), None)
+        );
+
+        let from_url = read_credential("url=https://bot@git.example.com:8443/a/b\n".as_bytes());
+        assert_eq!(
+            from_url.unwrap(),
+            request("git.example.com:8443", Some("a/b"), None)
+        );
+
+        let mut output = Vec::new();
+        let found = GitCredential {
+            entry_id: "github".to_string(),
+            title: "GitHub".to_string(),
+            username: Some("octocat".to_string()),
+            password: "personal-token".to_string(),
+        };
+        write_credential(&mut output, &found).unwrap();
+        assert_eq!(output, b"username=octocat\npassword=personal-token\n");
+
+        let broken = GitCredential {
+            password: "two\nlines".to_string(),
+            ..found
+        };
+        assert!(write_credential(Vec::new(), &broken).is_err());
+    }
+
+    #[test]
+    fn matches_host_path_and_username_against_url_modules() {
+        let vault = vault();
+        let found = |credential| lookup_credential(&vault, &credential).map(|found| found.entry_id);
+
+        assert_eq!(
+            found(request("github.com", Some("acme/app.git"), None)).unwrap(),
+            "github-work"
+        );
+        assert_eq!(
+            found(request("github.com", Some("octocat/dotfiles.git"), None)).unwrap(),
+            "github"
+        );
+        assert_eq!(
+            found(request("github.com", Some("acme/app.git"), Some("octocat"))).unwrap(),
+            "github"
+        );
+
+        let without_path = lookup_credential(&vault, &request("github.com:443", None, None));
+        assert_eq!(without_path.unwrap().username.as_deref(), Some("octocat"));
+
+        assert!(found(request("github.com", None, Some("someone"))).is_err());
+        assert!(found(request("gitlab.com", None, None)).is_err());
+
+        let http = CredentialPayload {
+            protocol: Some("http".to_string()),
+            ..request("github.com", None, None)
+        };
+        assert!(found(http).is_err());
+    }
+}
@@ -0,0 +1,826 @@
+//! Headless `clavispass` client for reading vault files from scripts and CI.
+
+pub mod git_credential;
+mod reference;
+
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    path::Path,
+    process::{self, Stdio},
+    sync::Arc,
+    thread,
+};
+use zeroize::Zeroizing;
+
+use reference::{Masker, SecretReference};
+
+use crate::{
+    bridge::vault::{
+        custom_fields, fill_data_for_entry, first_string, list_entries, search_entries,
+        SearchEntrySuggestion, VaultData, VaultEntry,
+    },
+    vault::{model, totp, v1},
+};
+
+#[cfg(unix)]
+use crate::control::{
+    client::ControlClient,
+    protocol::{
+        GetPayload, SearchPayload, TotpPayload, COMMAND_GET, COMMAND_LIST, COMMAND_SEARCH,
+        COMMAND_TOTP,
+    },
+};
+
+const VAULT_ENV: &str = "CLAVISPASS_VAULT";
+const MAX_CANDIDATES: usize = 10;
+
+const USAGE: &str = "\
+Usage: clavispass [OPTIONS] [VAULT.lock] <COMMAND>
+
+Commands:
+  list                      List all entries
+  search <QUERY>...         Search entries by title, account, URL or folder
+  get <ENTRY> [--field F]   Print a field of an entry (default: password)
+  totp <ENTRY>              Print the current TOTP code of an entry
+  run [--env-file F]... -- <CMD>...
+                            Run CMD with clavispass:// references in its
+                            environment resolved
+  inject [--in-file F] [--out-file F]
+                            Render a template (default: stdin to stdout) with
+                            clavispass:// references resolved
+
+<ENTRY> is an entry id, an exact title or a search query with a single match.
+Fields: password, username, email, url, totp, title, id, note, any module name
+(e.g. pin) or the title of a custom field.
+
+References have the form clavispass://<vault>/<entry>/<field>, where <vault> is
+the file name of the vault without .lock and segments are percent-encoded.
+
+Without a vault file, clavispass reads from the running ClavisPass app, which
+must be unlocked. The app asks once per process to approve the access, and
+approvals end when the vault locks. References to the app's vault use
+clavispass as <vault>, the name of its clavispass.lock file.
+
+Options:
+  --vault <PATH>        Vault file, instead of the positional path or $CLAVISPASS_VAULT
+  --password-fd <FD>    Read the master password from this file descriptor
+  --limit <N>           Maximum number of search results
+  --json                Print JSON instead of plain text
+  --no-masking          Do not conceal resolved secrets in the output of run
+  -h, --help            Print this help
+";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    List,
+    Search {
+        query: String,
+        limit: Option<usize>,
+    },
+    Get {
+        entry: String,
+        field: String,
+    },
+    Totp {
+        entry: String,
+    },
+    Run {
+        env_files: Vec<String>,
+        command: Vec<String>,
+        mask: bool,
+    },
+    Inject {
+        input: Option<String>,
+        output: Option<String>,
+    },
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct CliArgs {
+    vault_path: Option<String>,
+    password_fd: Option<i32>,
+    json: bool,
+    command: Command,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FieldValue {
+    pub entry_id: String,
+    pub title: String,
+    pub field: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TotpValue {
+    pub entry_id: String,
+    pub title: String,
+    pub code: String,
+    pub remaining: u64,
+    pub period: u64,
+}
+
+/// Where entries are read from: a vault file decrypted with the master
+/// password, or the running desktop app.
+enum Source {
+    File {
+        vault: VaultData,
+        name: String,
+    },
+    #[cfg(unix)]
+    App(ControlClient),
+}
+
+impl Source {
+    fn open(args: &CliArgs) -> io::Result<Self> {
+        let path = args
+            .vault_path
+            .clone()
+            .or_else(|| env::var(VAULT_ENV).ok().filter(|path| !path.is_empty()));
+        let Some(path) = path else {
+            return Self::connect_app();
+        };
+
+        let content = fs::read_to_string(&path)
+            .map_err(|err| io::Error::new(err.kind(), format!("Failed to read {path}: {err}")))?;
+        let password = read_master_password(args.password_fd)?;
+        Ok(Self::File {
+            vault: bridge_vault(&v1::decrypt_vault_content(&content, &password)?)?,
+            name: vault_name(&path),
+        })
+    }
+
+    #[cfg(unix)]
+    fn connect_app() -> io::Result<Self> {
+        ControlClient::connect().map(Self::App).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("No vault file given and ClavisPass is not reachable: {err}"),
+            )
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn connect_app() -> io::Result<Self> {
+        Err(usage_error("No vault file given"))
+    }
+
+    fn list(&mut self) -> io::Result<Vec<SearchEntrySuggestion>> {
+        match self {
+            Self::File { vault, .. } => Ok(list_entries(vault)),
+            #[cfg(unix)]
+            Self::App(client) => client.request(COMMAND_LIST, ()),
+        }
+    }
+
+    fn search(
+        &mut self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> io::Result<Vec<SearchEntrySuggestion>> {
+        match self {
+            Self::File { vault, .. } => Ok(search_entries(vault, query, limit)),
+            #[cfg(unix)]
+            Self::App(client) => client.request(
+                COMMAND_SEARCH,
+                SearchPayload {
+                    query: query.to_string(),
+                    limit,
+                },
+            ),
+        }
+    }
+
+    fn field(&mut self, entry: &str, field: &str) -> io::Result<FieldValue> {
+        match self {
+            Self::File { vault, .. } => lookup_field(vault, entry, field),
+            #[cfg(unix)]
+            Self::App(client) => client.request(
+                COMMAND_GET,
+                GetPayload {
+                    entry: entry.to_string(),
+                    field: field.to_string(),
+                },
+            ),
+        }
+    }
+
+    fn totp(&mut self, entry: &str) -> io::Result<TotpValue> {
+        match self {
+            Self::File { vault, .. } => lookup_totp(vault, entry),
+            #[cfg(unix)]
+            Self::App(client) => client.request(
+                COMMAND_TOTP,
+                TotpPayload {
+                    entry: entry.to_string(),
+                },
+            ),
+        }
+    }
+
+    /// The `<vault>` segment references to the open vault must carry.
+    fn open_vault_name(&self) -> &str {
+        match self {
+            Self::File { name, .. } => name,
+            #[cfg(unix)]
+            Self::App(_) => APP_VAULT_NAME,
+        }
+    }
+
+    fn resolve(&mut self, reference: &SecretReference) -> io::Result<String> {
+        let open = self.open_vault_name();
+        if !reference.vault.eq_ignore_ascii_case(open) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "Reference to vault \"{}\" but \"{open}\" is open",
+                    reference.vault
+                ),
+            ));
+        }
+        self.field(&reference.entry, &reference.field)
+            .map(|found| found.value)
+    }
+}
+
+/// Entry point of the `clavispass` binary. Returns the process exit code.
+pub fn run() -> io::Result<i32> {
+    let args = parse_args(env::args().skip(1).collect())?;
+    if args.command == Command::Help {
+        print!("{USAGE}");
+        return Ok(0);
+    }
+
+    let mut source = Source::open(&args)?;
+
+    match &args.command {
+        Command::List => print_suggestions(&source.list()?, args.json),
+        Command::Search { query, limit } => {
+            print_suggestions(&source.search(query, *limit)?, args.json)
+        }
+        Command::Run {
+            env_files,
+            command,
+            mask,
+        } => {
+            return run_with_secrets(&mut source, env_files, command, *mask);
+        }
+        Command::Inject { input, output } => {
+            inject_secrets(&mut source, input.as_deref(), output.as_deref())
+        }
+        Command::Get { entry, field } => {
+            let found = source.field(entry, field)?;
+            if args.json {
+                print_json(&found)
+            } else {
+                println!("{}", found.value);
+                Ok(())
+            }
+        }
+        Command::Totp { entry } => {
+            let found = source.totp(entry)?;
+            if args.json {
+                print_json(&found)
+            } else {
+                println!("{}", found.code);
+                Ok(())
+            }
+        }
+        Command::Help => Ok(()),
+    }
+    .map(|_| 0)
+}
+
+fn parse_args(args: Vec<String>) -> io::Result<CliArgs> {
+    let mut vault_path = None;
+    let mut password_fd = None;
+    let mut json = false;
+    let mut field = None;
+    let mut limit = None;
+    let mut env_files = Vec::new();
+    let mut input = None;
+    let mut output = None;
+    let mut mask = true;
+    let mut positional = Vec::new();
+    let mut child_command = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                return Ok(CliArgs {
+                    vault_path,
+                    password_fd,
+                    json,
+                    command: Command::Help,
+                })
+            }
+            "--" => {
+                child_command.extend(args.by_ref());
+                break;
+            }
+            "--json" => json = true,
+            "--no-masking" => mask = false,
+            "--env-file" => env_files.push(flag_value(&mut args, "--env-file")?),
+            "--in-file" => input = Some(flag_value(&mut args, "--in-file")?),
+            "--out-file" => output = Some(flag_value(&mut args, "--out-file")?),
+            "--vault" => vault_path = Some(flag_value(&mut args, "--vault")?),
+            "--field" => field = Some(flag_value(&mut args, "--field")?),
+            "--password-fd" => {
+                password_fd = Some(
+                    flag_value(&mut args, "--password-fd")?
+                        .parse::<i32>()
+                        .ok()
+                        .filter(|fd| *fd >= 0)
+                        .ok_or_else(|| usage_error("--password-fd expects a descriptor number"))?,
+                )
+            }
+            "--limit" => {
+                limit = Some(
+                    flag_value(&mut args, "--limit")?
+                        .parse::<usize>()
+                        .map_err(|_| usage_error("--limit expects a number"))?,
+                )
+            }
+            flag if flag.starts_with("--") => {
+                return Err(usage_error(&format!("Unknown option {flag}")))
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    if vault_path.is_none() {
+        vault_path = crate::get_requested_vault_file_path(&positional);
+        if let Some(path) = &vault_path {
+            positional.retain(|arg| arg.trim_matches('"') != path);
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let command = match positional.next().as_deref() {
+        None | Some("help") => Command::Help,
+        Some("list") => Command::List,
+        Some("search") => Command::Search {
+            query: positional.collect::<Vec<_>>().join(" "),
+            limit,
+        },
+        Some("get") => Command::Get {
+            entry: required_entry(positional.collect())?,
+            field: field.unwrap_or_else(|| "password".to_string()),
+        },
+        Some("totp") => Command::Totp {
+            entry: required_entry(positional.collect())?,
+        },
+        Some("run") if child_command.is_empty() => {
+            return Err(usage_error("run expects a command after --"))
+        }
+        Some("run") => Command::Run {
+            env_files,
+            command: child_command,
+            mask,
+        },
+        Some("inject") => Command::Inject { input, output },
+        Some(other) => return Err(usage_error(&format!("Unknown command {other}"))),
+    };
+
+    Ok(CliArgs {
+        vault_path,
+        password_fd,
+        json,
+        command,
+    })
+}
+
+fn flag_value(args: &mut impl Iterator<Item = String>, flag: &str) -> io::Result<String> {
+    args.next()
+        .ok_or_else(|| usage_error(&format!("{flag} expects a value")))
+}
+
+fn required_entry(words: Vec<String>) -> io::Result<String> {
+    let entry = words.join(" ");
+    if entry.trim().is_empty() {
+        return Err(usage_error("Missing <ENTRY>"));
+    }
+    Ok(entry)
+}
+
+fn read_master_password(fd: Option<i32>) -> io::Result<Zeroizing<String>> {
+    let mut password = match fd {
+        Some(fd) => read_password_fd(fd)?,
+        None => Zeroizing::new(rpassword::prompt_password("Master password: ")?),
+    };
+
+    let trimmed = password.trim_end_matches(['\r', '\n']).len();
+    password.truncate(trimmed);
+    Ok(password)
+}
+
+#[cfg(unix)]
+fn read_password_fd(fd: i32) -> io::Result<Zeroizing<String>> {
+    use std::os::fd::BorrowedFd;
+
+    // SAFETY: `F_GETFD` only queries the descriptor flags.
+    if fd < 0 || unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        return Err(usage_error(&format!(
+            "--password-fd {fd} is not an open file descriptor"
+        )));
+    }
+    // SAFETY: `fcntl` confirmed the descriptor is open, and it is only borrowed
+    // to duplicate it. The duplicate is closed after reading; the caller's
+    // descriptor, which may be stdout or stderr, stays open.
+    let borrowed = unsafe { BorrowedFd::borrow_raw(fd) };
+    let mut file = fs::File::from(borrowed.try_clone_to_owned()?);
+    let mut password = Zeroizing::new(String::new());
+    file.read_to_string(&mut password)?;
+    Ok(password)
+}
+
+#[cfg(not(unix))]
+fn read_password_fd(_fd: i32) -> io::Result<Zeroizing<String>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "--password-fd is only supported on Unix",
+    ))
+}
+
+/// The app always stores its vault as `clavispass.lock`.
+#[cfg(unix)]
+const APP_VAULT_NAME: &str = "clavispass";
+
+/// The `<vault>` segment of references to this file: its name without `.lock`.
+fn vault_name(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Looks up `field` of the entry `query` resolves to. Shared with the
+/// control server, which answers from the unlocked app session.
+pub(crate) fn lookup_field(vault: &VaultData, query: &str, field: &str) -> io::Result<FieldValue> {
+    let entry = resolve_entry(vault, query)?;
+    let value = field_value(vault, entry, field).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("\"{}\" has no {field}", entry.title),
+        )
+    })?;
+    Ok(FieldValue {
+        entry_id: entry.id.clone(),
+        title: entry.title.clone(),
+        field: field.to_string(),
+        value,
+    })
+}
+
+pub(crate) fn lookup_totp(vault: &VaultData, query: &str) -> io::Result<TotpValue> {
+    let entry = resolve_entry(vault, query)?;
+    let uri = first_string(entry, "TOTP").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("\"{}\" has no TOTP", entry.title),
+        )
+    })?;
+    let code = totp::current_code(&uri)?;
+    Ok(TotpValue {
+        entry_id: entry.id.clone(),
+        title: entry.title.clone(),
+        code: code.code,
+        remaining: code.remaining,
+        period: code.period,
+    })
+}
+
+/// Runs `command` with references in the inherited environment and in
+/// `env_files` resolved. With `mask`, resolved values are concealed in the
+/// child's stdout and stderr.
+fn run_with_secrets(
+    source: &mut Source,
+    env_files: &[String],
+    command: &[String],
+    mask: bool,
+) -> io::Result<i32> {
+    let mut variables = env::vars()
+        .filter(|(_, value)| value.contains(reference::SCHEME))
+        .collect::<Vec<_>>();
+    for path in env_files {
+        variables.extend(reference::parse_env_file(&fs::read_to_string(path)?)?);
+    }
+
+    let mut secrets = Vec::new();
+    let mut resolved = Vec::new();
+    for (key, value) in variables {
+        let (value, values) = reference::render(&value, |found| source.resolve(found))?;
+        secrets.extend(values);
+        resolved.push((key, value));
+    }
+
+    let mut child = process::Command::new(&command[0]);
+    child.args(&command[1..]).envs(resolved);
+    if !mask {
+        return Ok(child.status()?.code().unwrap_or(1));
+    }
+
+    let mut child = child
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let masker = Arc::new(Masker::new(&secrets));
+    let stdout = child.stdout.take().map(|pipe| {
+        let masker = Arc::clone(&masker);
+        thread::spawn(move || forward_masked(pipe, io::stdout(), &masker))
+    });
+    let stderr = child.stderr.take().map(|pipe| {
+        let masker = Arc::clone(&masker);
+        thread::spawn(move || forward_masked(pipe, io::stderr(), &masker))
+    });
+
+    let status = child.wait()?;
+    for pump in [stdout, stderr].into_iter().flatten() {
+        let _ = pump.join();
+    }
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Copies `pipe` to `out` line by line with secrets masked. A line ending in
+/// what may be the start of a multi-line secret is held back until the next
+/// line shows whether the secret continues.
+fn forward_masked(pipe: impl Read, mut out: impl Write, masker: &Masker) -> io::Result<()> {
+    let mut reader = BufReader::new(pipe);
+    let mut pending = Vec::new();
+    while reader.read_until(b'\n', &mut pending)? > 0 {
+        let (masked, consumed) = masker.mask_partial(&pending, false);
+        out.write_all(&masked)?;
+        out.flush()?;
+        pending.drain(..consumed);
+    }
+    out.write_all(&masker.mask(&pending))?;
+    out.flush()
+}
+
+fn inject_secrets(
+    source: &mut Source,
+    input: Option<&str>,
+    output: Option<&str>,
+) -> io::Result<()> {
+    let template = match input {
+        Some(path) => fs::read_to_string(path)?,
+        None => io::read_to_string(io::stdin())?,
+    };
+    let (rendered, _) = reference::render(&template, |found| source.resolve(found))?;
+
+    match output {
+        Some(path) => write_private_file(path, rendered.as_bytes()),
+        None => io::stdout().write_all(rendered.as_bytes()),
+    }
+}
+
+/// Writes rendered secrets to a file only the current user can read. The mode
+/// only applies to new files, so an existing file is narrowed on the open
+/// handle before anything is written.
+fn write_private_file(path: &str, content: &[u8]) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(content)
+}
+
+/// Converts the decrypted vault into the bridge model the matching logic
+/// works on. Trashed entries are left out, as in the browser session.
+fn bridge_vault(vault: &model::VaultData) -> io::Result<VaultData> {
+    let value =
+        serde_json::to_value(vault).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    let mut vault = serde_json::from_value::<VaultData>(value)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    vault.values.retain(|entry| entry.deleted_at.is_none());
+    Ok(vault)
+}
+
+/// Resolves `<ENTRY>` by id, then by exact title, then by a search with a
+/// single result. Ambiguous names fail with the candidates listed.
+fn resolve_entry<'a>(vault: &'a VaultData, query: &str) -> io::Result<&'a VaultEntry> {
+    if let Some(entry) = vault.values.iter().find(|entry| entry.id == query) {
+        return Ok(entry);
+    }
+
+    let lowered = query.to_lowercase();
+    let by_title = vault
+        .values
+        .iter()
+        .filter(|entry| entry.title.to_lowercase() == lowered)
+        .collect::<Vec<_>>();
+    let candidates = match by_title.as_slice() {
+        [entry] => return Ok(entry),
+        [] => search_entries(vault, query, Some(MAX_CANDIDATES))
+            .into_iter()
+            .filter_map(|suggestion| {
+                vault
+                    .values
+                    .iter()
+                    .find(|entry| entry.id == suggestion.entry_id)
+            })
+            .collect::<Vec<_>>(),
+        _ => by_title,
+    };
+
+    match candidates.as_slice() {
+        [entry] => Ok(entry),
+        [] => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No entry matches \"{query}\""),
+        )),
+        _ => {
+            let names = candidates
+                .iter()
+                .map(|entry| format!("  {}\t{}", entry.id, entry.title))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Err(usage_error(&format!(
+                "\"{query}\" matches several entries, use the id:\n{names}"
+            )))
+        }
+    }
+}
+
+fn field_value(vault: &VaultData, entry: &VaultEntry, field: &str) -> Option<String> {
+    match field.to_lowercase().as_str() {
+        "password" => fill_data_for_entry(vault, &entry.id).map(|fill| fill.password),
+        "username" => first_string(entry, "USERNAME").or_else(|| first_string(entry, "E_MAIL")),
+        "email" => first_string(entry, "E_MAIL"),
+        "title" => Some(entry.title.clone()),
+        "id" => Some(entry.id.clone()),
+        name => first_string(entry, &name.to_uppercase()).or_else(|| {
+            custom_fields(entry)
+                .into_iter()
+                .find(|custom| {
+                    custom.title.to_lowercase() == name
+                        || custom
+                            .field_name
+                            .as_deref()
+                            .map(str::to_lowercase)
+                            .as_deref()
+                            == Some(name)
+                })
+                .map(|custom| custom.value)
+        }),
+    }
+}
+
+fn print_suggestions(suggestions: &[SearchEntrySuggestion], json: bool) -> io::Result<()> {
+    if json {
+        return print_json(&suggestions);
+    }
+
+    for suggestion in suggestions {
+        let account = suggestion
+            .username
+            .as_deref()
+            .or(suggestion.email.as_deref())
+            .unwrap_or_default();
+        println!("{}\t{}\t{}", suggestion.entry_id, suggestion.title, account);
+    }
+    Ok(())
+}
+
+fn print_json<T: Serialize>(value: &T) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    println!("{json}");
+    Ok(())
+}
+
+fn usage_error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use super::read_password_fd;
+    use super::{
+        bridge_vault, field_value, parse_args, reference::parse_reference, resolve_entry, CliArgs,
+        Command, Source,
+    };
+    use crate::vault::model::VaultData;
+
+    const FULL_EXPORT: &str = include_str!("../vault/fixtures/full-export.json");
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_vault_path_flags_and_command() {
+        let parsed = parse_args(args(&[
+            "--json",
+            "work.lock",
+            "get",
+            "GitHub",
+            "--field",
+            "API token",
+            "--password-fd",
+            "3",
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            parsed,
+            CliArgs {
+                vault_path: Some("work.lock".into()),
+                password_fd: Some(3),
+                json: true,
+                command: Command::Get {
+                    entry: "GitHub".into(),
+                    field: "API token".into(),
+                },
+            }
+        );
+        assert!(parse_args(args(&["work.lock", "totp"])).is_err());
+        assert!(parse_args(args(&["work.lock", "--nope", "list"])).is_err());
+        assert!(parse_args(args(&["work.lock", "--password-fd", "-1", "list"])).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn password_fds_are_checked_and_left_open() {
+        use std::{io::Write, os::fd::AsRawFd};
+
+        let path = std::env::temp_dir().join(format!("clavispass-fd-{}", std::process::id()));
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(b"hunter2")
+            .unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let fd = file.as_raw_fd();
+
+        assert_eq!(read_password_fd(fd).unwrap().as_str(), "hunter2");
+        assert_ne!(unsafe { libc::fcntl(fd, libc::F_GETFD) }, -1);
+        assert!(read_password_fd(-1).is_err());
+        assert!(read_password_fd(987_654).is_err());
+
+        drop(file);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn resolves_entries_and_fields_like_the_bridge() {
+        let vault = bridge_vault(&serde_json::from_str::<VaultData>(FULL_EXPORT).unwrap()).unwrap();
+
+        // Trashed entries are not visible.
+        assert!(resolve_entry(&vault, "entry-future").is_err());
+
+        let github = resolve_entry(&vault, "github").unwrap();
+        assert_eq!(github.id, "entry-login");
+        assert_eq!(
+            field_value(&vault, github, "password").as_deref(),
+            Some("correct horse battery staple")
+        );
+        assert_eq!(
+            field_value(&vault, github, "token").as_deref(),
+            Some("ghp_example")
+        );
+        assert_eq!(
+            field_value(&vault, github, "url").as_deref(),
+            Some("https://github.com/login")
+        );
+
+        let wifi = resolve_entry(&vault, "home wi").unwrap();
+        assert_eq!(field_value(&vault, wifi, "pin").as_deref(), Some("4711"));
+        assert_eq!(
+            field_value(&vault, wifi, "password").as_deref(),
+            Some("wifi-passphrase")
+        );
+    }
+
+    #[test]
+    fn references_must_name_the_open_vault() {
+        let vault = bridge_vault(&serde_json::from_str::<VaultData>(FULL_EXPORT).unwrap()).unwrap();
+        let mut source = Source::File {
+            vault,
+            name: "work".into(),
+        };
+
+        let matching = parse_reference("clavispass://Work/github/password").unwrap();
+        assert_eq!(
+            source.resolve(&matching).unwrap(),
+            "correct horse battery staple"
+        );
+        let other = parse_reference("clavispass://home/github/password").unwrap();
+        assert!(source.resolve(&other).is_err());
+    }
+}
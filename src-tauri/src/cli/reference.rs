@@ -0,0 +1,261 @@
+//! `clavispass://<vault>/<entry-or-id>/<field>` secret references as used in
+//! env files and templates. Segments are percent-encoded, so an entry titled
+//! `My Bank` is written `clavispass://personal/My%20Bank/password`.
+
+use std::io;
+
+pub const SCHEME: &str = "clavispass://";
+pub const MASK: &[u8] = b"<concealed by ClavisPass>";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretReference {
+    pub vault: String,
+    pub entry: String,
+    pub field: String,
+}
+
+pub fn parse_reference(input: &str) -> io::Result<SecretReference> {
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid secret reference {input}, expected {SCHEME}<vault>/<entry>/<field>"),
+        )
+    };
+
+    let segments = input
+        .strip_prefix(SCHEME)
+        .ok_or_else(invalid)?
+        .split('/')
+        .map(percent_decode)
+        .collect::<Vec<_>>();
+    match segments.as_slice() {
+        [vault, entry, field] if segments.iter().all(|segment| !segment.is_empty()) => {
+            Ok(SecretReference {
+                vault: vault.clone(),
+                entry: entry.clone(),
+                field: field.clone(),
+            })
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Replaces every reference in `text` with its resolved value and returns the
+/// rendered text together with the values that were inserted.
+pub fn render<F>(text: &str, mut resolve: F) -> io::Result<(String, Vec<String>)>
+where
+    F: FnMut(&SecretReference) -> io::Result<String>,
+{
+    let mut output = String::with_capacity(text.len());
+    let mut secrets = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find(SCHEME) {
+        let end = rest[start..]
+            .find(|char: char| char.is_whitespace() || "\"'`<>()[]{},;".contains(char))
+            .map_or(rest.len(), |length| start + length);
+        let value = resolve(&parse_reference(&rest[start..end])?)?;
+
+        output.push_str(&rest[..start]);
+        output.push_str(&value);
+        secrets.push(value);
+        rest = &rest[end..];
+    }
+
+    output.push_str(rest);
+    Ok((output, secrets))
+}
+
+/// Parses `KEY=VALUE` lines of an env file. Blank lines, `#` comments and an
+/// `export ` prefix are allowed; values may be wrapped in matching quotes.
+pub fn parse_env_file(content: &str) -> io::Result<Vec<(String, String)>> {
+    let mut variables = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .filter(|(key, _)| !key.trim().is_empty())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Invalid env file line {}", index + 1),
+                )
+            })?;
+
+        let value = value.trim();
+        let unquoted = ['"', '\'']
+            .into_iter()
+            .find_map(|quote| {
+                value
+                    .strip_prefix(quote)
+                    .and_then(|inner| inner.strip_suffix(quote))
+            })
+            .unwrap_or(value);
+        variables.push((key.trim().to_string(), unquoted.to_string()));
+    }
+
+    Ok(variables)
+}
+
+/// Replaces resolved secrets in child process output.
+pub struct Masker {
+    secrets: Vec<Vec<u8>>,
+}
+
+impl Masker {
+    pub fn new(secrets: &[String]) -> Self {
+        let mut secrets = secrets
+            .iter()
+            .filter(|secret| !secret.is_empty())
+            .map(|secret| secret.as_bytes().to_vec())
+            .collect::<Vec<_>>();
+        // Longest first, so a secret containing another one is masked whole.
+        secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+        secrets.dedup();
+        Self { secrets }
+    }
+
+    pub fn mask(&self, input: &[u8]) -> Vec<u8> {
+        self.mask_partial(input, true).0
+    }
+
+    /// Masks `input` up to a tail that may be the start of a secret continuing
+    /// in the next read, and returns the output with the number of bytes
+    /// consumed. With `end` set, everything is consumed.
+    pub fn mask_partial(&self, input: &[u8], end: bool) -> (Vec<u8>, usize) {
+        let mut output = Vec::with_capacity(input.len());
+        let mut index = 0;
+
+        'outer: while index < input.len() {
+            let rest = &input[index..];
+            for secret in &self.secrets {
+                if rest.starts_with(secret) {
+                    output.extend_from_slice(MASK);
+                    index += secret.len();
+                    continue 'outer;
+                }
+            }
+            if !end && self.secrets.iter().any(|secret| secret.starts_with(rest)) {
+                break;
+            }
+            output.push(input[index]);
+            index += 1;
+        }
+
+        (output, index)
+    }
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let decoded = (bytes[index] == b'%')
+            .then(|| input.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match decoded {
+            Some(byte) => {
+                output.push(byte);
+                index += 3;
+            }
+            None => {
+                output.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_env_file, parse_reference, render, Masker, SecretReference};
+
+    #[test]
+    fn parses_percent_encoded_references() {
+        assert_eq!(
+            parse_reference("clavispass://work/My%20Bank/API%20token").unwrap(),
+            SecretReference {
+                vault: "work".into(),
+                entry: "My Bank".into(),
+                field: "API token".into(),
+            }
+        );
+        assert!(parse_reference("clavispass://work/GitHub").is_err());
+        assert!(parse_reference("clavispass://work//password").is_err());
+    }
+
+    #[test]
+    fn renders_references_inside_templates() {
+        let template = "db:\n  password: \"clavispass://work/db/password\"\n\
+                        token=clavispass://work/GitHub/token # ci\n";
+        let (rendered, secrets) = render(template, |reference| {
+            Ok(format!("<{}:{}>", reference.entry, reference.field))
+        })
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "db:\n  password: \"<db:password>\"\ntoken=<GitHub:token> # ci\n"
+        );
+        assert_eq!(secrets.len(), 2);
+    }
+
+    #[test]
+    fn parses_env_files() {
+        let variables = parse_env_file(
+            "# comment\n\nexport DB_PASSWORD=\"clavispass://work/db/password\"\nPLAIN = value\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            variables,
+            vec![
+                (
+                    "DB_PASSWORD".to_string(),
+                    "clavispass://work/db/password".to_string()
+                ),
+                ("PLAIN".to_string(), "value".to_string()),
+            ]
+        );
+        assert!(parse_env_file("NOT A VARIABLE").is_err());
+    }
+
+    #[test]
+    fn masks_secrets_in_output() {
+        let masker = Masker::new(&["hunter2".into(), "hunter".into(), String::new()]);
+        assert_eq!(
+            masker.mask(b"pw=hunter2 and hunter\n"),
+            b"pw=<concealed by ClavisPass> and <concealed by ClavisPass>\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn holds_back_secrets_split_across_reads() {
+        let masker = Masker::new(&["-----BEGIN KEY-----\nabc\n-----END KEY-----".into()]);
+
+        let (output, consumed) = masker.mask_partial(b"key: -----BEGIN KEY-----\n", false);
+        assert_eq!(output, b"key: ".to_vec());
+        assert_eq!(consumed, 5);
+
+        let (output, consumed) =
+            masker.mask_partial(b"-----BEGIN KEY-----\nabc\n-----END KEY-----\n", false);
+        assert_eq!(output, b"<concealed by ClavisPass>\n".to_vec());
+        assert_eq!(consumed, 42);
+
+        assert_eq!(
+            masker.mask_partial(b"-----BEGIN", true).0,
+            b"-----BEGIN".to_vec()
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! The system clipboard. Secrets copied through `copy_secret` are cleared
+//! after a timeout, but only while the clipboard still holds them, so a value
+//! the user copied afterwards is left alone.
+
+use arboard::Clipboard;
+use sha2::{Digest, Sha256};
+use std::{
+    io,
+    sync::{Mutex, MutexGuard},
+    thread,
+    time::Duration,
+};
+
+#[cfg(target_os = "linux")]
+mod wayland;
+#[cfg(target_os = "linux")]
+mod x11;
+
+/// Clipboard managers that see this type with this value skip the copy.
+#[cfg(target_os = "linux")]
+const PASSWORD_HINT_MIME: &str = "x-kde-passwordManagerHint";
+#[cfg(target_os = "linux")]
+const PASSWORD_HINT_VALUE: &[u8] = b"secret";
+#[cfg(target_os = "linux")]
+const TEXT_MIME_TYPES: [&str; 4] = [
+    "text/plain;charset=utf-8",
+    "text/plain",
+    "UTF8_STRING",
+    "STRING",
+];
+
+/// The bytes a paste receives. The `STRING` format is Latin-1, so with
+/// `latin1` set, values outside it are refused rather than sent as UTF-8.
+#[cfg(target_os = "linux")]
+fn encode_text(value: &str, latin1: bool) -> Option<std::borrow::Cow<'_, [u8]>> {
+    if !latin1 {
+        return Some(value.as_bytes().into());
+    }
+    value
+        .chars()
+        .map(|char| u8::try_from(u32::from(char)).ok())
+        .collect::<Option<Vec<_>>>()
+        .map(Into::into)
+}
+
+/// Kept open for the life of the app: on X11 and Wayland this process serves
+/// the clipboard contents, and they disappear with the handle.
+static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+
+static OWNED: Mutex<OwnedCopy> = Mutex::new(OwnedCopy {
+    hash: None,
+    generation: 0,
+});
+
+/// The last secret copied by `copy_secret`. Every copy bumps `generation`,
+/// which retires the clear timers of earlier copies.
+struct OwnedCopy {
+    hash: Option<[u8; 32]>,
+    generation: u64,
+}
+
+impl OwnedCopy {
+    fn holds(&self, generation: u64, current: Option<&str>) -> bool {
+        self.generation == generation && self.hash.is_some() && current.map(hash_value) == self.hash
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn with_clipboard<T>(
+    operation: impl FnOnce(&mut Clipboard) -> Result<T, arboard::Error>,
+) -> io::Result<T> {
+    let mut guard = lock(&CLIPBOARD);
+    let clipboard = match guard.as_mut() {
+        Some(clipboard) => clipboard,
+        None => guard.insert(Clipboard::new().map_err(into_io)?),
+    };
+    operation(clipboard).map_err(into_io)
+}
+
+fn into_io(error: arboard::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error.to_string())
+}
+
+fn hash_value(value: &str) -> [u8; 32] {
+    Sha256::digest(value.as_bytes()).into()
+}
+
+/// Copies `value` marked as a password, so clipboard managers and history
+/// skip it: `x-kde-passwordManagerHint` on X11 and Wayland, the exclusion
+/// formats on Windows and `org.nspasteboard.ConcealedType` on macOS. On Linux
+/// only the CLIPBOARD selection is written, never PRIMARY, which any middle
+/// click would paste.
+pub fn set_secret_text(value: &str) -> io::Result<()> {
+    with_clipboard(|clipboard| {
+        let set = clipboard.set();
+        #[cfg(all(unix, not(target_os = "macos")))]
+        let set = {
+            use arboard::{LinuxClipboardKind, SetExtLinux};
+            set.clipboard(LinuxClipboardKind::Clipboard)
+                .exclude_from_history()
+        };
+        #[cfg(target_os = "windows")]
+        let set = {
+            use arboard::SetExtWindows;
+            set.exclude_from_monitoring()
+                .exclude_from_cloud()
+                .exclude_from_history()
+        };
+        #[cfg(target_os = "macos")]
+        let set = {
+            use arboard::SetExtApple;
+            set.exclude_from_history()
+        };
+        set.text(value)
+    })
+}
+
+/// `None` when the clipboard is empty or holds something other than text.
+pub fn get_text() -> io::Result<Option<String>> {
+    with_clipboard(|clipboard| match clipboard.get_text() {
+        Ok(text) => Ok(Some(text)),
+        Err(arboard::Error::ContentNotAvailable) => Ok(None),
+        Err(error) => Err(error),
+    })
+}
+
+pub fn clear() -> io::Result<()> {
+    lock(&OWNED).hash = None;
+    clear_platform()
+}
+
+/// Copies `value` and, with `clear_after`, clears it again once that time has
+/// passed unless the clipboard changed in the meantime.
+pub fn copy_secret(value: &str, clear_after: Option<Duration>) -> io::Result<()> {
+    set_secret_text(value)?;
+    let generation = {
+        let mut owned = lock(&OWNED);
+        owned.generation = owned.generation.wrapping_add(1);
+        owned.hash = Some(hash_value(value));
+        owned.generation
+    };
+
+    if let Some(delay) = clear_after {
+        thread::spawn(move || {
+            thread::sleep(delay);
+            if let Err(error) = clear_if_owned(generation) {
+                eprintln!("Failed to clear the clipboard: {error}");
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Serves `values` to the next paste requests, one value per paste and in
+/// order, then gives up the clipboard. Passing a secret twice allows two
+/// pastes; passing a username and a password pastes them one after the other.
+/// Whatever was not pasted within `timeout` is dropped.
+///
+/// Every paste counts, including one made by a clipboard manager that ignores
+/// the password hint.
+#[cfg(target_os = "linux")]
+pub fn copy_for_pastes(values: Vec<String>, timeout: Duration) -> io::Result<()> {
+    if values.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Nothing to copy",
+        ));
+    }
+
+    // Earlier clear timers must not touch the new selection.
+    {
+        let mut owned = lock(&OWNED);
+        owned.generation = owned.generation.wrapping_add(1);
+        owned.hash = None;
+    }
+
+    let deadline = std::time::Instant::now() + timeout;
+    let values = std::collections::VecDeque::from(values);
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        match wayland::serve(values.clone(), deadline) {
+            Ok(_) => return Ok(()),
+            Err(error) if error.kind() == io::ErrorKind::Unsupported => {}
+            Err(error) => return Err(error),
+        }
+    }
+    x11::serve(None, values, deadline).map(|_| ())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn copy_for_pastes(_values: Vec<String>, _timeout: Duration) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Paste-limited copies are only available on Linux",
+    ))
+}
+
+/// Waits until `fd` has data to read. Returns false once `timeout` passed.
+#[cfg(target_os = "linux")]
+fn wait_readable(fd: std::os::fd::BorrowedFd<'_>, timeout: Duration) -> io::Result<bool> {
+    use std::os::fd::AsRawFd;
+
+    let mut poll_fd = libc::pollfd {
+        fd: fd.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let timeout_ms = timeout.as_millis().min(i32::MAX as u128) as i32;
+    match unsafe { libc::poll(&mut poll_fd, 1, timeout_ms) } {
+        -1 => {
+            let error = io::Error::last_os_error();
+            if error.kind() == io::ErrorKind::Interrupted {
+                Ok(false)
+            } else {
+                Err(error)
+            }
+        }
+        0 => Ok(false),
+        _ => Ok(true),
+    }
+}
+
+fn clear_if_owned(generation: u64) -> io::Result<()> {
+    // Checked before reading: a read counts as a paste for `copy_for_pastes`.
+    {
+        let owned = lock(&OWNED);
+        if owned.generation != generation || owned.hash.is_none() {
+            return Ok(());
+        }
+    }
+
+    let current = get_text()?;
+    let mut owned = lock(&OWNED);
+    if !owned.holds(generation, current.as_deref()) {
+        return Ok(());
+    }
+
+    owned.hash = None;
+    drop(owned);
+    clear_platform()
+}
+
+/// Windows keeps a placeholder text instead of an empty clipboard, so paste
+/// targets and clipboard history show that the secret was removed.
+#[cfg(target_os = "windows")]
+fn clear_platform() -> io::Result<()> {
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::DataExchange::{
+        CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData,
+    };
+    use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+
+    const CF_UNICODETEXT_FORMAT: u32 = 13;
+
+    let to_io =
+        |error: windows::core::Error| io::Error::new(io::ErrorKind::Other, error.to_string());
+    let replacement = "Clipboard cleared";
+    let utf16: Vec<u16> = replacement
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    let size_in_bytes = utf16.len() * std::mem::size_of::<u16>();
+
+    unsafe {
+        OpenClipboard(None).map_err(to_io)?;
+
+        let empty_result = EmptyClipboard().map_err(to_io);
+        if empty_result.is_ok() {
+            let memory = GlobalAlloc(GMEM_MOVEABLE, size_in_bytes).map_err(to_io)?;
+            let locked_ptr = GlobalLock(memory) as *mut u16;
+
+            if locked_ptr.is_null() {
+                let _ = CloseClipboard();
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Failed to lock clipboard memory",
+                ));
+            }
+
+            std::ptr::copy_nonoverlapping(utf16.as_ptr(), locked_ptr, utf16.len());
+            let _ = GlobalUnlock(memory);
+
+            SetClipboardData(CF_UNICODETEXT_FORMAT, HANDLE(memory.0)).map_err(to_io)?;
+        }
+
+        let close_result = CloseClipboard().map_err(to_io);
+        empty_result?;
+        close_result?;
+    }
+    Ok(())
+}
+
+/// Gives up the selection on X11 and Wayland, and empties the pasteboard on
+/// macOS.
+#[cfg(not(target_os = "windows"))]
+fn clear_platform() -> io::Result<()> {
+    with_clipboard(|clipboard| clipboard.clear())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_value, OwnedCopy};
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn string_pastes_are_latin1() {
+        use super::encode_text;
+
+        assert_eq!(
+            encode_text("pässword", false).unwrap(),
+            "pässword".as_bytes()
+        );
+        assert_eq!(encode_text("pässword", true).unwrap(), &b"p\xe4ssword"[..]);
+        assert!(encode_text("price: 5€", true).is_none());
+    }
+
+    #[test]
+    fn only_the_latest_unchanged_copy_is_owned() {
+        let owned = OwnedCopy {
+            hash: Some(hash_value("hunter2")),
+            generation: 7,
+        };
+
+        assert!(owned.holds(7, Some("hunter2")));
+        assert!(!owned.holds(6, Some("hunter2")));
+        assert!(!owned.holds(7, Some("copied later")));
+        assert!(!owned.holds(7, None));
+        assert!(!OwnedCopy {
+            hash: None,
+            generation: 7
+        }
+        .holds(7, None));
+    }
+}
@@ -0,0 +1,235 @@
+//! Paste-limited copies on Wayland through wlr-data-control, which KDE and
+//! wlroots-based compositors offer. GNOME lacks it and goes through XWayland.
+
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, Write},
+    os::fd::{AsRawFd, OwnedFd},
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+use wayland_client::{
+    event_created_child,
+    globals::{registry_queue_init, GlobalListContents},
+    protocol::{wl_registry::WlRegistry, wl_seat::WlSeat},
+    Connection, Dispatch, EventQueue, Proxy, QueueHandle,
+};
+use wayland_protocols_wlr::data_control::v1::client::{
+    zwlr_data_control_device_v1::{self, ZwlrDataControlDeviceV1},
+    zwlr_data_control_manager_v1::ZwlrDataControlManagerV1,
+    zwlr_data_control_offer_v1::ZwlrDataControlOfferV1,
+    zwlr_data_control_source_v1::{self, ZwlrDataControlSourceV1},
+};
+
+use super::{encode_text, wait_readable, PASSWORD_HINT_MIME, PASSWORD_HINT_VALUE, TEXT_MIME_TYPES};
+
+fn wayland_error(error: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("Wayland: {error}"))
+}
+
+struct State {
+    values: VecDeque<String>,
+    done: bool,
+    error: Option<io::Error>,
+}
+
+/// Offers the values as the regular selection and answers paste requests
+/// from a background thread until every value was pasted once, the deadline
+/// passes or another client sets the selection. Fails with `Unsupported`
+/// when the compositor lacks wlr-data-control.
+pub fn serve(
+    values: VecDeque<String>,
+    deadline: Instant,
+) -> io::Result<JoinHandle<io::Result<()>>> {
+    let conn = Connection::connect_to_env().map_err(wayland_error)?;
+    let (globals, mut queue) = registry_queue_init::<State>(&conn).map_err(wayland_error)?;
+    let qh = queue.handle();
+    let seat = globals
+        .bind::<WlSeat, _, _>(&qh, 1..=1, ())
+        .map_err(wayland_error)?;
+    let manager = globals
+        .bind::<ZwlrDataControlManagerV1, _, _>(&qh, 1..=2, ())
+        .map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                "The compositor does not support wlr-data-control",
+            )
+        })?;
+
+    let device = manager.get_data_device(&seat, &qh, ());
+    let source = manager.create_data_source(&qh, ());
+    for mime_type in TEXT_MIME_TYPES.iter().chain([&PASSWORD_HINT_MIME]) {
+        source.offer(mime_type.to_string());
+    }
+    device.set_selection(Some(&source));
+
+    let mut state = State {
+        values,
+        done: false,
+        error: None,
+    };
+    queue.roundtrip(&mut state).map_err(wayland_error)?;
+
+    Ok(thread::spawn(move || {
+        let result = serve_requests(&mut queue, &mut state, deadline);
+        // Destroying a source that still holds the selection clears it.
+        if source.is_alive() {
+            source.destroy();
+        }
+        device.destroy();
+        let result = result.and(conn.flush().map_err(wayland_error));
+        if let Err(error) = &result {
+            eprintln!("Failed to serve the clipboard: {error}");
+        }
+        result
+    }))
+}
+
+fn serve_requests(
+    queue: &mut EventQueue<State>,
+    state: &mut State,
+    deadline: Instant,
+) -> io::Result<()> {
+    loop {
+        queue.dispatch_pending(state).map_err(wayland_error)?;
+        if let Some(error) = state.error.take() {
+            return Err(error);
+        }
+        let now = Instant::now();
+        if state.done || now >= deadline {
+            return Ok(());
+        }
+
+        queue.flush().map_err(wayland_error)?;
+        if let Some(guard) = queue.prepare_read() {
+            if wait_readable(guard.connection_fd(), deadline - now)? {
+                guard.read().map_err(wayland_error)?;
+            }
+        }
+    }
+}
+
+fn write_to(fd: OwnedFd, data: &[u8]) -> io::Result<()> {
+    // The pipe may be non-blocking; a short write would cut the value off.
+    unsafe {
+        let flags = libc::fcntl(fd.as_raw_fd(), libc::F_GETFL);
+        if flags >= 0 {
+            libc::fcntl(fd.as_raw_fd(), libc::F_SETFL, flags & !libc::O_NONBLOCK);
+        }
+    }
+    File::from(fd).write_all(data)
+}
+
+impl Dispatch<ZwlrDataControlSourceV1, ()> for State {
+    fn event(
+        state: &mut Self,
+        source: &ZwlrDataControlSourceV1,
+        event: zwlr_data_control_source_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwlr_data_control_source_v1::Event::Send { mime_type, fd } => {
+                // The password hint does not count as a paste.
+                // A value `STRING` cannot carry is refused and stays queued.
+                let data = state
+                    .values
+                    .front()
+                    .and_then(|value| encode_text(value, mime_type == "STRING"))
+                    .map(|data| data.into_owned());
+                let result = if mime_type == PASSWORD_HINT_MIME {
+                    write_to(fd, PASSWORD_HINT_VALUE)
+                } else if let Some(data) = data {
+                    state.values.pop_front();
+                    write_to(fd, &data)
+                } else {
+                    Ok(())
+                };
+                if let Err(error) = result {
+                    state.error = Some(error);
+                }
+                if state.values.is_empty() {
+                    source.destroy();
+                    state.done = true;
+                }
+            }
+            zwlr_data_control_source_v1::Event::Cancelled => {
+                source.destroy();
+                state.done = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<ZwlrDataControlDeviceV1, ()> for State {
+    fn event(
+        state: &mut Self,
+        _: &ZwlrDataControlDeviceV1,
+        event: zwlr_data_control_device_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            // Offers for our own selection are not needed.
+            zwlr_data_control_device_v1::Event::DataOffer { id } => id.destroy(),
+            zwlr_data_control_device_v1::Event::Finished => state.done = true,
+            _ => {}
+        }
+    }
+
+    event_created_child!(State, ZwlrDataControlDeviceV1, [
+        zwlr_data_control_device_v1::EVT_DATA_OFFER_OPCODE => (ZwlrDataControlOfferV1, ()),
+    ]);
+}
+
+impl Dispatch<ZwlrDataControlOfferV1, ()> for State {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrDataControlOfferV1,
+        _: <ZwlrDataControlOfferV1 as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrDataControlManagerV1, ()> for State {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrDataControlManagerV1,
+        _: <ZwlrDataControlManagerV1 as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<WlSeat, ()> for State {
+    fn event(
+        _: &mut Self,
+        _: &WlSeat,
+        _: <WlSeat as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for State {
+    fn event(
+        _: &mut Self,
+        _: &WlRegistry,
+        _: <WlRegistry as Proxy>::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
@@ -0,0 +1,353 @@
+//! Paste-limited copies on X11. Wayland sessions without wlr-data-control
+//! reach this through XWayland.
+
+use std::{
+    collections::VecDeque,
+    io,
+    os::fd::AsFd,
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+use x11rb::{
+    connection::{Connection, RequestConnection},
+    protocol::{
+        xproto::{
+            Atom, AtomEnum, ConnectionExt as _, CreateWindowAux, EventMask, PropMode,
+            SelectionNotifyEvent, SelectionRequestEvent, Window, WindowClass,
+            SELECTION_NOTIFY_EVENT,
+        },
+        Event,
+    },
+    rust_connection::RustConnection,
+    wrapper::ConnectionExt as _,
+    COPY_DEPTH_FROM_PARENT, CURRENT_TIME, NONE,
+};
+
+use super::{encode_text, wait_readable, PASSWORD_HINT_MIME, PASSWORD_HINT_VALUE};
+
+x11rb::atom_manager! {
+    pub Atoms: AtomsCookie {
+        CLIPBOARD,
+        TARGETS,
+        UTF8_STRING,
+        STRING,
+        TEXT,
+        TEXT_PLAIN: b"text/plain",
+        TEXT_PLAIN_UTF8: b"text/plain;charset=utf-8",
+        PASSWORD_HINT: PASSWORD_HINT_MIME.as_bytes(),
+    }
+}
+
+fn x11_error(error: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("X11: {error}"))
+}
+
+/// Takes the CLIPBOARD selection on `display` and answers paste requests from
+/// a background thread until every value was pasted once, the deadline
+/// passes or another client takes the selection.
+pub fn serve(
+    display: Option<&str>,
+    values: VecDeque<String>,
+    deadline: Instant,
+) -> io::Result<JoinHandle<io::Result<()>>> {
+    let (conn, screen_num) = x11rb::connect(display).map_err(x11_error)?;
+    let screen = &conn.setup().roots[screen_num];
+    let window = conn.generate_id().map_err(x11_error)?;
+    conn.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        window,
+        screen.root,
+        0,
+        0,
+        1,
+        1,
+        0,
+        WindowClass::INPUT_OUTPUT,
+        screen.root_visual,
+        &CreateWindowAux::new(),
+    )
+    .map_err(x11_error)?;
+    let atoms = Atoms::new(&conn)
+        .map_err(x11_error)?
+        .reply()
+        .map_err(x11_error)?;
+
+    conn.set_selection_owner(window, atoms.CLIPBOARD, CURRENT_TIME)
+        .map_err(x11_error)?;
+    let owner = conn
+        .get_selection_owner(atoms.CLIPBOARD)
+        .map_err(x11_error)?
+        .reply()
+        .map_err(x11_error)?
+        .owner;
+    if owner != window {
+        return Err(x11_error("could not take the clipboard selection"));
+    }
+
+    Ok(thread::spawn(move || {
+        let result = serve_requests(&conn, &atoms, values, deadline)
+            .and(release_selection(&conn, &atoms, window));
+        if let Err(error) = &result {
+            eprintln!("Failed to serve the clipboard: {error}");
+        }
+        result
+    }))
+}
+
+fn release_selection(conn: &RustConnection, atoms: &Atoms, window: Window) -> io::Result<()> {
+    // Only give the selection up if nobody else took it meanwhile.
+    let owner = conn
+        .get_selection_owner(atoms.CLIPBOARD)
+        .map_err(x11_error)?
+        .reply()
+        .map_err(x11_error)?
+        .owner;
+    if owner == window {
+        conn.set_selection_owner(NONE, atoms.CLIPBOARD, CURRENT_TIME)
+            .map_err(x11_error)?;
+    }
+    conn.destroy_window(window).map_err(x11_error)?;
+    conn.flush().map_err(x11_error)?;
+    Ok(())
+}
+
+fn serve_requests(
+    conn: &RustConnection,
+    atoms: &Atoms,
+    mut values: VecDeque<String>,
+    deadline: Instant,
+) -> io::Result<()> {
+    loop {
+        conn.flush().map_err(x11_error)?;
+        let Some(event) = conn.poll_for_event().map_err(x11_error)? else {
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(());
+            }
+            wait_readable(conn.stream().as_fd(), deadline - now)?;
+            continue;
+        };
+
+        match event {
+            Event::SelectionRequest(request) if answer(conn, atoms, &request, values.front())? => {
+                values.pop_front();
+                if values.is_empty() {
+                    return Ok(());
+                }
+            }
+            Event::SelectionClear(_) => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+/// Answers one `SelectionRequest`. Returns whether a value was handed out;
+/// `TARGETS` and the password hint do not count as pastes.
+fn answer(
+    conn: &RustConnection,
+    atoms: &Atoms,
+    request: &SelectionRequestEvent,
+    value: Option<&String>,
+) -> io::Result<bool> {
+    // Obsolete clients leave the property unset and expect the target.
+    let property = if request.property == NONE {
+        request.target
+    } else {
+        request.property
+    };
+    let text_type = text_type(atoms, request.target);
+    // Values too large for a single request would need the INCR protocol.
+    let fits = |data: &[u8]| data.len() + 64 <= conn.maximum_request_bytes();
+    let data = value
+        .and_then(|value| encode_text(value, request.target == atoms.STRING))
+        .filter(|data| fits(data));
+
+    let mut pasted = false;
+    let stored = if request.selection != atoms.CLIPBOARD {
+        false
+    } else if request.target == atoms.TARGETS {
+        let targets = [
+            atoms.TARGETS,
+            atoms.UTF8_STRING,
+            atoms.TEXT_PLAIN_UTF8,
+            atoms.TEXT_PLAIN,
+            atoms.STRING,
+            atoms.TEXT,
+            atoms.PASSWORD_HINT,
+        ];
+        conn.change_property32(
+            PropMode::REPLACE,
+            request.requestor,
+            property,
+            AtomEnum::ATOM,
+            &targets,
+        )
+        .map_err(x11_error)?;
+        true
+    } else if request.target == atoms.PASSWORD_HINT {
+        store(
+            conn,
+            request.requestor,
+            property,
+            request.target,
+            PASSWORD_HINT_VALUE,
+        )?;
+        true
+    } else if let (Some(kind), Some(data)) = (text_type, data) {
+        store(conn, request.requestor, property, kind, &data)?;
+        pasted = true;
+        true
+    } else {
+        false
+    };
+
+    let notify = SelectionNotifyEvent {
+        response_type: SELECTION_NOTIFY_EVENT,
+        sequence: 0,
+        time: request.time,
+        requestor: request.requestor,
+        selection: request.selection,
+        target: request.target,
+        property: if stored { property } else { NONE },
+    };
+    conn.send_event(false, request.requestor, EventMask::NO_EVENT, notify)
+        .map_err(x11_error)?;
+    conn.flush().map_err(x11_error)?;
+    Ok(pasted)
+}
+
+/// The property type to answer a text `target` with, `None` for targets that
+/// are not text.
+fn text_type(atoms: &Atoms, target: Atom) -> Option<Atom> {
+    if target == atoms.TEXT {
+        Some(atoms.UTF8_STRING)
+    } else if [
+        atoms.UTF8_STRING,
+        atoms.TEXT_PLAIN_UTF8,
+        atoms.TEXT_PLAIN,
+        atoms.STRING,
+    ]
+    .contains(&target)
+    {
+        Some(target)
+    } else {
+        None
+    }
+}
+
+fn store(
+    conn: &RustConnection,
+    requestor: Window,
+    property: Atom,
+    kind: Atom,
+    data: &[u8],
+) -> io::Result<()> {
+    conn.change_property8(PropMode::REPLACE, requestor, property, kind, data)
+        .map_err(x11_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{serve, Atoms};
+    use std::{
+        collections::VecDeque,
+        time::{Duration, Instant},
+    };
+    use x11rb::{
+        connection::Connection,
+        protocol::{
+            xproto::{Atom, AtomEnum, ConnectionExt as _, CreateWindowAux, Window, WindowClass},
+            Event,
+        },
+        rust_connection::RustConnection,
+        COPY_DEPTH_FROM_PARENT, CURRENT_TIME, NONE,
+    };
+
+    fn paste(
+        conn: &RustConnection,
+        window: Window,
+        atoms: &Atoms,
+        target: Atom,
+    ) -> Option<Vec<u8>> {
+        conn.convert_selection(
+            window,
+            atoms.CLIPBOARD,
+            target,
+            atoms.CLIPBOARD,
+            CURRENT_TIME,
+        )
+        .unwrap();
+        conn.flush().unwrap();
+        let notify = loop {
+            if let Event::SelectionNotify(notify) = conn.wait_for_event().unwrap() {
+                break notify;
+            }
+        };
+        if notify.property == NONE {
+            return None;
+        }
+        let reply = conn
+            .get_property(
+                true,
+                window,
+                notify.property,
+                AtomEnum::ANY,
+                0,
+                u32::MAX / 4,
+            )
+            .unwrap()
+            .reply()
+            .unwrap();
+        Some(reply.value)
+    }
+
+    #[test]
+    #[ignore = "needs an X server, e.g. `xvfb-run cargo test -- --ignored`"]
+    fn serves_each_value_once_then_releases_the_clipboard() {
+        let values = VecDeque::from(["alice".to_string(), "hunter2".to_string()]);
+        let server = serve(None, values, Instant::now() + Duration::from_secs(10)).unwrap();
+
+        let (conn, screen_num) = x11rb::connect(None).unwrap();
+        let screen = &conn.setup().roots[screen_num];
+        let window = conn.generate_id().unwrap();
+        conn.create_window(
+            COPY_DEPTH_FROM_PARENT,
+            window,
+            screen.root,
+            0,
+            0,
+            1,
+            1,
+            0,
+            WindowClass::INPUT_OUTPUT,
+            screen.root_visual,
+            &CreateWindowAux::new(),
+        )
+        .unwrap();
+        let atoms = Atoms::new(&conn).unwrap().reply().unwrap();
+
+        assert!(paste(&conn, window, &atoms, atoms.TARGETS).is_some());
+        assert_eq!(
+            paste(&conn, window, &atoms, atoms.PASSWORD_HINT).as_deref(),
+            Some(&b"secret"[..])
+        );
+        assert_eq!(
+            paste(&conn, window, &atoms, atoms.UTF8_STRING).as_deref(),
+            Some(&b"alice"[..])
+        );
+        assert_eq!(
+            paste(&conn, window, &atoms, atoms.UTF8_STRING).as_deref(),
+            Some(&b"hunter2"[..])
+        );
+
+        server.join().unwrap().unwrap();
+        let owner = conn
+            .get_selection_owner(atoms.CLIPBOARD)
+            .unwrap()
+            .reply()
+            .unwrap()
+            .owner;
+        assert_eq!(owner, NONE);
+    }
+}
@@ -6,6 +6,10 @@ use tauri;
 use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
 use tauri::{AppHandle, Manager, Size, State};
 
+use crate::clipboard;
+
+const DEFAULT_PASTE_TIMEOUT_MS: u64 = 60_000;
+
 #[derive(serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CloseBehavior {
@@ -89,26 +93,6 @@ pub struct TrayMenuLabels {
     quit: String,
 }
 
-#[cfg(target_os = "windows")]
-use windows::Win32::Foundation::HANDLE;
-#[cfg(target_os = "windows")]
-use windows::Win32::System::DataExchange::{
-    CloseClipboard,
-    EmptyClipboard,
-    OpenClipboard,
-    SetClipboardData,
-};
-#[cfg(target_os = "windows")]
-use windows::Win32::System::Memory::{
-    GlobalAlloc,
-    GlobalLock,
-    GlobalUnlock,
-    GMEM_MOVEABLE,
-};
-
-#[cfg(target_os = "windows")]
-const CF_UNICODETEXT_FORMAT: u32 = 13;
-
 fn schedule_exit_watchdog() {
     std::thread::spawn(|| {
         std::thread::sleep(Duration::from_secs(5));
@@ -321,42 +305,39 @@ pub async fn reset_window_size(app: AppHandle) -> Result<(), String> {
 
 #[tauri::command]
 pub async fn clear_clipboard_text() -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    {
-        let replacement = "Clipboard cleared";
-        let utf16: Vec<u16> = replacement.encode_utf16().chain(std::iter::once(0)).collect();
-        let size_in_bytes = utf16.len() * std::mem::size_of::<u16>();
-
-        unsafe {
-            OpenClipboard(None).map_err(|e| e.to_string())?;
-
-            let empty_result = EmptyClipboard().map_err(|e| e.to_string());
-            if empty_result.is_ok() {
-                let memory = GlobalAlloc(GMEM_MOVEABLE, size_in_bytes).map_err(|e| e.to_string())?;
-                let locked_ptr = GlobalLock(memory) as *mut u16;
-
-                if locked_ptr.is_null() {
-                    let _ = CloseClipboard();
-                    return Err("Failed to lock clipboard memory".to_string());
-                }
-
-                std::ptr::copy_nonoverlapping(utf16.as_ptr(), locked_ptr, utf16.len());
-                let _ = GlobalUnlock(memory);
-
-                SetClipboardData(CF_UNICODETEXT_FORMAT, HANDLE(memory.0))
-                    .map_err(|e| e.to_string())?;
-            }
+    clipboard::clear().map_err(|e| e.to_string())
+}
 
-            let close_result = CloseClipboard().map_err(|e| e.to_string());
-            empty_result?;
-            close_result?;
-        }
+/// Copies a secret from the Rust side, which clears it after `clear_after_ms`
+/// if the clipboard still holds it by then.
+#[tauri::command]
+pub async fn copy_secret_to_clipboard(
+    value: String,
+    clear_after_ms: Option<u64>,
+) -> Result<(), String> {
+    let clear_after = clear_after_ms
+        .filter(|ms| *ms > 0)
+        .map(Duration::from_millis);
+    clipboard::copy_secret(&value, clear_after).map_err(|e| e.to_string())
+}
 
-        return Ok(());
-    }
+/// Whether `copy_secret_for_pastes` works on this platform.
+#[tauri::command]
+pub fn clipboard_supports_paste_limit() -> bool {
+    cfg!(target_os = "linux")
+}
 
-    #[cfg(not(target_os = "windows"))]
-    {
-        Err("Clipboard clear command not implemented for this platform".to_string())
-    }
+/// Serves `values` to the next pastes, one value each, then leaves the
+/// clipboard empty. Values not pasted within `timeout_ms` are dropped.
+#[tauri::command]
+pub async fn copy_secret_for_pastes(
+    values: Vec<String>,
+    timeout_ms: Option<u64>,
+) -> Result<(), String> {
+    let timeout = Duration::from_millis(
+        timeout_ms
+            .filter(|ms| *ms > 0)
+            .unwrap_or(DEFAULT_PASTE_TIMEOUT_MS),
+    );
+    clipboard::copy_for_pastes(values, timeout).map_err(|e| e.to_string())
 }
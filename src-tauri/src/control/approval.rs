@@ -0,0 +1,421 @@
+use serde::Serialize;
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, OnceLock},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+pub const APPROVAL_TIMEOUT_MS: u64 = 60_000;
+
+/// A local process talking to the control channel, identified by its peer
+/// credentials. `start_time` tells a process apart from a later one that
+/// reuses the same pid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessIdentity {
+    pub pid: u32,
+    pub uid: u32,
+    pub start_time: u64,
+    pub exe: Option<String>,
+    pub command_line: Option<String>,
+}
+
+/// What a grant covers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ApprovalScope {
+    /// The one running process.
+    #[default]
+    Process,
+    /// Every process of the same executable, for clients that start anew
+    /// for each lookup. Interpreters and generic clients such as
+    /// `secret-tool` can run anything, so their grants also require the same
+    /// command line. Processes whose executable or command line cannot be
+    /// read are approved one by one.
+    Executable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ApprovalKey {
+    Process(u32, u64),
+    Executable(String),
+    Command(String, String),
+}
+
+/// Executables that run whatever their arguments say, by file name without a
+/// version suffix (`python3.12` is `python`).
+const GENERIC_CLIENTS: &[&str] = &[
+    "bash",
+    "bun",
+    "busybox",
+    "dash",
+    "deno",
+    "env",
+    "fish",
+    "gjs",
+    "ksh",
+    "lua",
+    "luajit",
+    "node",
+    "perl",
+    "php",
+    "python",
+    "ruby",
+    "secret-tool",
+    "sh",
+    "zsh",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingProcessApproval {
+    pub id: String,
+    pub pid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exe: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command_line: Option<String>,
+    pub requested_at_ms: u64,
+    #[serde(skip)]
+    key: ApprovalKey,
+    /// Requests blocked on this record; it is dropped when the last one
+    /// times out.
+    #[serde(skip)]
+    waiters: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessGrant {
+    pub pid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exe: Option<String>,
+    pub granted_at_ms: u64,
+    #[serde(skip)]
+    key: ApprovalKey,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalDecision {
+    Approved,
+    Rejected,
+    TimedOut,
+}
+
+/// Grants of the current unlock session. Nothing is persisted: every grant is
+/// dropped by `revoke_all` when the vault locks or the app exits.
+#[derive(Default)]
+pub struct ApprovalBook {
+    scope: ApprovalScope,
+    state: Mutex<ApprovalState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct ApprovalState {
+    pending: Vec<PendingProcessApproval>,
+    grants: Vec<ProcessGrant>,
+    rejected: Vec<ApprovalKey>,
+    next_id: u64,
+}
+
+impl ApprovalBook {
+    pub fn new(scope: ApprovalScope) -> Self {
+        Self {
+            scope,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_granted(&self, identity: &ProcessIdentity) -> bool {
+        let key = self.key(identity);
+        self.lock().grants.iter().any(|grant| grant.key == key)
+    }
+
+    /// Queues `identity` for approval in the app and blocks until the user
+    /// decides, the grants are revoked or `timeout` passes.
+    pub fn request_approval(
+        &self,
+        identity: &ProcessIdentity,
+        timeout: Duration,
+    ) -> ApprovalDecision {
+        let key = self.key(identity);
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+
+        // Waiters follow their record by id, so a record queued again after
+        // `revoke_all` is not mistaken for the one that was dropped.
+        let id = match state.pending.iter_mut().find(|pending| pending.key == key) {
+            Some(pending) => {
+                pending.waiters += 1;
+                pending.id.clone()
+            }
+            None => {
+                state.next_id += 1;
+                let id = format!("process-{}-{}", identity.pid, state.next_id);
+                state.pending.push(PendingProcessApproval {
+                    id: id.clone(),
+                    pid: identity.pid,
+                    exe: identity.exe.clone(),
+                    command_line: identity.command_line.clone(),
+                    requested_at_ms: now_ms(),
+                    key: key.clone(),
+                    waiters: 1,
+                });
+                id
+            }
+        };
+
+        loop {
+            if state.grants.iter().any(|grant| grant.key == key) {
+                return ApprovalDecision::Approved;
+            }
+            if state.rejected.contains(&key) {
+                return ApprovalDecision::Rejected;
+            }
+            let Some(index) = state.pending.iter().position(|pending| pending.id == id) else {
+                // Dropped by `revoke_all` without a decision.
+                return ApprovalDecision::Rejected;
+            };
+
+            let now = Instant::now();
+            if now >= deadline {
+                state.pending[index].waiters -= 1;
+                if state.pending[index].waiters == 0 {
+                    state.pending.remove(index);
+                }
+                return ApprovalDecision::TimedOut;
+            }
+            state = self
+                .changed
+                .wait_timeout(state, deadline - now)
+                .map(|(state, _)| state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner().0);
+        }
+    }
+
+    pub fn approve(&self, id: &str) -> bool {
+        let mut state = self.lock();
+        let Some(index) = state.pending.iter().position(|pending| pending.id == id) else {
+            return false;
+        };
+        let pending = state.pending.remove(index);
+        state.grants.push(ProcessGrant {
+            pid: pending.pid,
+            exe: pending.exe,
+            granted_at_ms: now_ms(),
+            key: pending.key,
+        });
+        self.changed.notify_all();
+        true
+    }
+
+    /// Rejects a pending process for the rest of the unlock session.
+    pub fn reject(&self, id: &str) -> bool {
+        let mut state = self.lock();
+        let Some(index) = state.pending.iter().position(|pending| pending.id == id) else {
+            return false;
+        };
+        let pending = state.pending.remove(index);
+        state.rejected.push(pending.key);
+        self.changed.notify_all();
+        true
+    }
+
+    pub fn is_rejected(&self, identity: &ProcessIdentity) -> bool {
+        self.lock().rejected.contains(&self.key(identity))
+    }
+
+    pub fn list_pending(&self) -> Vec<PendingProcessApproval> {
+        self.lock().pending.clone()
+    }
+
+    pub fn list_grants(&self) -> Vec<ProcessGrant> {
+        self.lock().grants.clone()
+    }
+
+    pub fn revoke_all(&self) {
+        let mut state = self.lock();
+        state.pending.clear();
+        state.grants.clear();
+        state.rejected.clear();
+        self.changed.notify_all();
+    }
+
+    fn key(&self, identity: &ProcessIdentity) -> ApprovalKey {
+        match (self.scope, &identity.exe, &identity.command_line) {
+            (ApprovalScope::Executable, Some(exe), _) if !is_generic_client(exe) => {
+                ApprovalKey::Executable(exe.clone())
+            }
+            (ApprovalScope::Executable, Some(exe), Some(command_line)) => {
+                ApprovalKey::Command(exe.clone(), command_line.clone())
+            }
+            _ => ApprovalKey::Process(identity.pid, identity.start_time),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ApprovalState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The approval book of this app instance.
+pub fn approvals() -> &'static ApprovalBook {
+    static APPROVALS: OnceLock<ApprovalBook> = OnceLock::new();
+    APPROVALS.get_or_init(ApprovalBook::default)
+}
+
+fn is_generic_client(exe: &str) -> bool {
+    let name = exe.rsplit('/').next().unwrap_or(exe);
+    let name = name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    GENERIC_CLIENTS.contains(&name)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApprovalBook, ApprovalDecision, ApprovalScope, ProcessIdentity};
+    use std::{sync::Arc, thread, time::Duration};
+
+    fn process(pid: u32, start_time: u64) -> ProcessIdentity {
+        ProcessIdentity {
+            pid,
+            uid: 1000,
+            start_time,
+            exe: Some("/usr/bin/clavispass".into()),
+            command_line: Some("clavispass list".into()),
+        }
+    }
+
+    #[test]
+    fn approval_grants_only_the_same_process_until_revoked() {
+        let book = Arc::new(ApprovalBook::default());
+        let waiter = {
+            let book = Arc::clone(&book);
+            thread::spawn(move || book.request_approval(&process(42, 7), Duration::from_secs(5)))
+        };
+
+        while book.list_pending().is_empty() {
+            thread::sleep(Duration::from_millis(5));
+        }
+        let id = book.list_pending()[0].id.clone();
+        assert!(book.approve(&id));
+        assert_eq!(waiter.join().unwrap(), ApprovalDecision::Approved);
+
+        assert!(book.is_granted(&process(42, 7)));
+        // Same pid, but a different process that reused it.
+        assert!(!book.is_granted(&process(42, 8)));
+
+        book.revoke_all();
+        assert!(!book.is_granted(&process(42, 7)));
+    }
+
+    #[test]
+    fn rejected_and_unanswered_requests_are_not_granted() {
+        let book = ApprovalBook::default();
+        assert_eq!(
+            book.request_approval(&process(1, 1), Duration::from_millis(10)),
+            ApprovalDecision::TimedOut
+        );
+        assert!(book.list_pending().is_empty());
+
+        let book = Arc::new(book);
+        let waiter = {
+            let book = Arc::clone(&book);
+            thread::spawn(move || book.request_approval(&process(2, 1), Duration::from_secs(5)))
+        };
+        while book.list_pending().is_empty() {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert!(book.reject(&book.list_pending()[0].id));
+        assert_eq!(waiter.join().unwrap(), ApprovalDecision::Rejected);
+        assert!(book.is_rejected(&process(2, 1)));
+    }
+
+    #[test]
+    fn a_timed_out_request_keeps_the_record_for_other_waiters() {
+        let book = Arc::new(ApprovalBook::new(ApprovalScope::Executable));
+        let waiter = {
+            let book = Arc::clone(&book);
+            thread::spawn(move || book.request_approval(&process(42, 7), Duration::from_secs(5)))
+        };
+        while book.list_pending().is_empty() {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(
+            book.request_approval(&process(43, 9), Duration::from_millis(10)),
+            ApprovalDecision::TimedOut
+        );
+
+        let pending = book.list_pending();
+        assert_eq!(pending.len(), 1);
+        assert!(book.approve(&pending[0].id));
+        assert_eq!(waiter.join().unwrap(), ApprovalDecision::Approved);
+    }
+
+    #[test]
+    fn executable_scope_grants_later_processes_of_the_same_binary() {
+        let book = Arc::new(ApprovalBook::new(ApprovalScope::Executable));
+        let waiter = {
+            let book = Arc::clone(&book);
+            thread::spawn(move || book.request_approval(&process(42, 7), Duration::from_secs(5)))
+        };
+        while book.list_pending().is_empty() {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert!(book.approve(&book.list_pending()[0].id));
+        assert_eq!(waiter.join().unwrap(), ApprovalDecision::Approved);
+
+        assert!(book.is_granted(&process(43, 9)));
+        let other = ProcessIdentity {
+            exe: Some("/usr/bin/other".into()),
+            ..process(44, 9)
+        };
+        assert!(!book.is_granted(&other));
+        let unknown = ProcessIdentity {
+            exe: None,
+            ..process(42, 7)
+        };
+        assert!(!book.is_granted(&unknown));
+    }
+
+    #[test]
+    fn executable_scope_ties_interpreters_to_their_command_line() {
+        let book = Arc::new(ApprovalBook::new(ApprovalScope::Executable));
+        let script = |pid, command_line: &str| ProcessIdentity {
+            exe: Some("/usr/bin/python3.12".into()),
+            command_line: Some(command_line.into()),
+            ..process(pid, 1)
+        };
+        let waiter = {
+            let book = Arc::clone(&book);
+            thread::spawn(move || {
+                book.request_approval(&script(42, "python3 sync.py"), Duration::from_secs(5))
+            })
+        };
+        while book.list_pending().is_empty() {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert!(book.approve(&book.list_pending()[0].id));
+        assert_eq!(waiter.join().unwrap(), ApprovalDecision::Approved);
+
+        assert!(book.is_granted(&script(43, "python3 sync.py")));
+        assert!(!book.is_granted(&script(44, "python3 dump.py")));
+        let secret_tool = |command_line: &str| ProcessIdentity {
+            exe: Some("/usr/bin/secret-tool".into()),
+            command_line: Some(command_line.into()),
+            ..process(45, 1)
+        };
+        assert!(!book.is_granted(&secret_tool("python3 sync.py")));
+        let without_command_line = ProcessIdentity {
+            command_line: None,
+            ..script(42, "")
+        };
+        assert!(!book.is_granted(&without_command_line));
+    }
+}
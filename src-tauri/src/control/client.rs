@@ -0,0 +1,112 @@
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    time::Duration,
+};
+
+use super::{
+    approval::APPROVAL_TIMEOUT_MS,
+    protocol::{ControlRequest, ControlResponse},
+};
+use crate::bridge::path::control_socket_path;
+
+/// How long an answer may take before the user is told to look at the app.
+const APPROVAL_HINT_AFTER: Duration = Duration::from_millis(750);
+
+/// A connection to the control socket of the running desktop app.
+pub struct ControlClient {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+    next_id: u64,
+    hinted: bool,
+}
+
+impl ControlClient {
+    pub fn connect() -> io::Result<Self> {
+        let stream = UnixStream::connect(control_socket_path()?)?;
+        Ok(Self {
+            writer: stream.try_clone()?,
+            reader: BufReader::new(stream),
+            next_id: 0,
+            hinted: false,
+        })
+    }
+
+    /// Sends one request and waits for its answer. The first request of a
+    /// process is held by the app until the user approves it there.
+    pub fn request<P: Serialize, T: DeserializeOwned>(
+        &mut self,
+        command: &str,
+        payload: P,
+    ) -> io::Result<T> {
+        self.next_id += 1;
+        let request = ControlRequest {
+            id: format!("cli-{}", self.next_id),
+            command: command.to_string(),
+            payload: serde_json::to_value(payload)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?,
+        };
+        let mut line = serde_json::to_vec(&request)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+
+        let response = self.read_response()?;
+        if response.ok {
+            return serde_json::from_value(response.result.unwrap_or(Value::Null))
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err));
+        }
+
+        let error = response.error.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "ClavisPass sent an empty error")
+        })?;
+        let kind = match error.code.as_str() {
+            "ENTRY_NOT_FOUND" => io::ErrorKind::NotFound,
+            "AMBIGUOUS_ENTRY" | "INVALID_PAYLOAD" => io::ErrorKind::InvalidInput,
+            "APP_LOCKED" | "PROCESS_REJECTED" | "PEER_REJECTED" => io::ErrorKind::PermissionDenied,
+            "APPROVAL_TIMEOUT" => io::ErrorKind::TimedOut,
+            _ => io::ErrorKind::Other,
+        };
+        Err(io::Error::new(kind, error.message))
+    }
+
+    fn read_response(&mut self) -> io::Result<ControlResponse> {
+        let socket = self.reader.get_ref();
+        socket.set_read_timeout(Some(if self.hinted {
+            Duration::from_millis(APPROVAL_TIMEOUT_MS) + APPROVAL_HINT_AFTER
+        } else {
+            APPROVAL_HINT_AFTER
+        }))?;
+
+        let mut line = String::new();
+        loop {
+            match self.reader.read_line(&mut line) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "ClavisPass closed the connection",
+                    ))
+                }
+                Ok(_) => break,
+                Err(error)
+                    if !self.hinted
+                        && matches!(
+                            error.kind(),
+                            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                        ) =>
+                {
+                    self.hinted = true;
+                    eprintln!("Waiting for approval in ClavisPass…");
+                    self.reader.get_ref().set_read_timeout(Some(
+                        Duration::from_millis(APPROVAL_TIMEOUT_MS) + APPROVAL_HINT_AFTER,
+                    ))?;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        serde_json::from_str(&line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
@@ -0,0 +1,11 @@
+//! Local control channel through which the `clavispass` CLI reads from the
+//! running, unlocked desktop app instead of asking for the master password.
+
+pub mod approval;
+#[cfg(unix)]
+pub mod client;
+#[cfg(unix)]
+pub(crate) mod peer;
+pub mod protocol;
+#[cfg(unix)]
+pub mod server;
@@ -0,0 +1,199 @@
+use std::{io, os::unix::io::AsRawFd, os::unix::net::UnixStream};
+
+use super::approval::ProcessIdentity;
+
+/// Reads the kernel-reported credentials of the process on the other end of
+/// `stream`. Nothing the client sends is trusted for this.
+pub fn peer_identity(stream: &UnixStream) -> io::Result<ProcessIdentity> {
+    let (pid, uid) = peer_credentials(stream)?;
+    process_identity(pid, uid)
+}
+
+/// Describes the process `pid`, whose credentials the caller already learned
+/// from a trusted source such as the kernel or the bus daemon.
+pub fn process_identity(pid: u32, uid: u32) -> io::Result<ProcessIdentity> {
+    Ok(ProcessIdentity {
+        pid,
+        uid,
+        start_time: process_start_time(pid)?,
+        exe: process_exe(pid),
+        command_line: process_command_line(pid),
+    })
+}
+
+pub fn current_uid() -> u32 {
+    // SAFETY: geteuid has no preconditions and cannot fail.
+    unsafe { libc::geteuid() }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn peer_credentials(stream: &UnixStream) -> io::Result<(u32, u32)> {
+    let mut credentials = libc::ucred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut length = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    // SAFETY: `credentials` and `length` are valid for writes of the size passed.
+    let result = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            &mut credentials as *mut libc::ucred as *mut libc::c_void,
+            &mut length,
+        )
+    };
+    if result != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if credentials.pid <= 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "Peer process is not visible",
+        ));
+    }
+    Ok((credentials.pid as u32, credentials.uid))
+}
+
+#[cfg(target_os = "macos")]
+fn peer_credentials(stream: &UnixStream) -> io::Result<(u32, u32)> {
+    let fd = stream.as_raw_fd();
+    let mut uid: libc::uid_t = 0;
+    let mut gid: libc::gid_t = 0;
+    // SAFETY: `uid` and `gid` are valid for writes.
+    if unsafe { libc::getpeereid(fd, &mut uid, &mut gid) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut pid: libc::pid_t = 0;
+    let mut length = std::mem::size_of::<libc::pid_t>() as libc::socklen_t;
+    // SAFETY: `pid` and `length` are valid for writes of the size passed.
+    let result = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_LOCAL,
+            libc::LOCAL_PEERPID,
+            &mut pid as *mut libc::pid_t as *mut libc::c_void,
+            &mut length,
+        )
+    };
+    if result != 0 || pid <= 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok((pid as u32, uid))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn peer_credentials(_stream: &UnixStream) -> io::Result<(u32, u32)> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Peer credentials are not supported on this platform",
+    ))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn process_start_time(pid: u32) -> io::Result<u64> {
+    // Field 22 of /proc/<pid>/stat, counted after the parenthesised command
+    // name, which may itself contain spaces.
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat"))?;
+    stat.rsplit_once(')')
+        .and_then(|(_, rest)| rest.split_whitespace().nth(19))
+        .and_then(|value| value.parse::<u64>().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Unreadable process start time"))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn process_exe(pid: u32) -> Option<String> {
+    std::fs::read_link(format!("/proc/{pid}/exe"))
+        .ok()
+        .map(|path| path.to_string_lossy().into_owned())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn process_command_line(pid: u32) -> Option<String> {
+    let raw = std::fs::read(format!("/proc/{pid}/cmdline")).ok()?;
+    let command_line = raw
+        .split(|byte| *byte == 0)
+        .filter(|part| !part.is_empty())
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(command_line).filter(|value| !value.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+fn process_start_time(pid: u32) -> io::Result<u64> {
+    let mut info = std::mem::MaybeUninit::<libc::proc_bsdinfo>::zeroed();
+    let size = std::mem::size_of::<libc::proc_bsdinfo>() as libc::c_int;
+    // SAFETY: `info` is valid for writes of `size` bytes.
+    let written = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDTBSDINFO,
+            0,
+            info.as_mut_ptr() as *mut libc::c_void,
+            size,
+        )
+    };
+    if written != size {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: proc_pidinfo filled the whole struct.
+    let info = unsafe { info.assume_init() };
+    Ok(info.pbi_start_tvsec * 1_000_000 + info.pbi_start_tvusec)
+}
+
+#[cfg(target_os = "macos")]
+fn process_exe(pid: u32) -> Option<String> {
+    let mut buffer = vec![0u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+    // SAFETY: `buffer` is valid for writes of its length.
+    let length = unsafe {
+        libc::proc_pidpath(
+            pid as libc::c_int,
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            buffer.len() as u32,
+        )
+    };
+    (length > 0).then(|| String::from_utf8_lossy(&buffer[..length as usize]).into_owned())
+}
+
+#[cfg(target_os = "macos")]
+fn process_command_line(_pid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn process_start_time(_pid: u32) -> io::Result<u64> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Process start time is not supported on this platform",
+    ))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn process_exe(_pid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn process_command_line(_pid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::{current_uid, peer_identity};
+    use std::os::unix::net::UnixStream;
+
+    #[test]
+    fn reports_the_connecting_process() {
+        let (left, _right) = UnixStream::pair().unwrap();
+        let identity = peer_identity(&left).unwrap();
+
+        assert_eq!(identity.pid, std::process::id());
+        assert_eq!(identity.uid, current_uid());
+        assert!(identity.start_time > 0);
+        assert!(identity.exe.is_some());
+    }
+}
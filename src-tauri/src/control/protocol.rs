@@ -0,0 +1,60 @@
+//! Line-delimited JSON spoken over the control socket. Requests carry one of
+//! the commands below; answers reuse [`BridgeResponse`] so error codes match
+//! the browser bridge.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub use crate::bridge::protocol::BridgeResponse as ControlResponse;
+
+pub const COMMAND_LIST: &str = "list";
+pub const COMMAND_SEARCH: &str = "search";
+pub const COMMAND_GET: &str = "get";
+pub const COMMAND_TOTP: &str = "totp";
+pub const COMMAND_CREDENTIAL_GET: &str = "credentialGet";
+pub const COMMAND_CREDENTIAL_STORE: &str = "credentialStore";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlRequest {
+    pub id: String,
+    pub command: String,
+    #[serde(default)]
+    pub payload: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPayload {
+    pub query: String,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPayload {
+    pub entry: String,
+    pub field: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpPayload {
+    pub entry: String,
+}
+
+/// The attributes of git's credential helper protocol.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialPayload {
+    #[serde(default)]
+    pub protocol: Option<String>,
+    pub host: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+}
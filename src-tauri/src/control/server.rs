@@ -0,0 +1,234 @@
+use serde_json::Value;
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    thread,
+    time::Duration,
+};
+
+use super::{
+    approval::{approvals, ApprovalDecision, ProcessIdentity, APPROVAL_TIMEOUT_MS},
+    peer::{current_uid, peer_identity},
+    protocol::{
+        ControlRequest, ControlResponse, CredentialPayload, GetPayload, SearchPayload, TotpPayload,
+        COMMAND_CREDENTIAL_GET, COMMAND_CREDENTIAL_STORE, COMMAND_GET, COMMAND_LIST,
+        COMMAND_SEARCH, COMMAND_TOTP,
+    },
+};
+use crate::{
+    bridge::{
+        path::control_socket_path,
+        session::{self, BridgeSessionSnapshot},
+        vault::{list_entries, search_entries},
+    },
+    cli::{
+        git_credential::{lookup_credential, store_credential},
+        lookup_field, lookup_totp,
+    },
+};
+
+/// Binds the control socket and serves clients on a background thread for as
+/// long as the app runs. Answers depend on the published unlocked session, so
+/// a locked app refuses every request.
+pub fn start() -> io::Result<()> {
+    let path = control_socket_path()?;
+    match UnixStream::connect(&path) {
+        Ok(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                "Another ClavisPass instance serves the control socket",
+            ))
+        }
+        Err(_) if path.exists() => fs::remove_file(&path)?,
+        Err(_) => {}
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    thread::spawn(move || {
+                        if let Err(error) = serve_client(stream) {
+                            eprintln!("Control client failed: {error}");
+                        }
+                    });
+                }
+                Err(error) => eprintln!("Failed to accept control client: {error}"),
+            }
+        }
+    });
+    Ok(())
+}
+
+fn serve_client(stream: UnixStream) -> io::Result<()> {
+    let identity = peer_identity(&stream)?;
+    let mut writer = stream.try_clone()?;
+
+    if identity.uid != current_uid() {
+        return write_response(
+            &mut writer,
+            &ControlResponse::error(
+                "unknown".to_string(),
+                "PEER_REJECTED",
+                "Only processes of the desktop user may use ClavisPass.",
+            ),
+        );
+    }
+
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<ControlRequest>(&line) {
+            Ok(request) => handle_request(&identity, request),
+            Err(error) => ControlResponse::error(
+                "unknown".to_string(),
+                "BAD_REQUEST",
+                format!("Invalid request payload: {error}"),
+            ),
+        };
+        write_response(&mut writer, &response)?;
+    }
+    Ok(())
+}
+
+fn handle_request(identity: &ProcessIdentity, request: ControlRequest) -> ControlResponse {
+    let id = request.id;
+    if let Err(response) = load_unlocked_session(&id) {
+        return response;
+    }
+    if let Err(response) = ensure_approved(&id, identity) {
+        return response;
+    }
+    // Approval may have taken a while; the vault could be locked by now.
+    let snapshot = match load_unlocked_session(&id) {
+        Ok(snapshot) => snapshot,
+        Err(response) => return response,
+    };
+    let vault = &snapshot.vault;
+
+    match request.command.as_str() {
+        COMMAND_LIST => ControlResponse::success(id, list_entries(vault)),
+        COMMAND_SEARCH => match parse_payload::<SearchPayload>(&id, request.payload) {
+            Ok(payload) => {
+                ControlResponse::success(id, search_entries(vault, &payload.query, payload.limit))
+            }
+            Err(response) => response,
+        },
+        COMMAND_GET => match parse_payload::<GetPayload>(&id, request.payload) {
+            Ok(payload) => lookup_response(id, lookup_field(vault, &payload.entry, &payload.field)),
+            Err(response) => response,
+        },
+        COMMAND_TOTP => match parse_payload::<TotpPayload>(&id, request.payload) {
+            Ok(payload) => lookup_response(id, lookup_totp(vault, &payload.entry)),
+            Err(response) => response,
+        },
+        COMMAND_CREDENTIAL_GET => match parse_payload::<CredentialPayload>(&id, request.payload) {
+            Ok(payload) => lookup_response(id, lookup_credential(vault, &payload)),
+            Err(response) => response,
+        },
+        COMMAND_CREDENTIAL_STORE => {
+            match parse_payload::<CredentialPayload>(&id, request.payload) {
+                Ok(payload) => lookup_response(id, store_credential(vault, &payload)),
+                Err(response) => response,
+            }
+        }
+        other => ControlResponse::error(
+            id,
+            "UNKNOWN_COMMAND",
+            format!("Unsupported command: {other}"),
+        ),
+    }
+}
+
+fn load_unlocked_session(id: &str) -> Result<BridgeSessionSnapshot, ControlResponse> {
+    match session::load_session() {
+        Ok(Some(snapshot)) => Ok(snapshot),
+        Ok(None) => {
+            // Grants only live as long as the unlock session.
+            approvals().revoke_all();
+            Err(ControlResponse::error(
+                id.to_string(),
+                "APP_LOCKED",
+                "ClavisPass is locked. Unlock the desktop app and try again.",
+            ))
+        }
+        Err(error) => Err(ControlResponse::error(
+            id.to_string(),
+            "SESSION_STORE_ERROR",
+            format!("Failed to load bridge session: {error}"),
+        )),
+    }
+}
+
+fn ensure_approved(id: &str, identity: &ProcessIdentity) -> Result<(), ControlResponse> {
+    let book = approvals();
+    if book.is_granted(identity) {
+        return Ok(());
+    }
+    if book.is_rejected(identity) {
+        return Err(rejected(id));
+    }
+
+    match book.request_approval(identity, Duration::from_millis(APPROVAL_TIMEOUT_MS)) {
+        ApprovalDecision::Approved => Ok(()),
+        ApprovalDecision::Rejected => Err(rejected(id)),
+        ApprovalDecision::TimedOut => Err(ControlResponse::error(
+            id.to_string(),
+            "APPROVAL_TIMEOUT",
+            "Access was not approved in the desktop app in time.",
+        )),
+    }
+}
+
+fn rejected(id: &str) -> ControlResponse {
+    ControlResponse::error(
+        id.to_string(),
+        "PROCESS_REJECTED",
+        "Access was denied in the desktop app.",
+    )
+}
+
+fn parse_payload<T: serde::de::DeserializeOwned>(
+    id: &str,
+    payload: Value,
+) -> Result<T, ControlResponse> {
+    serde_json::from_value(payload).map_err(|error| {
+        ControlResponse::error(
+            id.to_string(),
+            "INVALID_PAYLOAD",
+            format!("Request payload is invalid: {error}"),
+        )
+    })
+}
+
+fn lookup_response<T: serde::Serialize>(id: String, result: io::Result<T>) -> ControlResponse {
+    match result {
+        Ok(value) => ControlResponse::success(id, value),
+        Err(error) => {
+            let code = match error.kind() {
+                io::ErrorKind::NotFound => "ENTRY_NOT_FOUND",
+                io::ErrorKind::InvalidInput => "AMBIGUOUS_ENTRY",
+                _ => "LOOKUP_FAILED",
+            };
+            ControlResponse::error(id, code, error.to_string())
+        }
+    }
+}
+
+fn write_response(writer: &mut UnixStream, response: &ControlResponse) -> io::Result<()> {
+    let mut line =
+        serde_json::to_vec(response).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    line.push(b'\n');
+    writer.write_all(&line)?;
+    writer.flush()
+}
@@ -0,0 +1,26 @@
+use crate::control::approval::{approvals, PendingProcessApproval, ProcessGrant};
+
+#[tauri::command]
+pub fn control_list_pending_approvals() -> Vec<PendingProcessApproval> {
+    approvals().list_pending()
+}
+
+#[tauri::command]
+pub fn control_list_grants() -> Vec<ProcessGrant> {
+    approvals().list_grants()
+}
+
+#[tauri::command]
+pub fn control_approve_process(approval_id: String) -> bool {
+    approvals().approve(&approval_id)
+}
+
+#[tauri::command]
+pub fn control_reject_process(approval_id: String) -> bool {
+    approvals().reject(&approval_id)
+}
+
+#[tauri::command]
+pub fn control_revoke_grants() {
+    approvals().revoke_all();
+}
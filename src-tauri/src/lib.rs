@@ -1,8 +1,20 @@
 #![cfg_attr(target_os = "windows", windows_subsystem = "windows")]
 
+pub mod api;
+mod api_commands;
 pub mod bridge;
 mod bridge_commands;
+pub mod cli;
+mod clipboard;
+pub mod control;
+mod control_commands;
 mod screen_lock;
+pub mod secret_service;
+mod secret_service_commands;
+pub mod ssh;
+mod ssh_commands;
+pub mod vault;
+mod vault_commands;
 
 use serde::{Deserialize, Serialize};
 use std::{fs, path::PathBuf, time::Duration};
@@ -263,6 +275,21 @@ pub fn run() {
                 eprintln!("Failed to clear stale browser bridge session on startup: {error}");
             }
 
+            #[cfg(unix)]
+            if let Err(error) = control::server::start() {
+                eprintln!("Failed to start the CLI control channel: {error}");
+            }
+
+            #[cfg(unix)]
+            if let Err(error) = ssh::agent::start() {
+                eprintln!("Failed to start the SSH agent: {error}");
+            }
+
+            #[cfg(target_os = "linux")]
+            secret_service_commands::start_if_enabled(app.handle());
+
+            api_commands::start_if_enabled();
+
             #[cfg(debug_assertions)]
             if let Err(error) = app.deep_link().register("clavispass-dev") {
                 eprintln!("Failed to register clavispass-dev deep link for development: {error}");
@@ -430,6 +457,9 @@ pub fn run() {
             commands::set_content_protection,
             commands::reset_window_size,
             commands::clear_clipboard_text,
+            commands::copy_secret_to_clipboard,
+            commands::copy_secret_for_pastes,
+            commands::clipboard_supports_paste_limit,
             device_identity::get_device_identity,
             bridge_commands::bridge_publish_session,
             bridge_commands::bridge_clear_session,
@@ -439,8 +469,44 @@ pub fn run() {
             bridge_commands::bridge_approve_pairing,
             bridge_commands::bridge_reject_pairing,
             bridge_commands::bridge_revoke_pairing,
+            bridge_commands::bridge_set_client_capabilities,
             bridge_commands::bridge_claim_pending_writes,
-            bridge_commands::bridge_complete_write_request
+            bridge_commands::bridge_generate_inbox_secret,
+            bridge_commands::bridge_list_write_requests,
+            bridge_commands::bridge_mark_write_awaiting_user,
+            bridge_commands::bridge_confirm_write_request,
+            bridge_commands::bridge_complete_write_request,
+            bridge_commands::bridge_list_password_history,
+            bridge_commands::bridge_record_password_change,
+            bridge_commands::bridge_restore_password_from_history,
+            bridge_commands::bridge_get_password_history_retention,
+            bridge_commands::bridge_set_password_history_retention,
+            vault_commands::vault_encrypt_v1,
+            vault_commands::vault_decrypt_v1,
+            control_commands::control_list_pending_approvals,
+            control_commands::control_list_grants,
+            control_commands::control_approve_process,
+            control_commands::control_reject_process,
+            control_commands::control_revoke_grants,
+            ssh_commands::ssh_agent_socket_path,
+            ssh_commands::ssh_agent_get_settings,
+            ssh_commands::ssh_agent_set_settings,
+            ssh_commands::ssh_agent_list_pending_signatures,
+            ssh_commands::ssh_agent_approve_signature,
+            ssh_commands::ssh_agent_reject_signature,
+            ssh_commands::ssh_generate_key,
+            ssh_commands::ssh_describe_key,
+            secret_service_commands::secret_service_get_status,
+            secret_service_commands::secret_service_set_enabled,
+            secret_service_commands::secret_service_list_pending_approvals,
+            secret_service_commands::secret_service_approve_application,
+            secret_service_commands::secret_service_reject_application,
+            api_commands::local_api_get_status,
+            api_commands::local_api_set_enabled,
+            api_commands::local_api_list_tokens,
+            api_commands::local_api_issue_token,
+            api_commands::local_api_revoke_token,
+            api_commands::local_api_list_audit
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
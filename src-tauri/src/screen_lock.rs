@@ -11,6 +11,8 @@ struct ScreenLockPayload {
 }
 
 fn emit(app: &AppHandle, locked: bool) {
+    #[cfg(unix)]
+    crate::ssh::agent::set_screen_locked(locked);
     let _ = app.emit("screen-lock://changed", ScreenLockPayload { locked });
 }
 
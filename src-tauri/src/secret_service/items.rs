@@ -0,0 +1,169 @@
+//! How vault entries appear as Secret Service items.
+
+use std::collections::HashMap;
+
+use crate::bridge::vault::{custom_fields, first_string, url_hosts, VaultData, VaultEntry};
+
+pub const ENTRY_ID_ATTRIBUTE: &str = "clavispass:entry-id";
+
+/// What the provider keeps of an item while the vault is unlocked: its
+/// entry and the attributes to search by. The secret is read on demand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretItem {
+    pub entry_id: String,
+    pub attributes: HashMap<String, String>,
+}
+
+/// One item per live entry with a password. Attributes are the entry id,
+/// `username`, `email`, the first `url` and its `host`, plus every custom
+/// field that is not a password field, keyed by its title. Custom fields
+/// let users add the attributes an application looks up, such as
+/// `service` for `secret-tool`.
+pub fn collect_items(vault: &VaultData) -> Vec<SecretItem> {
+    vault
+        .values
+        .iter()
+        .filter(|entry| entry.deleted_at.is_none())
+        .filter(|entry| first_string(entry, "PASSWORD").is_some())
+        .map(|entry| SecretItem {
+            entry_id: entry.id.clone(),
+            attributes: attributes(entry),
+        })
+        .collect()
+}
+
+/// Items whose attributes include every requested pair.
+pub fn search<'a>(
+    items: &'a [SecretItem],
+    requested: &HashMap<String, String>,
+) -> Vec<&'a SecretItem> {
+    items
+        .iter()
+        .filter(|item| {
+            requested
+                .iter()
+                .all(|(key, value)| item.attributes.get(key) == Some(value))
+        })
+        .collect()
+}
+
+/// The secret of an item, read from the current vault.
+pub fn secret(vault: &VaultData, entry_id: &str) -> Option<String> {
+    vault
+        .values
+        .iter()
+        .find(|entry| entry.id == entry_id && entry.deleted_at.is_none())
+        .and_then(|entry| first_string(entry, "PASSWORD"))
+}
+
+/// Object path element for an entry id. D-Bus only allows `[A-Za-z0-9_]`,
+/// so every other byte is written as `_` and two hex digits.
+pub fn object_name(entry_id: &str) -> String {
+    let mut name = String::with_capacity(entry_id.len());
+    for byte in entry_id.bytes() {
+        if byte.is_ascii_alphanumeric() {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("_{byte:02x}"));
+        }
+    }
+    if name.is_empty() {
+        name.push('_');
+    }
+    name
+}
+
+fn attributes(entry: &VaultEntry) -> HashMap<String, String> {
+    let mut attributes = HashMap::from([(ENTRY_ID_ATTRIBUTE.to_string(), entry.id.clone())]);
+    let builtin = [
+        ("username", first_string(entry, "USERNAME")),
+        ("email", first_string(entry, "E_MAIL")),
+        ("url", first_string(entry, "URL")),
+        ("host", url_hosts(entry).into_iter().next()),
+    ];
+    for (key, value) in builtin {
+        if let Some(value) = value {
+            attributes.insert(key.to_string(), value);
+        }
+    }
+
+    for field in custom_fields(entry) {
+        if field.input_type.as_deref() == Some("password") {
+            continue;
+        }
+        attributes.entry(field.title).or_insert(field.value);
+    }
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect_items, object_name, search, ENTRY_ID_ATTRIBUTE};
+    use crate::bridge::vault::VaultData;
+    use std::collections::HashMap;
+
+    #[test]
+    fn maps_live_entries_with_passwords_to_items() {
+        let vault = serde_json::from_value::<VaultData>(serde_json::json!({
+            "values": [
+                {
+                    "id": "mail-1",
+                    "title": "Mail server",
+                    "created": "2024-01-31T12:00:00.000Z",
+                    "modules": [
+                        { "module": "URL", "value": "https://mail.example.com/login" },
+                        { "module": "USERNAME", "value": "alice" },
+                        { "module": "PASSWORD", "value": "secret" },
+                        { "module": "CUSTOM_FIELD", "title": "service", "value": "imap" },
+                        {
+                            "module": "CUSTOM_FIELD",
+                            "title": "pin",
+                            "value": "1234",
+                            "inputType": "password"
+                        }
+                    ]
+                },
+                { "id": "note", "title": "Note only", "modules": [] },
+                {
+                    "id": "trashed",
+                    "title": "Trashed",
+                    "deletedAt": "2024-01-01T00:00:00.000Z",
+                    "modules": [{ "module": "PASSWORD", "value": "old" }]
+                }
+            ]
+        }))
+        .unwrap();
+
+        let items = collect_items(&vault);
+        assert_eq!(items.len(), 1);
+        let item = &items[0];
+        assert_eq!(item.attributes[ENTRY_ID_ATTRIBUTE], "mail-1");
+        assert_eq!(item.attributes["host"], "mail.example.com");
+        assert_eq!(item.attributes["service"], "imap");
+        assert!(!item.attributes.contains_key("pin"));
+
+        let query = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+        assert_eq!(
+            search(
+                &items,
+                &query(&[("service", "imap"), ("username", "alice")])
+            )
+            .len(),
+            1
+        );
+        assert!(search(&items, &query(&[("service", "smtp")])).is_empty());
+        assert_eq!(search(&items, &HashMap::new()).len(), 1);
+    }
+
+    #[test]
+    fn escapes_entry_ids_for_object_paths() {
+        assert_eq!(object_name("abc123"), "abc123");
+        assert_eq!(object_name("a-b.c"), "a_2db_2ec");
+        assert_eq!(object_name(""), "_");
+    }
+}
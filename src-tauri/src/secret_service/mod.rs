@@ -0,0 +1,21 @@
+//! Optional `org.freedesktop.secrets` provider, so that libsecret clients on
+//! Linux can read logins from the unlocked vault.
+
+use std::sync::OnceLock;
+
+use crate::control::approval::{ApprovalBook, ApprovalScope};
+
+#[cfg(target_os = "linux")]
+mod items;
+#[cfg(target_os = "linux")]
+pub mod service;
+pub mod settings;
+
+/// Applications the user allowed to read secrets during the current unlock
+/// session. Grants cover the executable, as libsecret clients start a new
+/// process for every lookup; for interpreters and `secret-tool` they cover
+/// the executable with its command line.
+pub fn approvals() -> &'static ApprovalBook {
+    static APPROVALS: OnceLock<ApprovalBook> = OnceLock::new();
+    APPROVALS.get_or_init(|| ApprovalBook::new(ApprovalScope::Executable))
+}
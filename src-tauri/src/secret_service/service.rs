@@ -0,0 +1,926 @@
+//! The Secret Service API (`org.freedesktop.Secret.*`) served on a D-Bus
+//! connection. There is one collection, `clavispass`, which is also the
+//! `default` alias. It is locked while the vault is. Applications only see
+//! items once the user allowed them in the desktop app; until then the
+//! collection looks empty and `Unlock` asks the app.
+//!
+//! Property reads do not tell who is asking, so items carry no label,
+//! attributes or dates in their properties and the collection lists no
+//! items there. Applications find items through `SearchItems` instead.
+//!
+//! Secrets travel with the `plain` algorithm; the session bus is private to
+//! the desktop user. Items cannot be created, changed or deleted over D-Bus.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use zbus::{
+    blocking::{self, connection::Builder},
+    fdo, interface,
+    message::Header,
+    names::BusName,
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Type, Value},
+    Connection, ObjectServer,
+};
+
+use super::items::{self, collect_items, object_name, SecretItem};
+use crate::{
+    bridge::vault::VaultData,
+    control::{
+        approval::{ApprovalBook, ApprovalDecision, ProcessIdentity, APPROVAL_TIMEOUT_MS},
+        peer::{current_uid, process_identity},
+    },
+};
+
+pub const SERVICE_NAME: &str = "org.freedesktop.secrets";
+const SERVICE_PATH: &str = "/org/freedesktop/secrets";
+const COLLECTION_PATH: &str = "/org/freedesktop/secrets/collection/clavispass";
+const DEFAULT_ALIAS_PATH: &str = "/org/freedesktop/secrets/aliases/default";
+const COLLECTION_INTERFACE: &str = "org.freedesktop.Secret.Collection";
+const PROMPT_INTERFACE: &str = "org.freedesktop.Secret.Prompt";
+
+const SYNC_INTERVAL: Duration = Duration::from_millis(500);
+const UNLOCK_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// What the provider needs from the desktop app.
+pub trait SecretBackend: Send + Sync + 'static {
+    /// The vault while it is unlocked.
+    fn unlocked_vault(&self) -> Option<VaultData>;
+    /// Grants of applications for the current unlock session.
+    fn approvals(&self) -> &ApprovalBook;
+    /// Brings the app forward so the user can unlock the vault.
+    fn request_unlock(&self);
+    fn request_lock(&self);
+}
+
+/// A running provider. Dropping it releases the bus name and stops syncing.
+pub struct SecretService {
+    connection: blocking::Connection,
+    stopped: Arc<AtomicBool>,
+}
+
+impl SecretService {
+    /// Serves on the session bus and claims `org.freedesktop.secrets`. Fails
+    /// if another provider, such as gnome-keyring, owns the name.
+    pub fn start(backend: impl SecretBackend) -> zbus::Result<Self> {
+        Self::serve(Builder::session()?, backend)
+    }
+
+    /// Serves on the bus at `address`, e.g. a private `dbus-daemon`.
+    pub fn start_at(address: &str, backend: impl SecretBackend) -> zbus::Result<Self> {
+        Self::serve(Builder::address(address)?, backend)
+    }
+
+    fn serve(builder: Builder<'_>, backend: impl SecretBackend) -> zbus::Result<Self> {
+        let shared = Arc::new(Shared {
+            backend: Box::new(backend),
+            state: Mutex::new(State {
+                locked: true,
+                ..State::default()
+            }),
+            next_id: AtomicU64::new(0),
+        });
+        let connection = builder
+            .serve_at(
+                SERVICE_PATH,
+                Service {
+                    shared: Arc::clone(&shared),
+                },
+            )?
+            .serve_at(
+                COLLECTION_PATH,
+                Collection {
+                    shared: Arc::clone(&shared),
+                },
+            )?
+            .serve_at(
+                DEFAULT_ALIAS_PATH,
+                Collection {
+                    shared: Arc::clone(&shared),
+                },
+            )?
+            .build()?;
+
+        sync(&connection, &shared)?;
+        connection.request_name(SERVICE_NAME)?;
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let sync_connection = connection.clone();
+        let sync_stopped = Arc::clone(&stopped);
+        thread::spawn(move || {
+            while !sync_stopped.load(Ordering::SeqCst) {
+                if let Err(error) = sync(&sync_connection, &shared) {
+                    eprintln!("Failed to sync Secret Service items: {error}");
+                }
+                thread::sleep(SYNC_INTERVAL);
+            }
+        });
+
+        Ok(Self {
+            connection,
+            stopped,
+        })
+    }
+}
+
+impl Drop for SecretService {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        let _ = self.connection.release_name(SERVICE_NAME);
+    }
+}
+
+struct Shared {
+    backend: Box<dyn SecretBackend>,
+    state: Mutex<State>,
+    next_id: AtomicU64,
+}
+
+#[derive(Default)]
+struct State {
+    /// Items of the unlocked vault; empty while it is locked.
+    items: Vec<SecretItem>,
+    locked: bool,
+    sessions: Vec<OwnedObjectPath>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn next_path(&self, kind: &str) -> OwnedObjectPath {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        owned_path(&format!("{SERVICE_PATH}/{kind}/{id}"))
+    }
+
+    /// Matching items, if `caller` may see them; none otherwise.
+    fn search(
+        &self,
+        caller: Option<&ProcessIdentity>,
+        attributes: &HashMap<String, String>,
+    ) -> Vec<OwnedObjectPath> {
+        if self.vault_for(caller).is_none() {
+            return Vec::new();
+        }
+        items::search(&self.lock().items, attributes)
+            .into_iter()
+            .map(|item| item_path(&item.entry_id))
+            .collect()
+    }
+
+    fn entry_id(&self, path: &ObjectPath<'_>) -> Option<String> {
+        self.lock()
+            .items
+            .iter()
+            .find(|item| item_path(&item.entry_id).as_str() == path.as_str())
+            .map(|item| item.entry_id.clone())
+    }
+
+    /// The vault, if it is unlocked and `caller` may read from it.
+    fn vault_for(&self, caller: Option<&ProcessIdentity>) -> Option<VaultData> {
+        let caller = caller?;
+        if !self.backend.approvals().is_granted(caller) {
+            return None;
+        }
+        self.backend.unlocked_vault()
+    }
+
+    fn check_session(&self, session: &ObjectPath<'_>) -> Result<(), SecretError> {
+        if self
+            .lock()
+            .sessions
+            .iter()
+            .any(|open| open.as_str() == session.as_str())
+        {
+            Ok(())
+        } else {
+            Err(SecretError::NoSession(format!("No session {session}")))
+        }
+    }
+
+    /// Waits until the vault is unlocked and the user allowed `caller`,
+    /// asking the app for both. Used by prompts, off the bus executor.
+    fn await_access(&self, caller: &ProcessIdentity) -> bool {
+        let deadline = Instant::now() + Duration::from_millis(APPROVAL_TIMEOUT_MS);
+        if self.backend.unlocked_vault().is_none() {
+            self.backend.request_unlock();
+            while self.backend.unlocked_vault().is_none() {
+                if Instant::now() >= deadline {
+                    return false;
+                }
+                thread::sleep(UNLOCK_POLL_INTERVAL);
+            }
+        }
+
+        let approvals = self.backend.approvals();
+        if approvals.is_granted(caller) {
+            return true;
+        }
+        if approvals.is_rejected(caller) {
+            return false;
+        }
+        approvals.request_approval(caller, Duration::from_millis(APPROVAL_TIMEOUT_MS))
+            == ApprovalDecision::Approved
+    }
+}
+
+/// Mirrors the vault into item objects and the lock state. Objects are
+/// registered before the index names them and removed after it stopped
+/// doing so. Locking drops every item.
+fn sync(connection: &blocking::Connection, shared: &Arc<Shared>) -> zbus::Result<()> {
+    let vault = shared.backend.unlocked_vault();
+    let locked = vault.is_none();
+    let server = connection.object_server();
+
+    let was_locked = std::mem::replace(&mut shared.lock().locked, locked);
+    if locked {
+        // Grants only live as long as the unlock session.
+        shared.backend.approvals().revoke_all();
+    }
+
+    let current = vault.as_ref().map(collect_items).unwrap_or_default();
+    let previous = shared.lock().items.clone();
+    let known = |items: &[SecretItem], entry_id: &str| {
+        items.iter().find(|item| item.entry_id == entry_id).cloned()
+    };
+
+    let mut created = Vec::new();
+    let mut changed = Vec::new();
+    for item in &current {
+        match known(&previous, &item.entry_id) {
+            None => {
+                server.at(
+                    item_path(&item.entry_id),
+                    Item {
+                        shared: Arc::clone(shared),
+                        entry_id: item.entry_id.clone(),
+                    },
+                )?;
+                created.push(item_path(&item.entry_id));
+            }
+            Some(old) if old != *item => changed.push(item_path(&item.entry_id)),
+            Some(_) => {}
+        }
+    }
+    let deleted = previous
+        .iter()
+        .filter(|item| known(&current, &item.entry_id).is_none())
+        .map(|item| item_path(&item.entry_id))
+        .collect::<Vec<_>>();
+
+    shared.lock().items = current;
+    for path in &deleted {
+        server.remove::<Item, _>(path)?;
+    }
+
+    for (signal, paths) in [
+        ("ItemCreated", &created),
+        ("ItemChanged", &changed),
+        ("ItemDeleted", &deleted),
+    ] {
+        for path in paths {
+            connection.emit_signal(
+                None::<BusName<'_>>,
+                COLLECTION_PATH,
+                COLLECTION_INTERFACE,
+                signal,
+                &(path,),
+            )?;
+        }
+    }
+
+    if was_locked != locked {
+        notify_collection(connection, |collection, context| {
+            zbus::block_on(collection.locked_changed(context))
+        })?;
+        connection.emit_signal(
+            None::<BusName<'_>>,
+            SERVICE_PATH,
+            "org.freedesktop.Secret.Service",
+            "CollectionChanged",
+            &(owned_path(COLLECTION_PATH),),
+        )?;
+    }
+    Ok(())
+}
+
+fn notify_collection<F>(connection: &blocking::Connection, notify: F) -> zbus::Result<()>
+where
+    F: Fn(&Collection, &zbus::SignalContext<'static>) -> zbus::Result<()>,
+{
+    for path in [COLLECTION_PATH, DEFAULT_ALIAS_PATH] {
+        let collection = connection
+            .object_server()
+            .interface::<_, Collection>(path)?;
+        notify(&collection.get(), collection.signal_context())?;
+    }
+    Ok(())
+}
+
+/// The calling process as reported by the bus daemon. `None` if it cannot be
+/// told, which leaves everything locked for it.
+async fn caller(connection: &Connection, header: &Header<'_>) -> Option<ProcessIdentity> {
+    let sender = BusName::from(header.sender()?.to_owned());
+    let dbus = fdo::DBusProxy::new(connection).await.ok()?;
+    let pid = dbus
+        .get_connection_unix_process_id(sender.clone())
+        .await
+        .ok()?;
+    let uid = dbus.get_connection_unix_user(sender).await.ok()?;
+    if uid != current_uid() {
+        return None;
+    }
+    process_identity(pid, uid).ok()
+}
+
+#[derive(Debug, zbus::DBusError)]
+#[zbus(prefix = "org.freedesktop.Secret.Error")]
+enum SecretError {
+    #[zbus(error)]
+    ZBus(zbus::Error),
+    IsLocked(String),
+    NoSession(String),
+}
+
+/// A secret as the API transfers it: `(oayays)`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+struct Secret {
+    session: OwnedObjectPath,
+    parameters: Vec<u8>,
+    value: Vec<u8>,
+    content_type: String,
+}
+
+impl Secret {
+    fn plain(session: OwnedObjectPath, value: String) -> Self {
+        Self {
+            session,
+            parameters: Vec::new(),
+            value: value.into_bytes(),
+            content_type: "text/plain; charset=utf8".to_string(),
+        }
+    }
+}
+
+struct Service {
+    shared: Arc<Shared>,
+}
+
+#[interface(name = "org.freedesktop.Secret.Service")]
+impl Service {
+    async fn open_session(
+        &self,
+        algorithm: &str,
+        _input: OwnedValue,
+        #[zbus(object_server)] server: &ObjectServer,
+    ) -> fdo::Result<(Value<'static>, OwnedObjectPath)> {
+        if algorithm != "plain" {
+            return Err(fdo::Error::NotSupported(format!(
+                "Algorithm {algorithm} is not supported"
+            )));
+        }
+
+        let path = self.shared.next_path("session");
+        server
+            .at(
+                &path,
+                Session {
+                    shared: Arc::clone(&self.shared),
+                },
+            )
+            .await?;
+        self.shared.lock().sessions.push(path.clone());
+        Ok((Value::from(""), path))
+    }
+
+    fn create_collection(
+        &self,
+        _properties: HashMap<String, OwnedValue>,
+        _alias: &str,
+    ) -> fdo::Result<(OwnedObjectPath, OwnedObjectPath)> {
+        Err(fdo::Error::NotSupported(
+            "ClavisPass only offers its own collection".to_string(),
+        ))
+    }
+
+    /// Matching items, all unlocked. Nothing matches for a caller the user
+    /// has not allowed, nor while the vault is locked.
+    async fn search_items(
+        &self,
+        attributes: HashMap<String, String>,
+        #[zbus(connection)] connection: &Connection,
+        #[zbus(header)] header: Header<'_>,
+    ) -> (Vec<OwnedObjectPath>, Vec<OwnedObjectPath>) {
+        let caller = caller(connection, &header).await;
+        (self.shared.search(caller.as_ref(), &attributes), Vec::new())
+    }
+
+    /// Unlocking asks the app: to unlock the vault if needed, then to allow
+    /// the caller. Both happen through a prompt.
+    async fn unlock(
+        &self,
+        objects: Vec<OwnedObjectPath>,
+        #[zbus(connection)] connection: &Connection,
+        #[zbus(header)] header: Header<'_>,
+        #[zbus(object_server)] server: &ObjectServer,
+    ) -> fdo::Result<(Vec<OwnedObjectPath>, OwnedObjectPath)> {
+        let caller = caller(connection, &header).await.ok_or_else(|| {
+            fdo::Error::AccessDenied("The calling process could not be identified".to_string())
+        })?;
+        if self.shared.vault_for(Some(&caller)).is_some() {
+            return Ok((objects, owned_path("/")));
+        }
+
+        let path = self.shared.next_path("prompt");
+        server
+            .at(
+                &path,
+                Prompt {
+                    shared: Arc::clone(&self.shared),
+                    caller,
+                    objects,
+                    path: path.clone(),
+                    started: AtomicBool::new(false),
+                    done: Arc::new(AtomicBool::new(false)),
+                },
+            )
+            .await?;
+        Ok((Vec::new(), path))
+    }
+
+    /// Locks the vault in the app, which locks every item.
+    fn lock(&self, objects: Vec<OwnedObjectPath>) -> (Vec<OwnedObjectPath>, OwnedObjectPath) {
+        self.shared.backend.request_lock();
+        (objects, owned_path("/"))
+    }
+
+    async fn get_secrets(
+        &self,
+        items: Vec<OwnedObjectPath>,
+        session: OwnedObjectPath,
+        #[zbus(connection)] connection: &Connection,
+        #[zbus(header)] header: Header<'_>,
+    ) -> Result<HashMap<OwnedObjectPath, Secret>, SecretError> {
+        self.shared.check_session(&session)?;
+        let caller = caller(connection, &header).await;
+        let vault = self
+            .shared
+            .vault_for(caller.as_ref())
+            .ok_or_else(|| SecretError::IsLocked("Unlock the items first".to_string()))?;
+
+        Ok(items
+            .into_iter()
+            .filter_map(|path| {
+                let value = items::secret(&vault, &self.shared.entry_id(&path)?)?;
+                Some((path, Secret::plain(session.clone(), value)))
+            })
+            .collect())
+    }
+
+    fn read_alias(&self, name: &str) -> OwnedObjectPath {
+        owned_path(if name == "default" {
+            COLLECTION_PATH
+        } else {
+            "/"
+        })
+    }
+
+    fn set_alias(&self, _name: &str, _collection: OwnedObjectPath) -> fdo::Result<()> {
+        Err(fdo::Error::NotSupported(
+            "Aliases cannot be changed".to_string(),
+        ))
+    }
+
+    #[zbus(property)]
+    fn collections(&self) -> Vec<OwnedObjectPath> {
+        vec![owned_path(COLLECTION_PATH)]
+    }
+}
+
+struct Collection {
+    shared: Arc<Shared>,
+}
+
+#[interface(name = "org.freedesktop.Secret.Collection")]
+impl Collection {
+    fn delete(&self) -> fdo::Result<OwnedObjectPath> {
+        Err(fdo::Error::NotSupported(
+            "The ClavisPass collection cannot be deleted".to_string(),
+        ))
+    }
+
+    async fn search_items(
+        &self,
+        attributes: HashMap<String, String>,
+        #[zbus(connection)] connection: &Connection,
+        #[zbus(header)] header: Header<'_>,
+    ) -> Vec<OwnedObjectPath> {
+        let caller = caller(connection, &header).await;
+        self.shared.search(caller.as_ref(), &attributes)
+    }
+
+    fn create_item(
+        &self,
+        _properties: HashMap<String, OwnedValue>,
+        _secret: Secret,
+        _replace: bool,
+    ) -> fdo::Result<(OwnedObjectPath, OwnedObjectPath)> {
+        Err(fdo::Error::NotSupported(
+            "Entries are added in the ClavisPass app".to_string(),
+        ))
+    }
+
+    #[zbus(property)]
+    fn items(&self) -> Vec<OwnedObjectPath> {
+        Vec::new()
+    }
+
+    #[zbus(property)]
+    fn label(&self) -> String {
+        "ClavisPass".to_string()
+    }
+
+    #[zbus(property)]
+    fn locked(&self) -> bool {
+        self.shared.lock().locked
+    }
+
+    #[zbus(property)]
+    fn created(&self) -> u64 {
+        0
+    }
+
+    #[zbus(property)]
+    fn modified(&self) -> u64 {
+        0
+    }
+}
+
+struct Item {
+    shared: Arc<Shared>,
+    entry_id: String,
+}
+
+#[interface(name = "org.freedesktop.Secret.Item")]
+impl Item {
+    fn delete(&self) -> fdo::Result<OwnedObjectPath> {
+        Err(fdo::Error::NotSupported(
+            "Entries are deleted in the ClavisPass app".to_string(),
+        ))
+    }
+
+    async fn get_secret(
+        &self,
+        session: OwnedObjectPath,
+        #[zbus(connection)] connection: &Connection,
+        #[zbus(header)] header: Header<'_>,
+    ) -> Result<Secret, SecretError> {
+        self.shared.check_session(&session)?;
+        let caller = caller(connection, &header).await;
+        let vault = self
+            .shared
+            .vault_for(caller.as_ref())
+            .ok_or_else(|| SecretError::IsLocked("Unlock the item first".to_string()))?;
+        let value = items::secret(&vault, &self.entry_id).ok_or_else(|| {
+            SecretError::ZBus(fdo::Error::UnknownObject("The entry is gone".to_string()).into())
+        })?;
+        Ok(Secret::plain(session, value))
+    }
+
+    fn set_secret(&self, _secret: Secret) -> fdo::Result<()> {
+        Err(fdo::Error::NotSupported(
+            "Entries are changed in the ClavisPass app".to_string(),
+        ))
+    }
+
+    #[zbus(property)]
+    fn locked(&self) -> bool {
+        self.shared.lock().locked
+    }
+
+    #[zbus(property)]
+    fn attributes(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    #[zbus(property)]
+    fn label(&self) -> String {
+        String::new()
+    }
+
+    #[zbus(property)]
+    fn created(&self) -> u64 {
+        0
+    }
+
+    #[zbus(property)]
+    fn modified(&self) -> u64 {
+        0
+    }
+}
+
+struct Session {
+    shared: Arc<Shared>,
+}
+
+#[interface(name = "org.freedesktop.Secret.Session")]
+impl Session {
+    async fn close(
+        &self,
+        #[zbus(header)] header: Header<'_>,
+        #[zbus(object_server)] server: &ObjectServer,
+    ) -> fdo::Result<()> {
+        let Some(path) = header
+            .path()
+            .map(|path| OwnedObjectPath::from(path.to_owned()))
+        else {
+            return Ok(());
+        };
+        self.shared.lock().sessions.retain(|open| *open != path);
+        server.remove::<Session, _>(&path).await?;
+        Ok(())
+    }
+}
+
+struct Prompt {
+    shared: Arc<Shared>,
+    caller: ProcessIdentity,
+    objects: Vec<OwnedObjectPath>,
+    path: OwnedObjectPath,
+    started: AtomicBool,
+    done: Arc<AtomicBool>,
+}
+
+#[interface(name = "org.freedesktop.Secret.Prompt")]
+impl Prompt {
+    /// Waits for the app on a separate thread and emits `Completed` with the
+    /// unlocked objects, or as dismissed if the user did not allow access.
+    fn prompt(&self, _window_id: &str, #[zbus(connection)] connection: &Connection) {
+        if self.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let connection = blocking::Connection::from(connection.clone());
+        let shared = Arc::clone(&self.shared);
+        let caller = self.caller.clone();
+        let objects = self.objects.clone();
+        let path = self.path.clone();
+        let done = Arc::clone(&self.done);
+        thread::spawn(move || {
+            let approved = shared.await_access(&caller);
+            if done.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            let result = if approved { objects } else { Vec::new() };
+            if let Err(error) = complete(&connection, &path, !approved, result) {
+                eprintln!("Failed to complete Secret Service prompt: {error}");
+            }
+        });
+    }
+
+    fn dismiss(&self, #[zbus(connection)] connection: &Connection) -> fdo::Result<()> {
+        if self.done.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        let connection = blocking::Connection::from(connection.clone());
+        let path = self.path.clone();
+        // Emitting and unregistering wait for the object server, which is
+        // busy dispatching this call.
+        thread::spawn(move || {
+            if let Err(error) = complete(&connection, &path, true, Vec::new()) {
+                eprintln!("Failed to dismiss Secret Service prompt: {error}");
+            }
+        });
+        Ok(())
+    }
+}
+
+fn complete(
+    connection: &blocking::Connection,
+    path: &OwnedObjectPath,
+    dismissed: bool,
+    result: Vec<OwnedObjectPath>,
+) -> zbus::Result<()> {
+    connection.emit_signal(
+        None::<BusName<'_>>,
+        path,
+        PROMPT_INTERFACE,
+        "Completed",
+        &(dismissed, Value::from(result)),
+    )?;
+    connection.object_server().remove::<Prompt, _>(path)?;
+    Ok(())
+}
+
+fn item_path(entry_id: &str) -> OwnedObjectPath {
+    owned_path(&format!("{COLLECTION_PATH}/{}", object_name(entry_id)))
+}
+
+fn owned_path(path: &str) -> OwnedObjectPath {
+    OwnedObjectPath::try_from(path.to_string()).expect("valid object path")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SecretBackend, SecretService, COLLECTION_PATH, SERVICE_NAME, SERVICE_PATH};
+    use crate::{bridge::vault::VaultData, control::approval::ApprovalBook};
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader},
+        process::{Child, Command, Stdio},
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+    use zbus::{
+        blocking::{connection::Builder, Connection, Proxy},
+        zvariant::{OwnedObjectPath, OwnedValue, Value},
+    };
+
+    type Secrets = HashMap<OwnedObjectPath, (OwnedObjectPath, Vec<u8>, Vec<u8>, String)>;
+
+    #[derive(Default)]
+    struct TestApp {
+        vault: Mutex<Option<VaultData>>,
+        approvals: ApprovalBook,
+    }
+
+    struct Backend(Arc<TestApp>);
+
+    impl SecretBackend for Backend {
+        fn unlocked_vault(&self) -> Option<VaultData> {
+            self.0.vault.lock().unwrap().clone()
+        }
+
+        fn approvals(&self) -> &ApprovalBook {
+            &self.0.approvals
+        }
+
+        fn request_unlock(&self) {
+            *self.0.vault.lock().unwrap() = Some(vault());
+        }
+
+        fn request_lock(&self) {
+            *self.0.vault.lock().unwrap() = None;
+        }
+    }
+
+    /// A private session bus for the test; killed when dropped.
+    struct PrivateBus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl PrivateBus {
+        fn spawn() -> Option<Self> {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address=1"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.take()?)
+                .read_line(&mut address)
+                .ok()?;
+            Some(Self {
+                daemon,
+                address: address.trim().to_string(),
+            })
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    fn vault() -> VaultData {
+        serde_json::from_value(serde_json::json!({
+            "values": [{
+                "id": "mail-1",
+                "title": "Mail server",
+                "modules": [
+                    { "module": "USERNAME", "value": "alice" },
+                    { "module": "PASSWORD", "value": "secret" },
+                    { "module": "CUSTOM_FIELD", "title": "service", "value": "imap" }
+                ]
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn proxy<'a>(connection: &Connection, path: &'a str, interface: &'a str) -> Proxy<'a> {
+        Proxy::new(connection, SERVICE_NAME, path, interface).unwrap()
+    }
+
+    #[test]
+    fn unlocks_items_per_application_on_a_private_bus() {
+        let Some(bus) = PrivateBus::spawn() else {
+            eprintln!("dbus-daemon is not installed; skipping");
+            return;
+        };
+        let app = Arc::new(TestApp::default());
+        let _service = SecretService::start_at(&bus.address, Backend(Arc::clone(&app))).unwrap();
+        assert!(SecretService::start_at(&bus.address, Backend(Arc::clone(&app))).is_err());
+
+        let client = Builder::address(bus.address.as_str())
+            .unwrap()
+            .build()
+            .unwrap();
+        let service = proxy(&client, SERVICE_PATH, "org.freedesktop.Secret.Service");
+        let collection = proxy(
+            &client,
+            COLLECTION_PATH,
+            "org.freedesktop.Secret.Collection",
+        );
+        let query = HashMap::from([("service", "imap")]);
+        let search = || -> (Vec<OwnedObjectPath>, Vec<OwnedObjectPath>) {
+            service.call("SearchItems", &(&query,)).unwrap()
+        };
+
+        let mut created = collection.receive_signal("ItemCreated").unwrap();
+        let mut deleted = collection.receive_signal("ItemDeleted").unwrap();
+
+        // Locked vault: nothing known yet. The prompt asks the app to unlock.
+        assert_eq!(search(), (Vec::new(), Vec::new()));
+        assert!(collection.get_property::<bool>("Locked").unwrap());
+        let (_, session): (OwnedValue, OwnedObjectPath) = service
+            .call("OpenSession", &("plain", Value::from("")))
+            .unwrap();
+        let (_, prompt): (Vec<OwnedObjectPath>, OwnedObjectPath) = service
+            .call("Unlock", &(Vec::<OwnedObjectPath>::new(),))
+            .unwrap();
+
+        let prompt = proxy(&client, prompt.as_str(), "org.freedesktop.Secret.Prompt");
+        let mut completed = prompt.receive_signal("Completed").unwrap();
+        let approver = {
+            let app = Arc::clone(&app);
+            thread::spawn(move || loop {
+                if let Some(pending) = app.approvals.list_pending().first() {
+                    assert!(app.approvals.approve(&pending.id));
+                    return;
+                }
+                thread::sleep(Duration::from_millis(10));
+            })
+        };
+        let _: () = prompt.call("Prompt", &("",)).unwrap();
+        let (dismissed, _): (bool, OwnedValue) =
+            completed.next().unwrap().body().deserialize().unwrap();
+        assert!(!dismissed);
+        approver.join().unwrap();
+
+        // Approved and unlocked: items show up unlocked and hand out secrets,
+        // but their properties name nothing.
+        let (item_path,): (OwnedObjectPath,) =
+            created.next().unwrap().body().deserialize().unwrap();
+        let (unlocked, locked) = search();
+        assert_eq!((unlocked, locked.len()), (vec![item_path.clone()], 0));
+        assert!(!collection.get_property::<bool>("Locked").unwrap());
+        let secrets: Secrets = service
+            .call("GetSecrets", &(vec![&item_path], &session))
+            .unwrap();
+        assert_eq!(secrets[&item_path].2, b"secret");
+        let item = proxy(&client, item_path.as_str(), "org.freedesktop.Secret.Item");
+        assert_eq!(item.get_property::<String>("Label").unwrap(), "");
+        assert!(item
+            .get_property::<HashMap<String, String>>("Attributes")
+            .unwrap()
+            .is_empty());
+        assert!(collection
+            .get_property::<Vec<OwnedObjectPath>>("Items")
+            .unwrap()
+            .is_empty());
+
+        // Locking the vault locks the items again and ends the grant.
+        let _: (Vec<OwnedObjectPath>, OwnedObjectPath) = service
+            .call(
+                "Lock",
+                &(vec![OwnedObjectPath::try_from(COLLECTION_PATH).unwrap()],),
+            )
+            .unwrap();
+        let (gone,): (OwnedObjectPath,) = deleted.next().unwrap().body().deserialize().unwrap();
+        assert_eq!(gone, item_path);
+        assert_eq!(search(), (Vec::new(), Vec::new()));
+        assert!(app.approvals.list_grants().is_empty());
+        let error = service
+            .call::<_, _, Secrets>("GetSecrets", &(vec![&item_path], &session))
+            .unwrap_err();
+        assert!(
+            matches!(&error, zbus::Error::MethodError(name, _, _) if name.as_str() == "org.freedesktop.Secret.Error.IsLocked"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+use crate::bridge::path::secret_service_settings_path;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretServiceSettings {
+    /// Claim `org.freedesktop.secrets` on the session bus. Off by default, as
+    /// only one provider can own the name and desktops ship their own.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+pub fn load_settings() -> io::Result<SecretServiceSettings> {
+    let path = secret_service_settings_path()?;
+    if !path.exists() {
+        return Ok(SecretServiceSettings::default());
+    }
+
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str::<SecretServiceSettings>(&content)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+pub fn save_settings(settings: &SecretServiceSettings) -> io::Result<()> {
+    let path = secret_service_settings_path()?;
+    write_json_atomically(&path, settings)
+}
+
+fn write_json_atomically<T: Serialize>(path: &PathBuf, value: &T) -> io::Result<()> {
+    let temp = path.with_extension("tmp");
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    fs::write(&temp, json)?;
+    fs::rename(temp, path)?;
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{
+    control::approval::PendingProcessApproval,
+    secret_service::{
+        approvals,
+        settings::{self, SecretServiceSettings},
+    },
+};
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretServiceStatus {
+    /// Only Linux desktops have a Secret Service.
+    pub supported: bool,
+    pub enabled: bool,
+    pub running: bool,
+}
+
+#[cfg(target_os = "linux")]
+mod provider {
+    use std::sync::{Mutex, MutexGuard};
+    use tauri::AppHandle;
+
+    use crate::{
+        bridge::{session, vault::VaultData},
+        control::approval::ApprovalBook,
+        secret_service::{
+            approvals,
+            service::{SecretBackend, SecretService},
+        },
+    };
+
+    static RUNNING: Mutex<Option<SecretService>> = Mutex::new(None);
+
+    struct AppBackend {
+        app: AppHandle,
+    }
+
+    impl SecretBackend for AppBackend {
+        fn unlocked_vault(&self) -> Option<VaultData> {
+            session::load_session()
+                .ok()
+                .flatten()
+                .map(|snapshot| snapshot.vault)
+        }
+
+        fn approvals(&self) -> &ApprovalBook {
+            approvals()
+        }
+
+        fn request_unlock(&self) {
+            crate::show_main_window(&self.app);
+        }
+
+        fn request_lock(&self) {
+            crate::emit_lock_vault(&self.app);
+        }
+    }
+
+    fn running() -> MutexGuard<'static, Option<SecretService>> {
+        RUNNING
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn start(app: &AppHandle) -> zbus::Result<()> {
+        let mut running = running();
+        if running.is_none() {
+            *running = Some(SecretService::start(AppBackend { app: app.clone() })?);
+        }
+        Ok(())
+    }
+
+    pub fn stop() {
+        running().take();
+        approvals().revoke_all();
+    }
+
+    pub fn is_running() -> bool {
+        running().is_some()
+    }
+}
+
+/// Starts the provider at launch if the user turned it on.
+#[cfg(target_os = "linux")]
+pub fn start_if_enabled(app: &AppHandle) {
+    match settings::load_settings() {
+        Ok(settings) if settings.enabled => {
+            if let Err(error) = provider::start(app) {
+                eprintln!("Failed to start the Secret Service provider: {error}");
+            }
+        }
+        Ok(_) => {}
+        Err(error) => eprintln!("Failed to load Secret Service settings: {error}"),
+    }
+}
+
+#[tauri::command]
+pub fn secret_service_get_status() -> Result<SecretServiceStatus, String> {
+    let settings = settings::load_settings()
+        .map_err(|err| format!("Failed to load Secret Service settings: {err}"))?;
+    Ok(SecretServiceStatus {
+        supported: cfg!(target_os = "linux"),
+        enabled: settings.enabled,
+        #[cfg(target_os = "linux")]
+        running: provider::is_running(),
+        #[cfg(not(target_os = "linux"))]
+        running: false,
+    })
+}
+
+/// Turning the provider on fails, and is not saved, while another provider
+/// such as gnome-keyring owns the bus name.
+#[tauri::command]
+pub fn secret_service_set_enabled(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<SecretServiceStatus, String> {
+    #[cfg(target_os = "linux")]
+    if enabled {
+        provider::start(&app)
+            .map_err(|err| format!("Failed to start the Secret Service provider: {err}"))?;
+    } else {
+        provider::stop();
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = app;
+        if enabled {
+            return Err("The Secret Service is only available on Linux".to_string());
+        }
+    }
+
+    settings::save_settings(&SecretServiceSettings { enabled })
+        .map_err(|err| format!("Failed to save Secret Service settings: {err}"))?;
+    secret_service_get_status()
+}
+
+#[tauri::command]
+pub fn secret_service_list_pending_approvals() -> Vec<PendingProcessApproval> {
+    approvals().list_pending()
+}
+
+#[tauri::command]
+pub fn secret_service_approve_application(approval_id: String) -> bool {
+    approvals().approve(&approval_id)
+}
+
+#[tauri::command]
+pub fn secret_service_reject_application(approval_id: String) -> bool {
+    approvals().reject(&approval_id)
+}
@@ -0,0 +1,306 @@
+//! The ssh-agent protocol (draft-miller-ssh-agent) on a Unix socket. Only
+//! listing identities and signing are supported; keys cannot be added or
+//! removed through the agent, they live in the vault.
+
+use std::{
+    fs,
+    io::{self, Read, Write},
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+
+use super::{
+    confirm::{confirmations, SignatureRequest, CONFIRM_TIMEOUT_MS},
+    keys::{load_identities, put_string, AgentIdentity},
+    settings::load_settings,
+};
+use crate::{
+    bridge::{path::ssh_agent_socket_path, session},
+    control::{approval::ProcessIdentity, peer},
+};
+
+const SSH_AGENT_FAILURE: u8 = 5;
+const SSH_AGENTC_REQUEST_IDENTITIES: u8 = 11;
+const SSH_AGENT_IDENTITIES_ANSWER: u8 = 12;
+const SSH_AGENTC_SIGN_REQUEST: u8 = 13;
+const SSH_AGENT_SIGN_RESPONSE: u8 = 14;
+
+const MAX_MESSAGE_LEN: usize = 256 * 1024;
+
+static SCREEN_LOCKED: AtomicBool = AtomicBool::new(false);
+
+/// Called on `screen-lock://changed`. While the screen is locked the agent
+/// offers no keys, and signatures waiting for confirmation are rejected.
+pub fn set_screen_locked(locked: bool) {
+    SCREEN_LOCKED.store(locked, Ordering::SeqCst);
+    if locked {
+        confirmations().reject_all();
+    }
+}
+
+/// Binds the agent socket and serves clients on a background thread for as
+/// long as the app runs.
+pub fn start() -> io::Result<()> {
+    let path = ssh_agent_socket_path()?;
+    match UnixStream::connect(&path) {
+        Ok(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                "Another ClavisPass instance serves the SSH agent socket",
+            ))
+        }
+        Err(_) if path.exists() => fs::remove_file(&path)?,
+        Err(_) => {}
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    thread::spawn(move || {
+                        if let Err(error) = serve_client(stream) {
+                            eprintln!("SSH agent client failed: {error}");
+                        }
+                    });
+                }
+                Err(error) => eprintln!("Failed to accept SSH agent client: {error}"),
+            }
+        }
+    });
+    Ok(())
+}
+
+fn serve_client(mut stream: UnixStream) -> io::Result<()> {
+    let client = peer::peer_identity(&stream)?;
+    if client.uid != peer::current_uid() {
+        return Ok(());
+    }
+
+    while let Some(message) = read_message(&mut stream)? {
+        let response = respond(&message, &unlocked_identities(), |identity| {
+            confirm_signature(identity, &client)
+        });
+        write_message(&mut stream, &response)?;
+    }
+    Ok(())
+}
+
+/// Keys of the published unlocked session; none while the vault or the
+/// screen is locked.
+fn unlocked_identities() -> Vec<AgentIdentity> {
+    if SCREEN_LOCKED.load(Ordering::SeqCst) {
+        return Vec::new();
+    }
+    match session::load_session() {
+        Ok(Some(snapshot)) => load_identities(&snapshot.vault),
+        _ => Vec::new(),
+    }
+}
+
+fn confirm_signature(identity: &AgentIdentity, client: &ProcessIdentity) -> bool {
+    let confirm_each_use = load_settings()
+        .map(|settings| settings.confirm_each_use)
+        .unwrap_or(true);
+    if confirm_each_use {
+        let approved = confirmations().confirm(
+            SignatureRequest {
+                entry_id: identity.entry_id.clone(),
+                title: identity.title.clone(),
+                fingerprint: identity.fingerprint(),
+                pid: Some(client.pid),
+                exe: client.exe.clone(),
+                command_line: client.command_line.clone(),
+            },
+            Duration::from_millis(CONFIRM_TIMEOUT_MS),
+        );
+        if !approved {
+            return false;
+        }
+    }
+
+    // The vault or screen may have been locked while the prompt was open.
+    !SCREEN_LOCKED.load(Ordering::SeqCst) && matches!(session::load_session(), Ok(Some(_)))
+}
+
+/// Answers one agent message. `confirm` decides whether `identity` may sign.
+fn respond<F>(message: &[u8], identities: &[AgentIdentity], confirm: F) -> Vec<u8>
+where
+    F: FnOnce(&AgentIdentity) -> bool,
+{
+    let Some((&kind, body)) = message.split_first() else {
+        return vec![SSH_AGENT_FAILURE];
+    };
+
+    match kind {
+        SSH_AGENTC_REQUEST_IDENTITIES => {
+            let listed = identities
+                .iter()
+                .filter_map(|identity| Some((identity.public_blob().ok()?, &identity.title)))
+                .collect::<Vec<_>>();
+            let mut response = vec![SSH_AGENT_IDENTITIES_ANSWER];
+            response.extend_from_slice(&(listed.len() as u32).to_be_bytes());
+            for (blob, title) in listed {
+                put_string(&mut response, &blob);
+                put_string(&mut response, title.as_bytes());
+            }
+            response
+        }
+        SSH_AGENTC_SIGN_REQUEST => {
+            sign(body, identities, confirm).unwrap_or_else(|| vec![SSH_AGENT_FAILURE])
+        }
+        _ => vec![SSH_AGENT_FAILURE],
+    }
+}
+
+fn sign<F>(body: &[u8], identities: &[AgentIdentity], confirm: F) -> Option<Vec<u8>>
+where
+    F: FnOnce(&AgentIdentity) -> bool,
+{
+    let mut reader = body;
+    let key_blob = take_string(&mut reader)?;
+    let data = take_string(&mut reader)?;
+    let flags = take_u32(&mut reader)?;
+
+    let identity = identities
+        .iter()
+        .find(|identity| identity.public_blob().ok().as_deref() == Some(key_blob))?;
+    if !confirm(identity) {
+        return None;
+    }
+
+    let signature = match identity.sign(data, flags) {
+        Ok(signature) => signature,
+        Err(error) => {
+            eprintln!(
+                "SSH agent could not sign with \"{}\": {error}",
+                identity.title
+            );
+            return None;
+        }
+    };
+    let mut response = vec![SSH_AGENT_SIGN_RESPONSE];
+    put_string(&mut response, &signature);
+    Some(response)
+}
+
+fn take_u32(reader: &mut &[u8]) -> Option<u32> {
+    let (bytes, rest) = (reader.len() >= 4).then(|| reader.split_at(4))?;
+    *reader = rest;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn take_string<'a>(reader: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let length = take_u32(reader)? as usize;
+    let (value, rest) = (reader.len() >= length).then(|| reader.split_at(length))?;
+    *reader = rest;
+    Some(value)
+}
+
+fn read_message(stream: &mut UnixStream) -> io::Result<Option<Vec<u8>>> {
+    let mut length = [0u8; 4];
+    match stream.read_exact(&mut length) {
+        Ok(()) => {}
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(error) => return Err(error),
+    }
+
+    let length = u32::from_be_bytes(length) as usize;
+    if length == 0 || length > MAX_MESSAGE_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Invalid agent message length {length}"),
+        ));
+    }
+    let mut message = vec![0u8; length];
+    stream.read_exact(&mut message)?;
+    Ok(Some(message))
+}
+
+fn write_message(stream: &mut UnixStream, message: &[u8]) -> io::Result<()> {
+    let mut framed = Vec::with_capacity(message.len() + 4);
+    put_string(&mut framed, message);
+    stream.write_all(&framed)?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        respond, take_string, take_u32, SSH_AGENTC_REQUEST_IDENTITIES, SSH_AGENTC_SIGN_REQUEST,
+        SSH_AGENT_FAILURE, SSH_AGENT_IDENTITIES_ANSWER, SSH_AGENT_SIGN_RESPONSE,
+    };
+    use crate::{
+        bridge::vault::VaultData,
+        ssh::keys::{load_identities, put_string},
+    };
+
+    fn identities() -> Vec<crate::ssh::keys::AgentIdentity> {
+        let vault = serde_json::from_value::<VaultData>(serde_json::json!({
+            "values": [{
+                "id": "entry-ed25519",
+                "title": "Build server",
+                "modules": [{ "module": "KEY", "value": include_str!("fixtures/ed25519") }]
+            }]
+        }))
+        .unwrap();
+        load_identities(&vault)
+    }
+
+    fn sign_request(key_blob: &[u8]) -> Vec<u8> {
+        let mut message = vec![SSH_AGENTC_SIGN_REQUEST];
+        put_string(&mut message, key_blob);
+        put_string(&mut message, b"challenge");
+        message.extend_from_slice(&0u32.to_be_bytes());
+        message
+    }
+
+    #[test]
+    fn lists_identities_with_entry_titles() {
+        let identities = identities();
+        let response = respond(&[SSH_AGENTC_REQUEST_IDENTITIES], &identities, |_| true);
+
+        let mut reader = &response[1..];
+        assert_eq!(response[0], SSH_AGENT_IDENTITIES_ANSWER);
+        assert_eq!(take_u32(&mut reader), Some(1));
+        assert_eq!(
+            take_string(&mut reader).unwrap(),
+            identities[0].public_blob().unwrap()
+        );
+        assert_eq!(take_string(&mut reader), Some(&b"Build server"[..]));
+
+        let locked = respond(&[SSH_AGENTC_REQUEST_IDENTITIES], &[], |_| true);
+        assert_eq!(locked, [SSH_AGENT_IDENTITIES_ANSWER, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn signs_only_known_and_confirmed_keys() {
+        let identities = identities();
+        let blob = identities[0].public_blob().unwrap();
+
+        let response = respond(&sign_request(&blob), &identities, |identity| {
+            identity.entry_id == "entry-ed25519"
+        });
+        assert_eq!(response[0], SSH_AGENT_SIGN_RESPONSE);
+        let mut reader = &response[1..];
+        let mut signature = take_string(&mut reader).unwrap();
+        assert_eq!(take_string(&mut signature), Some(&b"ssh-ed25519"[..]));
+
+        assert_eq!(
+            respond(&sign_request(&blob), &identities, |_| false),
+            [SSH_AGENT_FAILURE]
+        );
+        assert_eq!(
+            respond(&sign_request(b"unknown"), &identities, |_| true),
+            [SSH_AGENT_FAILURE]
+        );
+        assert_eq!(respond(&[17], &identities, |_| true), [SSH_AGENT_FAILURE]);
+    }
+}
@@ -0,0 +1,181 @@
+use serde::Serialize;
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, OnceLock},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+pub const CONFIRM_TIMEOUT_MS: u64 = 60_000;
+
+/// A signature waiting for the user in the desktop app.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingSignature {
+    pub id: String,
+    pub entry_id: String,
+    pub title: String,
+    pub fingerprint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exe: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command_line: Option<String>,
+    pub requested_at_ms: u64,
+}
+
+/// The request a signature is confirmed for; the book assigns id and time.
+#[derive(Debug, Clone, Default)]
+pub struct SignatureRequest {
+    pub entry_id: String,
+    pub title: String,
+    pub fingerprint: String,
+    pub pid: Option<u32>,
+    pub exe: Option<String>,
+    pub command_line: Option<String>,
+}
+
+/// Per-use confirmations of agent signatures. Every request is answered at
+/// most once; locking rejects whatever is still open.
+#[derive(Default)]
+pub struct ConfirmationBook {
+    state: Mutex<ConfirmationState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct ConfirmationState {
+    pending: Vec<PendingSignature>,
+    decided: Vec<(String, bool)>,
+    next_id: u64,
+}
+
+impl ConfirmationBook {
+    /// Queues `request` and blocks until the user decides, everything is
+    /// rejected or `timeout` passes. Only an explicit approval returns `true`.
+    pub fn confirm(&self, request: SignatureRequest, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+
+        state.next_id += 1;
+        let id = format!("signature-{}", state.next_id);
+        state.pending.push(PendingSignature {
+            id: id.clone(),
+            entry_id: request.entry_id,
+            title: request.title,
+            fingerprint: request.fingerprint,
+            pid: request.pid,
+            exe: request.exe,
+            command_line: request.command_line,
+            requested_at_ms: now_ms(),
+        });
+
+        loop {
+            if let Some(index) = state.decided.iter().position(|(decided, _)| *decided == id) {
+                return state.decided.remove(index).1;
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                state.pending.retain(|pending| pending.id != id);
+                return false;
+            }
+            state = self
+                .changed
+                .wait_timeout(state, deadline - now)
+                .map(|(state, _)| state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner().0);
+        }
+    }
+
+    pub fn approve(&self, id: &str) -> bool {
+        self.decide(id, true)
+    }
+
+    pub fn reject(&self, id: &str) -> bool {
+        self.decide(id, false)
+    }
+
+    pub fn reject_all(&self) {
+        let mut state = self.lock();
+        let pending = std::mem::take(&mut state.pending);
+        state
+            .decided
+            .extend(pending.into_iter().map(|pending| (pending.id, false)));
+        self.changed.notify_all();
+    }
+
+    pub fn list_pending(&self) -> Vec<PendingSignature> {
+        self.lock().pending.clone()
+    }
+
+    fn decide(&self, id: &str, approved: bool) -> bool {
+        let mut state = self.lock();
+        let Some(index) = state.pending.iter().position(|pending| pending.id == id) else {
+            return false;
+        };
+        let pending = state.pending.remove(index);
+        state.decided.push((pending.id, approved));
+        self.changed.notify_all();
+        true
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ConfirmationState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The confirmation book of this app instance.
+pub fn confirmations() -> &'static ConfirmationBook {
+    static CONFIRMATIONS: OnceLock<ConfirmationBook> = OnceLock::new();
+    CONFIRMATIONS.get_or_init(ConfirmationBook::default)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfirmationBook, SignatureRequest};
+    use std::{sync::Arc, thread, time::Duration};
+
+    fn confirm_in_background(book: &Arc<ConfirmationBook>) -> thread::JoinHandle<bool> {
+        let book = Arc::clone(book);
+        thread::spawn(move || book.confirm(SignatureRequest::default(), Duration::from_secs(5)))
+    }
+
+    fn first_pending(book: &ConfirmationBook) -> String {
+        loop {
+            if let Some(pending) = book.list_pending().first() {
+                return pending.id.clone();
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn each_signature_needs_its_own_approval() {
+        let book = Arc::new(ConfirmationBook::default());
+
+        let waiter = confirm_in_background(&book);
+        assert!(book.approve(&first_pending(&book)));
+        assert!(waiter.join().unwrap());
+
+        let waiter = confirm_in_background(&book);
+        assert!(book.reject(&first_pending(&book)));
+        assert!(!waiter.join().unwrap());
+
+        let waiter = confirm_in_background(&book);
+        first_pending(&book);
+        book.reject_all();
+        assert!(!waiter.join().unwrap());
+        assert!(book.list_pending().is_empty());
+
+        assert!(!book.confirm(SignatureRequest::default(), Duration::from_millis(10)));
+    }
+}
@@ -0,0 +1,169 @@
+use chacha20poly1305::aead::OsRng;
+use serde::{Deserialize, Serialize};
+use ssh_key::{
+    private::{Ed25519Keypair, KeypairData, RsaKeypair},
+    HashAlg, LineEnding, PrivateKey,
+};
+use std::io;
+use zeroize::Zeroizing;
+
+use crate::vault::model::{Module, ValueModule};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SshKeyAlgorithm {
+    #[serde(rename = "ed25519")]
+    Ed25519,
+    #[serde(rename = "rsa-3072")]
+    Rsa3072,
+    #[serde(rename = "rsa-4096")]
+    Rsa4096,
+}
+
+/// A freshly generated key pair. `private_key` is what goes into the `KEY`
+/// module, `public_key` is the `authorized_keys` line.
+pub struct GeneratedKeyPair {
+    pub private_key: Zeroizing<String>,
+    pub public_key: String,
+    pub fingerprint: String,
+}
+
+/// A `KEY` module holding a new private key, returned to the app together
+/// with what is needed to provision servers.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedSshKey {
+    pub module: Module,
+    pub public_key: String,
+    pub fingerprint: String,
+}
+
+/// The public half of a stored private key, shown next to the `KEY` module.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SshPublicKeyInfo {
+    pub public_key: String,
+    pub fingerprint: String,
+}
+
+pub fn generate_key_module(
+    module_id: &str,
+    algorithm: SshKeyAlgorithm,
+    comment: &str,
+    passphrase: Option<&str>,
+) -> io::Result<GeneratedSshKey> {
+    let generated = generate_key_pair(algorithm, comment, passphrase)?;
+    Ok(GeneratedSshKey {
+        module: Module::Key(ValueModule {
+            id: module_id.to_string(),
+            value: generated.private_key.to_string(),
+            extra: Default::default(),
+        }),
+        public_key: generated.public_key,
+        fingerprint: generated.fingerprint,
+    })
+}
+
+/// Generates a key pair in OpenSSH format, encrypted with `passphrase` when
+/// one is given.
+pub fn generate_key_pair(
+    algorithm: SshKeyAlgorithm,
+    comment: &str,
+    passphrase: Option<&str>,
+) -> io::Result<GeneratedKeyPair> {
+    let key_data = match algorithm {
+        SshKeyAlgorithm::Ed25519 => KeypairData::Ed25519(Ed25519Keypair::random(&mut OsRng)),
+        SshKeyAlgorithm::Rsa3072 => generate_rsa(3072)?,
+        SshKeyAlgorithm::Rsa4096 => generate_rsa(4096)?,
+    };
+
+    let mut key = PrivateKey::new(key_data, comment).map_err(invalid)?;
+    let public_key = key.public_key().to_openssh().map_err(invalid)?;
+    let fingerprint = key.fingerprint(HashAlg::Sha256).to_string();
+
+    if let Some(passphrase) = passphrase.filter(|passphrase| !passphrase.is_empty()) {
+        key = key.encrypt(&mut OsRng, passphrase).map_err(invalid)?;
+    }
+
+    Ok(GeneratedKeyPair {
+        private_key: key.to_openssh(LineEnding::LF).map_err(invalid)?,
+        public_key,
+        fingerprint,
+    })
+}
+
+/// Reads the public key of an OpenSSH private key. Encrypted keys keep it in
+/// the clear, so no passphrase is needed. Returns `None` for other values.
+pub fn describe_private_key(private_key: &str) -> Option<SshPublicKeyInfo> {
+    let key = PrivateKey::from_openssh(private_key.trim()).ok()?;
+    Some(SshPublicKeyInfo {
+        public_key: key.public_key().to_openssh().ok()?,
+        fingerprint: key.fingerprint(HashAlg::Sha256).to_string(),
+    })
+}
+
+fn generate_rsa(bits: usize) -> io::Result<KeypairData> {
+    let key = rsa::RsaPrivateKey::new(&mut OsRng, bits).map_err(invalid)?;
+    RsaKeypair::try_from(&key)
+        .map(KeypairData::Rsa)
+        .map_err(invalid)
+}
+
+fn invalid(err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{describe_private_key, generate_key_pair, SshKeyAlgorithm};
+    use crate::{bridge::vault::VaultData, ssh::keys::load_identities};
+    use ssh_key::{HashAlg, PrivateKey, PublicKey};
+
+    #[test]
+    fn generated_keys_round_trip_through_the_agent_identities() {
+        let generated =
+            generate_key_pair(SshKeyAlgorithm::Ed25519, "deploy@example", Some("secret")).unwrap();
+
+        assert!(generated.public_key.starts_with("ssh-ed25519 "));
+        assert!(generated.public_key.ends_with(" deploy@example"));
+        let public = PublicKey::from_openssh(&generated.public_key).unwrap();
+        assert_eq!(
+            public.fingerprint(HashAlg::Sha256).to_string(),
+            generated.fingerprint
+        );
+
+        let private = PrivateKey::from_openssh(generated.private_key.as_str()).unwrap();
+        assert!(private.is_encrypted());
+        assert_eq!(private.public_key().key_data(), public.key_data());
+
+        let described = describe_private_key(&generated.private_key).unwrap();
+        assert_eq!(described.fingerprint, generated.fingerprint);
+        assert!(described.public_key.starts_with("ssh-ed25519 "));
+        assert!(describe_private_key("not a key").is_none());
+
+        let vault = serde_json::from_value::<VaultData>(serde_json::json!({
+            "values": [{
+                "id": "entry",
+                "title": "Deploy",
+                "modules": [
+                    { "module": "KEY", "value": generated.private_key.as_str() },
+                    { "module": "PASSWORD", "value": "secret" }
+                ]
+            }]
+        }))
+        .unwrap();
+        let identities = load_identities(&vault);
+        assert_eq!(identities[0].fingerprint(), generated.fingerprint);
+        assert!(identities[0].sign(b"data", 0).is_ok());
+    }
+
+    #[test]
+    fn generates_rsa_keys_of_the_requested_size() {
+        let generated = generate_key_pair(SshKeyAlgorithm::Rsa3072, "", None).unwrap();
+        let private = PrivateKey::from_openssh(generated.private_key.as_str()).unwrap();
+
+        assert!(!private.is_encrypted());
+        assert!(generated.public_key.starts_with("ssh-rsa "));
+        let rsa = private.key_data().rsa().unwrap();
+        assert_eq!(rsa.public.n.as_positive_bytes().unwrap().len() * 8, 3072);
+    }
+}
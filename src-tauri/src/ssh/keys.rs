@@ -0,0 +1,233 @@
+use rsa::{
+    pkcs1v15::SigningKey,
+    sha2::{Sha256, Sha512},
+    signature::{SignatureEncoding, Signer},
+    BigUint, RsaPrivateKey,
+};
+use ssh_key::{
+    private::{KeypairData, RsaKeypair},
+    Algorithm, HashAlg, Mpint, PrivateKey, Signature,
+};
+use std::io;
+use zeroize::Zeroizing;
+
+use crate::bridge::vault::{first_string, VaultData};
+
+/// Sign request flags of the agent protocol selecting the RSA hash.
+pub const SSH_AGENT_RSA_SHA2_256: u32 = 2;
+pub const SSH_AGENT_RSA_SHA2_512: u32 = 4;
+
+/// A private key from a `KEY` module, named after its entry.
+pub struct AgentIdentity {
+    pub entry_id: String,
+    pub title: String,
+    pub key: PrivateKey,
+    /// The entry's password, used to decrypt a passphrase-protected key.
+    passphrase: Option<Zeroizing<String>>,
+}
+
+impl AgentIdentity {
+    /// The public key in SSH wire format, as listed to and sent by clients.
+    pub fn public_blob(&self) -> io::Result<Vec<u8>> {
+        self.key.public_key().to_bytes().map_err(invalid)
+    }
+
+    pub fn fingerprint(&self) -> String {
+        self.key.fingerprint(HashAlg::Sha256).to_string()
+    }
+
+    /// Signs `data` and returns the signature blob of the agent protocol.
+    pub fn sign(&self, data: &[u8], flags: u32) -> io::Result<Vec<u8>> {
+        let decrypted;
+        let key = if self.key.is_encrypted() {
+            let passphrase = self.passphrase.as_deref().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("\"{}\" has no password to unlock its key", self.title),
+                )
+            })?;
+            decrypted = self.key.decrypt(passphrase).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("The password of \"{}\" does not unlock its key", self.title),
+                )
+            })?;
+            &decrypted
+        } else {
+            &self.key
+        };
+
+        let signature = match key.key_data() {
+            KeypairData::Rsa(keypair) if flags & SSH_AGENT_RSA_SHA2_512 != 0 => {
+                let signed = SigningKey::<Sha512>::new(rsa_private_key(keypair)?)
+                    .try_sign(data)
+                    .map_err(invalid)?;
+                rsa_signature(HashAlg::Sha512, signed.to_vec())?
+            }
+            KeypairData::Rsa(keypair) if flags & SSH_AGENT_RSA_SHA2_256 != 0 => {
+                let signed = SigningKey::<Sha256>::new(rsa_private_key(keypair)?)
+                    .try_sign(data)
+                    .map_err(invalid)?;
+                rsa_signature(HashAlg::Sha256, signed.to_vec())?
+            }
+            // Plain `ssh-rsa` would mean SHA-1, which is not offered.
+            KeypairData::Rsa(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "SHA-1 RSA signatures are not supported",
+                ))
+            }
+            _ => Signer::<Signature>::try_sign(key, data).map_err(invalid)?,
+        };
+
+        let mut blob = Vec::new();
+        put_string(&mut blob, signature.algorithm().as_str().as_bytes());
+        put_string(&mut blob, signature.as_bytes());
+        Ok(blob)
+    }
+}
+
+/// Collects the OpenSSH private keys stored in `KEY` modules. Values that
+/// are not private keys, such as public keys or notes, are skipped.
+pub fn load_identities(vault: &VaultData) -> Vec<AgentIdentity> {
+    let mut identities = Vec::new();
+
+    for entry in vault
+        .values
+        .iter()
+        .filter(|entry| entry.deleted_at.is_none())
+    {
+        let passphrase = first_string(entry, "PASSWORD").map(Zeroizing::new);
+        for module in entry.modules.iter().filter(|module| module.module == "KEY") {
+            let Some(pem) = module.value.as_ref().and_then(|value| value.as_str()) else {
+                continue;
+            };
+            if let Ok(key) = PrivateKey::from_openssh(pem.trim()) {
+                identities.push(AgentIdentity {
+                    entry_id: entry.id.clone(),
+                    title: entry.title.clone(),
+                    key,
+                    passphrase: passphrase.clone(),
+                });
+            }
+        }
+    }
+
+    identities
+}
+
+/// Builds the `rsa` key from its components. The conversion ssh-key offers
+/// passes `p` twice instead of `p` and `q` and rejects every key.
+fn rsa_private_key(keypair: &RsaKeypair) -> io::Result<RsaPrivateKey> {
+    let uint = |value: &Mpint| {
+        value
+            .as_positive_bytes()
+            .map(BigUint::from_bytes_be)
+            .ok_or_else(|| invalid("Invalid RSA key component"))
+    };
+    RsaPrivateKey::from_components(
+        uint(&keypair.public.n)?,
+        uint(&keypair.public.e)?,
+        uint(&keypair.private.d)?,
+        vec![uint(&keypair.private.p)?, uint(&keypair.private.q)?],
+    )
+    .map_err(invalid)
+}
+
+fn rsa_signature(hash: HashAlg, data: Vec<u8>) -> io::Result<Signature> {
+    Signature::new(Algorithm::Rsa { hash: Some(hash) }, data).map_err(invalid)
+}
+
+/// Appends an SSH `string`: a big-endian length followed by the bytes.
+pub(crate) fn put_string(buffer: &mut Vec<u8>, value: &[u8]) {
+    buffer.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    buffer.extend_from_slice(value);
+}
+
+fn invalid(err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_identities, SSH_AGENT_RSA_SHA2_256, SSH_AGENT_RSA_SHA2_512};
+    use crate::bridge::vault::VaultData;
+    use rsa::signature::Verifier;
+    use ssh_key::{PublicKey, Signature};
+
+    fn vault_with_keys() -> VaultData {
+        serde_json::from_value(serde_json::json!({
+            "values": [
+                {
+                    "id": "entry-ed25519",
+                    "title": "Build server",
+                    "modules": [
+                        { "module": "KEY", "value": include_str!("fixtures/ed25519") },
+                        { "module": "KEY", "value": include_str!("fixtures/ed25519.pub") }
+                    ]
+                },
+                {
+                    "id": "entry-protected",
+                    "title": "Production",
+                    "modules": [
+                        { "module": "KEY", "value": include_str!("fixtures/ed25519-protected") },
+                        { "module": "PASSWORD", "value": "fixture passphrase" }
+                    ]
+                },
+                {
+                    "id": "entry-rsa",
+                    "title": "Legacy box",
+                    "modules": [{ "module": "KEY", "value": include_str!("fixtures/rsa") }]
+                },
+                {
+                    "id": "entry-trashed",
+                    "title": "Old",
+                    "deletedAt": "2024-01-01T00:00:00.000Z",
+                    "modules": [{ "module": "KEY", "value": include_str!("fixtures/ed25519") }]
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn verify(public: &str, data: &[u8], blob: &[u8]) {
+        let public = PublicKey::from_openssh(public).unwrap();
+        let signature = Signature::try_from(blob).unwrap();
+        Verifier::verify(&public, data, &signature).unwrap();
+    }
+
+    #[test]
+    fn lists_private_keys_of_live_entries() {
+        let identities = load_identities(&vault_with_keys());
+        let titles = identities
+            .iter()
+            .map(|identity| identity.title.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(titles, ["Build server", "Production", "Legacy box"]);
+        let expected = PublicKey::from_openssh(include_str!("fixtures/ed25519.pub")).unwrap();
+        assert_eq!(
+            identities[0].public_blob().unwrap(),
+            expected.to_bytes().unwrap()
+        );
+        assert!(identities[0].fingerprint().starts_with("SHA256:"));
+    }
+
+    #[test]
+    fn signs_with_plain_protected_and_rsa_keys() {
+        let identities = load_identities(&vault_with_keys());
+        let data = b"session data";
+
+        let blob = identities[0].sign(data, 0).unwrap();
+        verify(include_str!("fixtures/ed25519.pub"), data, &blob);
+
+        let blob = identities[1].sign(data, 0).unwrap();
+        verify(include_str!("fixtures/ed25519-protected.pub"), data, &blob);
+
+        for flags in [SSH_AGENT_RSA_SHA2_256, SSH_AGENT_RSA_SHA2_512] {
+            let blob = identities[2].sign(data, flags).unwrap();
+            verify(include_str!("fixtures/rsa.pub"), data, &blob);
+        }
+        assert!(identities[2].sign(data, 0).is_err());
+    }
+}
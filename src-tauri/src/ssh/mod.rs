@@ -0,0 +1,9 @@
+//! SSH agent serving the private keys stored in `KEY` modules while the
+//! vault is unlocked.
+
+#[cfg(unix)]
+pub mod agent;
+pub mod confirm;
+pub mod keygen;
+pub mod keys;
+pub mod settings;
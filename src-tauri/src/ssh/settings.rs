@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+use crate::bridge::path::ssh_agent_settings_path;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SshAgentSettings {
+    /// Ask in the desktop app before every signature.
+    #[serde(default = "default_confirm_each_use")]
+    pub confirm_each_use: bool,
+}
+
+impl Default for SshAgentSettings {
+    fn default() -> Self {
+        Self {
+            confirm_each_use: default_confirm_each_use(),
+        }
+    }
+}
+
+fn default_confirm_each_use() -> bool {
+    true
+}
+
+pub fn load_settings() -> io::Result<SshAgentSettings> {
+    let path = ssh_agent_settings_path()?;
+    if !path.exists() {
+        return Ok(SshAgentSettings::default());
+    }
+
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str::<SshAgentSettings>(&content)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+pub fn save_settings(settings: &SshAgentSettings) -> io::Result<()> {
+    let path = ssh_agent_settings_path()?;
+    write_json_atomically(&path, settings)
+}
+
+fn write_json_atomically<T: Serialize>(path: &PathBuf, value: &T) -> io::Result<()> {
+    let temp = path.with_extension("tmp");
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    fs::write(&temp, json)?;
+    fs::rename(temp, path)?;
+    Ok(())
+}
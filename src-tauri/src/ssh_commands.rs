@@ -0,0 +1,64 @@
+use crate::ssh::{
+    confirm::{confirmations, PendingSignature},
+    keygen::{self, GeneratedSshKey, SshKeyAlgorithm, SshPublicKeyInfo},
+    settings::{self, SshAgentSettings},
+};
+
+// RSA key generation takes seconds, so it runs off the main thread.
+#[tauri::command]
+pub async fn ssh_generate_key(
+    module_id: String,
+    algorithm: SshKeyAlgorithm,
+    comment: Option<String>,
+    passphrase: Option<String>,
+) -> Result<GeneratedSshKey, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        keygen::generate_key_module(
+            &module_id,
+            algorithm,
+            comment.as_deref().unwrap_or_default(),
+            passphrase.as_deref(),
+        )
+    })
+    .await
+    .map_err(|err| format!("Failed to generate SSH key: {err}"))?
+    .map_err(|err| format!("Failed to generate SSH key: {err}"))
+}
+
+#[tauri::command]
+pub fn ssh_describe_key(value: String) -> Option<SshPublicKeyInfo> {
+    keygen::describe_private_key(&value)
+}
+
+#[tauri::command]
+pub fn ssh_agent_socket_path() -> Result<String, String> {
+    crate::bridge::path::ssh_agent_socket_path()
+        .map(|path| path.to_string_lossy().into_owned())
+        .map_err(|err| format!("Failed to resolve SSH agent socket: {err}"))
+}
+
+#[tauri::command]
+pub fn ssh_agent_get_settings() -> Result<SshAgentSettings, String> {
+    settings::load_settings().map_err(|err| format!("Failed to load SSH agent settings: {err}"))
+}
+
+#[tauri::command]
+pub fn ssh_agent_set_settings(settings: SshAgentSettings) -> Result<(), String> {
+    settings::save_settings(&settings)
+        .map_err(|err| format!("Failed to save SSH agent settings: {err}"))
+}
+
+#[tauri::command]
+pub fn ssh_agent_list_pending_signatures() -> Vec<PendingSignature> {
+    confirmations().list_pending()
+}
+
+#[tauri::command]
+pub fn ssh_agent_approve_signature(request_id: String) -> bool {
+    confirmations().approve(&request_id)
+}
+
+#[tauri::command]
+pub fn ssh_agent_reject_signature(request_id: String) -> bool {
+    confirmations().reject(&request_id)
+}
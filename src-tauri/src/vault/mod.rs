@@ -0,0 +1,3 @@
+pub mod model;
+pub mod totp;
+pub mod v1;
@@ -0,0 +1,806 @@
+//! Typed mirror of the frontend vault model (`src/features/vault/model`).
+//!
+//! Every struct keeps fields it does not know in `extra`, and modules with an
+//! unknown `module` tag (or a shape that does not match their schema) are kept
+//! as [`UnknownModule`], so a vault written by a newer app survives a round
+//! trip through this model unchanged.
+
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultData {
+    #[serde(default = "default_version")]
+    pub version: String,
+    #[serde(default)]
+    pub folder: Vec<Folder>,
+    #[serde(default)]
+    pub values: Vec<Entry>,
+    #[serde(default)]
+    pub devices: Vec<VaultDevice>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for VaultData {
+    fn default() -> Self {
+        Self {
+            version: default_version(),
+            folder: Vec::new(),
+            values: Vec::new(),
+            devices: Vec::new(),
+            extra: Map::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Folder {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDevice {
+    pub id: String,
+    pub name: String,
+    pub platform: String,
+    pub first_seen_at: String,
+    pub last_seen_at: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entry {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub modules: Vec<Module>,
+    #[serde(default)]
+    pub fav: bool,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pinned_at: Option<Option<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    pub created: String,
+    pub last_updated: String,
+    #[serde(default)]
+    pub folder: Option<Folder>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_cross_origin_fill: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_history: Option<Vec<PasswordHistoryItem>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deleted_at: Option<Option<String>>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Entry {
+    pub fn find_module(&self, kind: ModuleKind) -> Option<&Module> {
+        self.modules
+            .iter()
+            .find(|module| module.kind() == Some(kind))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordHistoryItem {
+    pub password: String,
+    pub replaced_at_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The `module` tags known to `ModulesEnum`, except `UNKNOWN`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModuleKind {
+    Url,
+    Username,
+    EMail,
+    Password,
+    Totp,
+    Wifi,
+    CreditCard,
+    Address,
+    Person,
+    PhoneNumber,
+    Document,
+    Attachment,
+    RecoveryCodes,
+    CustomField,
+    Key,
+    DigitalCard,
+    Task,
+    Pin,
+    Expiry,
+    Note,
+    Company,
+    Title,
+}
+
+impl ModuleKind {
+    pub const ALL: [ModuleKind; 22] = [
+        Self::Url,
+        Self::Username,
+        Self::EMail,
+        Self::Password,
+        Self::Totp,
+        Self::Wifi,
+        Self::CreditCard,
+        Self::Address,
+        Self::Person,
+        Self::PhoneNumber,
+        Self::Document,
+        Self::Attachment,
+        Self::RecoveryCodes,
+        Self::CustomField,
+        Self::Key,
+        Self::DigitalCard,
+        Self::Task,
+        Self::Pin,
+        Self::Expiry,
+        Self::Note,
+        Self::Company,
+        Self::Title,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Url => "URL",
+            Self::Username => "USERNAME",
+            Self::EMail => "E_MAIL",
+            Self::Password => "PASSWORD",
+            Self::Totp => "TOTP",
+            Self::Wifi => "WIFI",
+            Self::CreditCard => "CREDIT_CARD",
+            Self::Address => "ADDRESS",
+            Self::Person => "PERSON",
+            Self::PhoneNumber => "PHONE_NUMBER",
+            Self::Document => "DOCUMENT",
+            Self::Attachment => "ATTACHMENT",
+            Self::RecoveryCodes => "RECOVERY_CODES",
+            Self::CustomField => "CUSTOM_FIELD",
+            Self::Key => "KEY",
+            Self::DigitalCard => "DIGITAL_CARD",
+            Self::Task => "TASK",
+            Self::Pin => "PIN",
+            Self::Expiry => "EXPIRY",
+            Self::Note => "NOTE",
+            Self::Company => "COMPANY",
+            Self::Title => "TITLE",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == tag)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Module {
+    Url(ValueModule),
+    Username(ValueModule),
+    EMail(ValueModule),
+    Password(ValueModule),
+    Totp(ValueModule),
+    Wifi(WifiModule),
+    CreditCard(CreditCardModule),
+    Address(AddressModule),
+    Person(PersonModule),
+    PhoneNumber(ValueModule),
+    Document(DocumentModule),
+    Attachment(AttachmentModule),
+    RecoveryCodes(RecoveryCodesModule),
+    CustomField(CustomFieldModule),
+    Key(ValueModule),
+    DigitalCard(DigitalCardModule),
+    Task(TaskModule),
+    Pin(ValueModule),
+    Expiry(ExpiryModule),
+    Note(NoteModule),
+    Company(CompanyModule),
+    Title(ValueModule),
+    Unknown(UnknownModule),
+}
+
+impl Module {
+    /// `None` for modules kept as [`UnknownModule`].
+    pub fn kind(&self) -> Option<ModuleKind> {
+        Some(match self {
+            Self::Url(_) => ModuleKind::Url,
+            Self::Username(_) => ModuleKind::Username,
+            Self::EMail(_) => ModuleKind::EMail,
+            Self::Password(_) => ModuleKind::Password,
+            Self::Totp(_) => ModuleKind::Totp,
+            Self::Wifi(_) => ModuleKind::Wifi,
+            Self::CreditCard(_) => ModuleKind::CreditCard,
+            Self::Address(_) => ModuleKind::Address,
+            Self::Person(_) => ModuleKind::Person,
+            Self::PhoneNumber(_) => ModuleKind::PhoneNumber,
+            Self::Document(_) => ModuleKind::Document,
+            Self::Attachment(_) => ModuleKind::Attachment,
+            Self::RecoveryCodes(_) => ModuleKind::RecoveryCodes,
+            Self::CustomField(_) => ModuleKind::CustomField,
+            Self::Key(_) => ModuleKind::Key,
+            Self::DigitalCard(_) => ModuleKind::DigitalCard,
+            Self::Task(_) => ModuleKind::Task,
+            Self::Pin(_) => ModuleKind::Pin,
+            Self::Expiry(_) => ModuleKind::Expiry,
+            Self::Note(_) => ModuleKind::Note,
+            Self::Company(_) => ModuleKind::Company,
+            Self::Title(_) => ModuleKind::Title,
+            Self::Unknown(_) => return None,
+        })
+    }
+
+    /// The raw `module` tag, also for unknown modules.
+    pub fn tag(&self) -> &str {
+        match self {
+            Self::Unknown(module) => &module.module,
+            other => other.kind().map(ModuleKind::as_str).unwrap_or_default(),
+        }
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Self::Url(module)
+            | Self::Username(module)
+            | Self::EMail(module)
+            | Self::Password(module)
+            | Self::Totp(module)
+            | Self::PhoneNumber(module)
+            | Self::Key(module)
+            | Self::Pin(module)
+            | Self::Title(module) => Some(&module.id),
+            Self::Wifi(module) => Some(&module.id),
+            Self::CreditCard(module) => Some(&module.id),
+            Self::Address(module) => Some(&module.id),
+            Self::Person(module) => Some(&module.id),
+            Self::Document(module) => Some(&module.id),
+            Self::Attachment(module) => Some(&module.id),
+            Self::RecoveryCodes(module) => Some(&module.id),
+            Self::CustomField(module) => Some(&module.id),
+            Self::DigitalCard(module) => Some(&module.id),
+            Self::Task(module) => Some(&module.id),
+            Self::Expiry(module) => Some(&module.id),
+            Self::Note(module) => Some(&module.id),
+            Self::Company(module) => Some(&module.id),
+            Self::Unknown(module) => module.fields.get("id").and_then(Value::as_str),
+        }
+    }
+
+    /// The single `value` of simple modules (URL, PASSWORD, NOTE, ...).
+    pub fn value(&self) -> Option<&str> {
+        match self {
+            Self::Url(module)
+            | Self::Username(module)
+            | Self::EMail(module)
+            | Self::Password(module)
+            | Self::Totp(module)
+            | Self::PhoneNumber(module)
+            | Self::Key(module)
+            | Self::Pin(module)
+            | Self::Title(module) => Some(&module.value),
+            Self::Wifi(module) => Some(&module.value),
+            Self::CustomField(module) => Some(&module.value),
+            Self::DigitalCard(module) => Some(&module.value),
+            Self::Task(module) => Some(&module.value),
+            Self::Expiry(module) => Some(&module.value),
+            Self::Note(module) => Some(&module.value),
+            _ => None,
+        }
+    }
+
+    fn from_map(mut fields: Map<String, Value>) -> Result<Self, String> {
+        let tag = match fields.get("module") {
+            Some(Value::String(tag)) => tag.clone(),
+            _ => return Err("module tag is missing".to_string()),
+        };
+        let Some(kind) = ModuleKind::from_tag(&tag) else {
+            return Ok(Self::Unknown(UnknownModule {
+                module: tag,
+                fields,
+            }));
+        };
+
+        fields.remove("module");
+        let body = Value::Object(fields);
+        let parsed = match kind {
+            ModuleKind::Url => serde_json::from_value(body.clone()).map(Self::Url),
+            ModuleKind::Username => serde_json::from_value(body.clone()).map(Self::Username),
+            ModuleKind::EMail => serde_json::from_value(body.clone()).map(Self::EMail),
+            ModuleKind::Password => serde_json::from_value(body.clone()).map(Self::Password),
+            ModuleKind::Totp => serde_json::from_value(body.clone()).map(Self::Totp),
+            ModuleKind::Wifi => serde_json::from_value(body.clone()).map(Self::Wifi),
+            ModuleKind::CreditCard => serde_json::from_value(body.clone()).map(Self::CreditCard),
+            ModuleKind::Address => serde_json::from_value(body.clone()).map(Self::Address),
+            ModuleKind::Person => serde_json::from_value(body.clone()).map(Self::Person),
+            ModuleKind::PhoneNumber => serde_json::from_value(body.clone()).map(Self::PhoneNumber),
+            ModuleKind::Document => serde_json::from_value(body.clone()).map(Self::Document),
+            ModuleKind::Attachment => serde_json::from_value(body.clone()).map(Self::Attachment),
+            ModuleKind::RecoveryCodes => {
+                serde_json::from_value(body.clone()).map(Self::RecoveryCodes)
+            }
+            ModuleKind::CustomField => serde_json::from_value(body.clone()).map(Self::CustomField),
+            ModuleKind::Key => serde_json::from_value(body.clone()).map(Self::Key),
+            ModuleKind::DigitalCard => serde_json::from_value(body.clone()).map(Self::DigitalCard),
+            ModuleKind::Task => serde_json::from_value(body.clone()).map(Self::Task),
+            ModuleKind::Pin => serde_json::from_value(body.clone()).map(Self::Pin),
+            ModuleKind::Expiry => serde_json::from_value(body.clone()).map(Self::Expiry),
+            ModuleKind::Note => serde_json::from_value(body.clone()).map(Self::Note),
+            ModuleKind::Company => serde_json::from_value(body.clone()).map(Self::Company),
+            ModuleKind::Title => serde_json::from_value(body.clone()).map(Self::Title),
+        };
+
+        // A known tag with an unexpected shape is kept verbatim rather than rejected.
+        Ok(parsed.unwrap_or_else(|_| {
+            let Value::Object(mut fields) = body else {
+                unreachable!("module body is always an object")
+            };
+            fields.insert("module".to_string(), Value::String(tag.clone()));
+            Self::Unknown(UnknownModule {
+                module: tag,
+                fields,
+            })
+        }))
+    }
+
+    fn to_map(&self) -> Result<Map<String, Value>, String> {
+        let body = match self {
+            Self::Unknown(module) => return Ok(module.fields.clone()),
+            Self::Url(module)
+            | Self::Username(module)
+            | Self::EMail(module)
+            | Self::Password(module)
+            | Self::Totp(module)
+            | Self::PhoneNumber(module)
+            | Self::Key(module)
+            | Self::Pin(module)
+            | Self::Title(module) => serde_json::to_value(module),
+            Self::Wifi(module) => serde_json::to_value(module),
+            Self::CreditCard(module) => serde_json::to_value(module),
+            Self::Address(module) => serde_json::to_value(module),
+            Self::Person(module) => serde_json::to_value(module),
+            Self::Document(module) => serde_json::to_value(module),
+            Self::Attachment(module) => serde_json::to_value(module),
+            Self::RecoveryCodes(module) => serde_json::to_value(module),
+            Self::CustomField(module) => serde_json::to_value(module),
+            Self::DigitalCard(module) => serde_json::to_value(module),
+            Self::Task(module) => serde_json::to_value(module),
+            Self::Expiry(module) => serde_json::to_value(module),
+            Self::Note(module) => serde_json::to_value(module),
+            Self::Company(module) => serde_json::to_value(module),
+        }
+        .map_err(|err| err.to_string())?;
+
+        let Value::Object(mut fields) = body else {
+            return Err("module must serialize to an object".to_string());
+        };
+        fields.insert("module".to_string(), Value::String(self.tag().to_string()));
+        Ok(fields)
+    }
+}
+
+impl Serialize for Module {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_map()
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Module {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = Map::<String, Value>::deserialize(deserializer)?;
+        Self::from_map(fields).map_err(de::Error::custom)
+    }
+}
+
+/// URL, USERNAME, E_MAIL, PASSWORD, TOTP, PHONE_NUMBER, KEY, PIN and TITLE.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueModule {
+    pub id: String,
+    pub value: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WifiType {
+    #[serde(rename = "WEP")]
+    Wep,
+    #[serde(rename = "WPA")]
+    Wpa,
+    #[serde(rename = "blank")]
+    Blank,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WifiModule {
+    pub id: String,
+    pub value: String,
+    pub wifi_name: String,
+    pub wifi_type: WifiType,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditCardModule {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cardholder_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brand: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry_month: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry_year: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressModule {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub street1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub street2: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonModule {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub middle_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanyModule {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub department: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_title: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentModule {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentModule {
+    pub id: String,
+    #[serde(default)]
+    pub files: Vec<AttachmentFile>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentFile {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    pub size: u64,
+    pub data_base64: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keepass_ref: Option<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryCodesModule {
+    pub id: String,
+    #[serde(default)]
+    pub codes: Vec<RecoveryCode>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryCode {
+    pub code: String,
+    #[serde(default)]
+    pub used: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomFieldInputType {
+    Text,
+    Secret,
+    Number,
+    Date,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFieldModule {
+    pub id: String,
+    pub title: String,
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_type: Option<CustomFieldInputType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field_name: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigitalCardModule {
+    pub id: String,
+    pub value: String,
+    /// Barcode format, one of `DIGITAL_CARD_TYPES` (e.g. `QR-Code`, `EAN13`).
+    #[serde(rename = "type")]
+    pub card_type: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskModule {
+    pub id: String,
+    pub value: String,
+    pub completed: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiryModule {
+    pub id: String,
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warn_before_ms: Option<u64>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteDisplayMode {
+    Compact,
+    Normal,
+    Large,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteVariant {
+    Plain,
+    Markdown,
+    Snippet,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteLanguage {
+    Text,
+    Json,
+    Yaml,
+    Env,
+    Shell,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteModule {
+    pub id: String,
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_mode: Option<NoteDisplayMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<NoteVariant>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<NoteLanguage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub show_line_numbers: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrap_lines: Option<bool>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A module this model does not know, or one whose fields did not match the
+/// expected schema. `fields` holds the complete original object, `module` included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownModule {
+    pub module: String,
+    pub fields: Map<String, Value>,
+}
+
+fn default_version() -> String {
+    "1".to_string()
+}
+
+/// Distinguishes an explicit `null` (`Some(None)`) from a missing field (`None`).
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Module, ModuleKind, VaultData, WifiType};
+    use serde_json::{json, Value};
+
+    const FULL_EXPORT: &str = include_str!("fixtures/full-export.json");
+    const LEGACY_EXPORT: &str = include_str!("fixtures/legacy-export.json");
+
+    fn round_trip(source: &str) -> (VaultData, Value) {
+        let original = serde_json::from_str::<Value>(source).unwrap();
+        let parsed = serde_json::from_str::<VaultData>(source).unwrap();
+        let written = serde_json::to_value(&parsed).unwrap();
+        assert_eq!(written, original);
+        (parsed, original)
+    }
+
+    #[test]
+    fn full_export_round_trips_and_types_every_module_kind() {
+        let (vault, _) = round_trip(FULL_EXPORT);
+
+        let kinds = vault
+            .values
+            .iter()
+            .flat_map(|entry| entry.modules.iter())
+            .filter_map(Module::kind)
+            .collect::<Vec<_>>();
+        for kind in ModuleKind::ALL {
+            assert!(
+                kinds.contains(&kind),
+                "fixture has no typed {kind:?} module"
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_modules_and_fields_survive_round_trips() {
+        let (vault, _) = round_trip(FULL_EXPORT);
+        let entry = vault
+            .values
+            .iter()
+            .find(|entry| entry.id == "entry-future")
+            .unwrap();
+
+        assert_eq!(entry.extra.get("syncRevision"), Some(&json!(7)));
+        assert!(matches!(&entry.modules[0], Module::Unknown(module) if module.module == "PASSKEY"));
+        assert_eq!(entry.modules[0].id(), Some("m-passkey"));
+        // PASSWORD without a value does not match the schema and is kept verbatim.
+        assert!(
+            matches!(&entry.modules[1], Module::Unknown(module) if module.module == "PASSWORD")
+        );
+        assert_eq!(
+            vault.extra.get("settings"),
+            Some(&json!({ "autoLockMs": 60000 }))
+        );
+    }
+
+    #[test]
+    fn typed_fields_are_readable() {
+        let (vault, _) = round_trip(FULL_EXPORT);
+        let login = &vault.values[0];
+
+        assert_eq!(
+            login
+                .find_module(ModuleKind::Password)
+                .and_then(Module::value),
+            Some("correct horse battery staple")
+        );
+        assert_eq!(login.pinned_at, Some(None));
+
+        let wifi = vault
+            .values
+            .iter()
+            .flat_map(|entry| entry.modules.iter())
+            .find_map(|module| match module {
+                Module::Wifi(wifi) => Some(wifi),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(wifi.wifi_type, WifiType::Wpa);
+        assert_eq!(wifi.wifi_name, "Home");
+    }
+
+    #[test]
+    fn legacy_export_round_trips() {
+        let (vault, _) = round_trip(LEGACY_EXPORT);
+        assert_eq!(vault.values.len(), 1);
+        assert!(vault.devices.is_empty());
+    }
+}
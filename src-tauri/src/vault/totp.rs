@@ -0,0 +1,240 @@
+//! TOTP codes for `otpauth://totp/...` URIs as stored in `TOTP` modules.
+//! Defaults follow `src/features/vault/utils/totp.ts`: SHA1, 6 digits, 30 s.
+
+use hmac::{digest::KeyInit, Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use std::{
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const URI_PREFIX: &str = "otpauth://totp/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TotpAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TotpParams {
+    pub label: String,
+    pub issuer: Option<String>,
+    pub secret: Vec<u8>,
+    pub algorithm: TotpAlgorithm,
+    pub digits: u32,
+    pub period: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TotpCode {
+    pub code: String,
+    /// Seconds until the code changes.
+    pub remaining: u64,
+    pub period: u64,
+}
+
+pub fn parse_otpauth(uri: &str) -> io::Result<TotpParams> {
+    let rest = uri
+        .trim()
+        .strip_prefix(URI_PREFIX)
+        .ok_or_else(|| invalid("Only otpauth://totp/ URIs are supported"))?;
+    let (label, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+    let mut params = TotpParams {
+        label: percent_decode(label),
+        issuer: None,
+        secret: Vec::new(),
+        algorithm: TotpAlgorithm::Sha1,
+        digits: 6,
+        period: 30,
+    };
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = percent_decode(value);
+        match key.to_ascii_lowercase().as_str() {
+            "secret" => params.secret = decode_base32(&value)?,
+            "issuer" => params.issuer = Some(value).filter(|issuer| !issuer.is_empty()),
+            "algorithm" => {
+                params.algorithm = match value.to_ascii_uppercase().as_str() {
+                    "SHA256" => TotpAlgorithm::Sha256,
+                    "SHA512" => TotpAlgorithm::Sha512,
+                    _ => TotpAlgorithm::Sha1,
+                }
+            }
+            "digits" => {
+                params.digits = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|digits| (6..=8).contains(digits))
+                    .ok_or_else(|| invalid("Invalid TOTP digits"))?
+            }
+            "period" => {
+                params.period = value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|period| *period > 0)
+                    .ok_or_else(|| invalid("Invalid TOTP period"))?
+            }
+            _ => {}
+        }
+    }
+
+    if params.secret.is_empty() {
+        return Err(invalid("TOTP secret is missing"));
+    }
+    Ok(params)
+}
+
+/// The code for `uri` at the current system time.
+pub fn current_code(uri: &str) -> io::Result<TotpCode> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    Ok(code_at(&parse_otpauth(uri)?, now))
+}
+
+pub fn code_at(params: &TotpParams, unix_seconds: u64) -> TotpCode {
+    let counter = (unix_seconds / params.period).to_be_bytes();
+    let digest = match params.algorithm {
+        TotpAlgorithm::Sha1 => hmac_digest::<Hmac<Sha1>>(&params.secret, &counter),
+        TotpAlgorithm::Sha256 => hmac_digest::<Hmac<Sha256>>(&params.secret, &counter),
+        TotpAlgorithm::Sha512 => hmac_digest::<Hmac<Sha512>>(&params.secret, &counter),
+    };
+
+    let offset = usize::from(digest[digest.len() - 1] & 0x0f);
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    let code = binary % 10u32.pow(params.digits);
+
+    TotpCode {
+        code: format!("{code:0width$}", width = params.digits as usize),
+        remaining: params.period - unix_seconds % params.period,
+        period: params.period,
+    }
+}
+
+fn hmac_digest<M: Mac + KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// RFC 4648 base32, case-insensitive, ignoring whitespace and padding.
+fn decode_base32(input: &str) -> io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0u32;
+
+    for char in input
+        .chars()
+        .filter(|char| !char.is_whitespace() && *char != '=')
+    {
+        let value = match char.to_ascii_uppercase() {
+            letter @ 'A'..='Z' => letter as u32 - 'A' as u32,
+            digit @ '2'..='7' => digit as u32 - '2' as u32 + 26,
+            _ => return Err(invalid("Invalid base32 TOTP secret")),
+        };
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Ok(output)
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let decoded = (bytes[index] == b'%')
+            .then(|| input.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match decoded {
+            Some(byte) => {
+                output.push(byte);
+                index += 3;
+            }
+            None => {
+                output.push(if bytes[index] == b'+' {
+                    b' '
+                } else {
+                    bytes[index]
+                });
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{code_at, parse_otpauth, TotpAlgorithm};
+
+    // RFC 6238 appendix B, T = 59 and T = 1111111109.
+    #[test]
+    fn matches_rfc_6238_vectors() {
+        let cases = [
+            ("SHA1", "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ", "94287082", "07081804"),
+            (
+                "SHA256",
+                "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA",
+                "46119246",
+                "68084774",
+            ),
+            (
+                "SHA512",
+                "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNA",
+                "90693936",
+                "25091201",
+            ),
+        ];
+
+        for (algorithm, secret, at_59, at_1111111109) in cases {
+            let uri = format!("otpauth://totp/RFC?secret={secret}&algorithm={algorithm}&digits=8");
+            let params = parse_otpauth(&uri).unwrap();
+            assert_eq!(code_at(&params, 59).code, at_59, "{algorithm}");
+            assert_eq!(
+                code_at(&params, 1_111_111_109).code,
+                at_1111111109,
+                "{algorithm}"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_app_uris_with_defaults() {
+        let params = parse_otpauth(
+            "otpauth://totp/GitHub:octocat%40example.com?secret=jbsw%20y3dpehpk3pxp&issuer=GitHub",
+        )
+        .unwrap();
+
+        assert_eq!(params.label, "GitHub:octocat@example.com");
+        assert_eq!(params.issuer.as_deref(), Some("GitHub"));
+        assert_eq!(params.algorithm, TotpAlgorithm::Sha1);
+        assert_eq!((params.digits, params.period), (6, 30));
+        assert_eq!(code_at(&params, 59).remaining, 1);
+        assert!(parse_otpauth("otpauth://hotp/x?secret=JBSWY3DP").is_err());
+    }
+}
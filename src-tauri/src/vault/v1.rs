@@ -0,0 +1,345 @@
+//! Native implementation of the `VaultV1` file format
+//! (`src/infrastructure/crypto/vault/v1`).
+//!
+//! The key is derived with argon2id (libsodium `crypto_pwhash` semantics:
+//! `opslimit` passes, `memlimit` bytes, one lane) and the vault JSON is sealed
+//! with XChaCha20-Poly1305 over the fixed V1 AAD.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, KeyInit, OsRng, Payload},
+    AeadCore, XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+use zeroize::Zeroizing;
+
+use super::model::VaultData;
+
+pub const VERSION: u32 = 1;
+pub const KDF_ALG: &str = "argon2id";
+pub const AEAD_ALG: &str = "xchacha20poly1305-ietf";
+
+pub const OPSLIMIT_DEFAULT: u64 = 3;
+pub const MEMLIMIT_DEFAULT: u64 = 64 * 1024 * 1024;
+
+pub const SALT_BYTES: usize = 16;
+pub const KEY_BYTES: usize = 32;
+pub const NONCE_BYTES: usize = 24;
+
+// libsodium's crypto_pwhash_argon2id_{OPSLIMIT,MEMLIMIT}_MIN.
+const OPSLIMIT_MIN: u64 = 1;
+const MEMLIMIT_MIN: u64 = 8192;
+// The app writes `MEMLIMIT_DEFAULT`. A file asking for far more is refused
+// before argon2 tries to allocate it.
+const MEMLIMIT_MAX: u64 = 1024 * 1024 * 1024;
+
+/// Must stay byte-identical to `makeV1AadBytes()` in `aad.ts`.
+pub const V1_AAD: &[u8] = br#"{"v":1,"kdf":"argon2id","aead":"xchacha20poly1305-ietf"}"#;
+
+/// The encrypted envelope. Field order matches the JSON written by `encryptVaultV1`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultV1 {
+    pub v: u32,
+    pub kdf: VaultV1Kdf,
+    pub aead: VaultV1Aead,
+    pub ct_b64: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultV1Kdf {
+    pub alg: String,
+    pub opslimit: u64,
+    pub memlimit: u64,
+    pub salt_b64: String,
+    pub keylen: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultV1Aead {
+    pub alg: String,
+    pub nonce_b64: String,
+    pub aad_b64: String,
+}
+
+/// Overrides for `encrypt_vault_v1`. Salt and nonce are random unless given.
+#[derive(Debug, Clone, Default)]
+pub struct EncryptOptions {
+    pub opslimit: Option<u64>,
+    pub memlimit: Option<u64>,
+    pub salt: Option<[u8; SALT_BYTES]>,
+    pub nonce: Option<[u8; NONCE_BYTES]>,
+}
+
+pub fn encrypt_vault_v1(
+    master_password: &str,
+    payload: &VaultData,
+    options: &EncryptOptions,
+) -> io::Result<String> {
+    let opslimit = options.opslimit.unwrap_or(OPSLIMIT_DEFAULT);
+    let memlimit = options.memlimit.unwrap_or(MEMLIMIT_DEFAULT);
+    let salt = options.salt.unwrap_or_else(|| {
+        let mut salt = [0u8; SALT_BYTES];
+        OsRng.fill_bytes(&mut salt);
+        salt
+    });
+    let nonce = options
+        .nonce
+        .map(XNonce::from)
+        .unwrap_or_else(|| XChaCha20Poly1305::generate_nonce(&mut OsRng));
+
+    let key = derive_key(master_password, &salt, opslimit, memlimit, KEY_BYTES as u64)?;
+    let plaintext = Zeroizing::new(serde_json::to_vec(payload).map_err(invalid_data)?);
+    let ciphertext = XChaCha20Poly1305::new_from_slice(&key)
+        .map_err(|_| invalid("invalid key length"))?
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: &plaintext,
+                aad: V1_AAD,
+            },
+        )
+        .map_err(|_| io::Error::new(io::ErrorKind::Other, "[VaultV1] encryption failed"))?;
+
+    let envelope = VaultV1 {
+        v: VERSION,
+        kdf: VaultV1Kdf {
+            alg: KDF_ALG.to_string(),
+            opslimit,
+            memlimit,
+            salt_b64: STANDARD.encode(salt),
+            keylen: KEY_BYTES as u64,
+        },
+        aead: VaultV1Aead {
+            alg: AEAD_ALG.to_string(),
+            nonce_b64: STANDARD.encode(nonce),
+            aad_b64: STANDARD.encode(V1_AAD),
+        },
+        ct_b64: STANDARD.encode(ciphertext),
+    };
+    serde_json::to_string(&envelope).map_err(invalid_data)
+}
+
+/// Parses file content and checks it against `VaultV1Schema`.
+pub fn parse_vault_v1(content: &str) -> io::Result<VaultV1> {
+    let vault = serde_json::from_str::<VaultV1>(content).map_err(invalid_data)?;
+
+    if vault.v != VERSION {
+        return Err(invalid(&format!("unsupported vault version: {}", vault.v)));
+    }
+    if vault.kdf.alg != KDF_ALG {
+        return Err(invalid(&format!("unsupported kdf: {}", vault.kdf.alg)));
+    }
+    if vault.aead.alg != AEAD_ALG {
+        return Err(invalid(&format!("unsupported aead: {}", vault.aead.alg)));
+    }
+    if [
+        &vault.kdf.salt_b64,
+        &vault.aead.nonce_b64,
+        &vault.aead.aad_b64,
+        &vault.ct_b64,
+    ]
+    .iter()
+    .any(|value| value.is_empty())
+    {
+        return Err(invalid("missing envelope field"));
+    }
+
+    Ok(vault)
+}
+
+/// Decrypts an envelope. A wrong master password (or tampered ciphertext)
+/// fails with `io::ErrorKind::PermissionDenied`, anything else with `InvalidData`.
+pub fn decrypt_vault_v1(vault: &VaultV1, master_password: &str) -> io::Result<VaultData> {
+    if vault.v != VERSION {
+        return Err(invalid(&format!("unsupported vault version: {}", vault.v)));
+    }
+
+    let salt = decode(&vault.kdf.salt_b64, "salt")?;
+    let nonce = decode(&vault.aead.nonce_b64, "nonce")?;
+    let aad = decode(&vault.aead.aad_b64, "aad")?;
+    let ciphertext = decode(&vault.ct_b64, "ciphertext")?;
+
+    if aad != V1_AAD {
+        return Err(invalid("unsupported AAD metadata"));
+    }
+    if nonce.len() != NONCE_BYTES {
+        return Err(invalid(&format!("invalid nonce length: {}", nonce.len())));
+    }
+
+    let key = derive_key(
+        master_password,
+        &salt,
+        vault.kdf.opslimit,
+        vault.kdf.memlimit,
+        vault.kdf.keylen,
+    )?;
+    let plaintext = XChaCha20Poly1305::new_from_slice(&key)
+        .map_err(|_| invalid("invalid key length"))?
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: &aad,
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "[VaultV1] wrong master password or corrupted vault",
+            )
+        })?;
+
+    serde_json::from_slice::<VaultData>(&plaintext).map_err(invalid_data)
+}
+
+/// `parse_vault_v1` followed by `decrypt_vault_v1`.
+pub fn decrypt_vault_content(content: &str, master_password: &str) -> io::Result<VaultData> {
+    decrypt_vault_v1(&parse_vault_v1(content)?, master_password)
+}
+
+fn derive_key(
+    master_password: &str,
+    salt: &[u8],
+    opslimit: u64,
+    memlimit: u64,
+    keylen: u64,
+) -> io::Result<Zeroizing<Vec<u8>>> {
+    if opslimit < OPSLIMIT_MIN {
+        return Err(invalid(&format!("invalid opslimit: {opslimit}")));
+    }
+    if !(MEMLIMIT_MIN..=MEMLIMIT_MAX).contains(&memlimit) {
+        return Err(invalid(&format!("invalid memlimit: {memlimit}")));
+    }
+    if keylen != KEY_BYTES as u64 {
+        return Err(invalid(&format!("invalid keylen: {keylen}")));
+    }
+    if salt.len() != SALT_BYTES {
+        return Err(invalid(&format!("invalid salt length: {}", salt.len())));
+    }
+
+    let t_cost =
+        u32::try_from(opslimit).map_err(|_| invalid(&format!("invalid opslimit: {opslimit}")))?;
+    let m_cost = u32::try_from(memlimit / 1024)
+        .map_err(|_| invalid(&format!("invalid memlimit: {memlimit}")))?;
+    let params = Params::new(m_cost, t_cost, 1, Some(KEY_BYTES))
+        .map_err(|err| invalid(&format!("invalid kdf parameters: {err}")))?;
+
+    let mut key = Zeroizing::new(vec![0u8; KEY_BYTES]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(master_password.as_bytes(), salt, &mut key)
+        .map_err(|err| invalid(&format!("key derivation failed: {err}")))?;
+    Ok(key)
+}
+
+fn decode(value: &str, label: &str) -> io::Result<Vec<u8>> {
+    STANDARD
+        .decode(value)
+        .map_err(|_| invalid(&format!("invalid base64 {label}")))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("[VaultV1] {message}"))
+}
+
+fn invalid_data(err: serde_json::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decrypt_vault_content, decrypt_vault_v1, encrypt_vault_v1, parse_vault_v1, EncryptOptions,
+        STANDARD,
+    };
+    use crate::vault::model::VaultData;
+    use base64::Engine as _;
+    use serde_json::Value;
+    use std::io;
+
+    // Written by `encryptVaultV1` from `VaultV1.ts` on top of libsodium
+    // (crypto_pwhash + crypto_aead_xchacha20poly1305_ietf, as used by both TS
+    // providers), with the app's default KDF parameters.
+    const EMPTY_V1: &str = include_str!("fixtures/v1-empty.json");
+    const FULL_EXPORT_V1: &str = include_str!("fixtures/v1-full-export.json");
+    const FULL_EXPORT: &str = include_str!("fixtures/full-export.json");
+
+    const SELF_TEST_PASSWORD: &str = "ClavisPass::VaultV1::SelfTest";
+    const FULL_EXPORT_PASSWORD: &str = "correct horse battery staple";
+
+    /// The salt and nonce patterns of `verifyVaultV1Provider`.
+    fn self_test_options() -> EncryptOptions {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 24];
+        for (index, byte) in salt.iter_mut().enumerate() {
+            *byte = ((index * 13 + 7) % 256) as u8;
+        }
+        for (index, byte) in nonce.iter_mut().enumerate() {
+            *byte = ((index * 17 + 11) % 256) as u8;
+        }
+        EncryptOptions {
+            opslimit: Some(3),
+            memlimit: Some(64 * 1024 * 1024),
+            salt: Some(salt),
+            nonce: Some(nonce),
+        }
+    }
+
+    #[test]
+    fn decrypts_vaults_written_by_the_app() {
+        assert_eq!(
+            decrypt_vault_content(EMPTY_V1, SELF_TEST_PASSWORD).unwrap(),
+            VaultData::default()
+        );
+
+        let vault = decrypt_vault_content(FULL_EXPORT_V1, FULL_EXPORT_PASSWORD).unwrap();
+        assert_eq!(
+            serde_json::to_value(vault).unwrap(),
+            serde_json::from_str::<Value>(FULL_EXPORT).unwrap()
+        );
+    }
+
+    #[test]
+    fn encrypts_byte_identical_to_the_app() {
+        let content = encrypt_vault_v1(
+            SELF_TEST_PASSWORD,
+            &VaultData::default(),
+            &self_test_options(),
+        )
+        .unwrap();
+        assert_eq!(content, EMPTY_V1.trim_end());
+    }
+
+    #[test]
+    fn wrong_password_is_reported_as_auth_failure() {
+        let err = decrypt_vault_content(EMPTY_V1, "nope").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn rejects_foreign_aad_and_out_of_range_parameters() {
+        let mut vault = parse_vault_v1(EMPTY_V1).unwrap();
+        vault.aead.aad_b64 = STANDARD.encode(br#"{"v":1}"#);
+        let err = decrypt_vault_v1(&vault, SELF_TEST_PASSWORD).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        for (opslimit, memlimit, keylen) in [
+            (0, 1 << 20, 32),
+            (2, 1024, 32),
+            (2, 1 << 40, 32),
+            (2, 1 << 20, 16),
+        ] {
+            let mut vault = parse_vault_v1(EMPTY_V1).unwrap();
+            vault.kdf.opslimit = opslimit;
+            vault.kdf.memlimit = memlimit;
+            vault.kdf.keylen = keylen;
+            let err = decrypt_vault_v1(&vault, SELF_TEST_PASSWORD).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+
+        let other_alg = EMPTY_V1.replace("\"argon2id\"", "\"scrypt\"");
+        assert!(parse_vault_v1(&other_alg).is_err());
+    }
+}
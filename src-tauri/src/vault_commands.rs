@@ -0,0 +1,40 @@
+use crate::vault::{
+    model::VaultData,
+    v1::{self, EncryptOptions},
+};
+
+// Key derivation takes a noticeable moment, so both commands run off the main thread.
+
+#[tauri::command]
+pub async fn vault_encrypt_v1(
+    master_password: String,
+    payload: VaultData,
+    opslimit: Option<u64>,
+    memlimit: Option<u64>,
+) -> Result<String, String> {
+    let options = EncryptOptions {
+        opslimit,
+        memlimit,
+        ..EncryptOptions::default()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        v1::encrypt_vault_v1(&master_password, &payload, &options)
+    })
+    .await
+    .map_err(|err| format!("Failed to encrypt vault: {err}"))?
+    .map_err(|err| format!("Failed to encrypt vault: {err}"))
+}
+
+#[tauri::command]
+pub async fn vault_decrypt_v1(
+    content: String,
+    master_password: String,
+) -> Result<VaultData, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        v1::decrypt_vault_content(&content, &master_password)
+    })
+    .await
+    .map_err(|err| format!("Failed to decrypt vault: {err}"))?
+    .map_err(|err| format!("Failed to decrypt vault: {err}"))
+}
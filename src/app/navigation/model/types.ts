@@ -63,6 +63,8 @@ export type SettingsStackParamList = {
   Scan: undefined;
   Devices: undefined;
   BrowserExtensions: undefined;
+  LocalApi: undefined;
+  Trash: undefined;
 };
 
 export type LoginStackParamList = {
@@ -5,6 +5,8 @@ import ScanScreen from "../../../screens/ScanScreen";
 import { SettingsStackParamList } from "../model/types";
 import DevicesScreen from "../../../screens/DevicesScreen";
 import BrowserExtensionsScreen from "../../../screens/BrowserExtensionsScreen";
+import LocalApiScreen from "../../../screens/LocalApiScreen";
+import TrashScreen from "../../../screens/TrashScreen";
 
 const Stack = createNativeStackNavigator<SettingsStackParamList>();
 
@@ -18,6 +20,8 @@ export default function SettingsStack() {
         name="BrowserExtensions"
         component={BrowserExtensionsScreen}
       />
+      <Stack.Screen name="LocalApi" component={LocalApiScreen} />
+      <Stack.Screen name="Trash" component={TrashScreen} />
     </Stack.Navigator>
   );
 }
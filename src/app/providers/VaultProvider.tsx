@@ -80,7 +80,9 @@ export function VaultProvider({ children }: { children: ReactNode }) {
   }, []);
 
   const refresh = useCallback(() => {
-    const metas = VaultSession.getValues().map(buildEntryMeta);
+    const metas = VaultSession.getValues()
+      .filter((entry) => !entry.deletedAt)
+      .map(buildEntryMeta);
     setEntries(metas);
 
     // Folder list is non-secret meta
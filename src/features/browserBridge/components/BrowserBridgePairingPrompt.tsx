@@ -190,7 +190,7 @@ export default function BrowserBridgePairingPrompt() {
   );
 }
 
-function PromptButton(props: {
+export function PromptButton(props: {
   disabled?: boolean;
   icon: string;
   label: string;
@@ -1,9 +1,12 @@
 import { useEffect, useRef } from "react";
+import { useTranslation } from "react-i18next";
+import type { TFunction } from "i18next";
 
 import { useAuth } from "../../../app/providers/AuthProvider";
 import { useVault } from "../../../app/providers/VaultProvider";
 import ModulesEnum from "../../vault/model/ModulesEnum";
 import type ValuesType from "../../vault/model/ValuesType";
+import type { PasswordHistoryItem } from "../../vault/model/ValuesType";
 import type FolderType from "../../vault/model/FolderType";
 import getModuleData from "../../vault/utils/getModuleData";
 import createUniqueID from "../../../shared/utils/createUniqueID";
@@ -11,12 +14,24 @@ import { getDateTime } from "../../../shared/utils/Timestamp";
 import { logger } from "../../../infrastructure/logging/logger";
 import { detectTauriEnvironment } from "../../../infrastructure/platform/isTauri";
 
-type BrowserWriteKind = "createEntryFromBrowser" | "updateEntryFromBrowser";
+type BrowserWriteKind =
+  | "createEntryFromBrowser"
+  | "updateEntryFromBrowser"
+  | "deleteEntryFromBrowser"
+  | "moveEntryToFolder"
+  | "setFavorite";
 
 type BrowserWriteRequest = {
   id: string;
   kind: BrowserWriteKind;
+  client: { extensionId: string; name?: string | null };
   payload: Record<string, unknown>;
+  queuedWhileLocked?: boolean;
+};
+
+type PasswordHistoryUpdate = {
+  history: PasswordHistoryItem[];
+  report: { recorded: boolean; replacedAtMs?: number; retained: number };
 };
 
 type CreatePayload = {
@@ -39,8 +54,22 @@ type UpdatePayload = {
   url?: string | null;
   matchedHost?: string | null;
   totp?: string | null;
+  lastUpdated?: string | null;
+};
+
+type EntryChangePayload = {
+  entryId: string;
+  lastUpdated?: string | null;
+  folderId?: string | null;
+  fav?: boolean;
 };
 
+class EntryConflictError extends Error {
+  constructor(readonly entry: ValuesType) {
+    super("ENTRY_CONFLICT");
+  }
+}
+
 function normalizeText(value: unknown): string | null {
   if (typeof value !== "string") return null;
   const trimmed = value.trim();
@@ -114,7 +143,18 @@ function createBrowserEntry(
   };
 }
 
-function applyUpdateToEntry(entry: ValuesType, payload: UpdatePayload): ValuesType {
+function currentPassword(entry: ValuesType): string | null {
+  const module = entry.modules.find(
+    (item) => item.module === ModulesEnum.PASSWORD
+  );
+  return module && "value" in module ? normalizeText(module.value) : null;
+}
+
+function applyUpdateToEntry(
+  entry: ValuesType,
+  payload: UpdatePayload,
+  passwordHistory?: PasswordHistoryItem[]
+): ValuesType {
   const modules = [...entry.modules];
   upsertSimpleModule(modules, ModulesEnum.USERNAME, normalizeText(payload.username));
   upsertSimpleModule(modules, ModulesEnum.E_MAIL, normalizeText(payload.email));
@@ -126,13 +166,84 @@ function applyUpdateToEntry(entry: ValuesType, payload: UpdatePayload): ValuesTy
     ...entry,
     title: normalizeText(payload.title) ?? entry.title,
     modules,
+    passwordHistory: passwordHistory ?? entry.passwordHistory,
     lastUpdated: getDateTime(),
   };
 }
 
+async function confirmBrowserDelete(
+  entry: ValuesType,
+  clientName: string,
+  t: TFunction
+) {
+  const { ask } = await import("@tauri-apps/plugin-dialog");
+  return ask(
+    t("settings:browserDeletePrompt", {
+      client: clientName,
+      title: entry.title,
+    }),
+    {
+      title: "ClavisPass",
+      kind: "warning",
+      okLabel: t("settings:browserDeletePromptConfirm"),
+      cancelLabel: t("settings:browserDeletePromptCancel"),
+    }
+  );
+}
+
+async function confirmLockedSave(
+  title: string,
+  clientName: string,
+  t: TFunction
+) {
+  const { ask } = await import("@tauri-apps/plugin-dialog");
+  return ask(
+    t("settings:browserLockedSavePrompt", {
+      client: clientName,
+      title,
+    }),
+    {
+      title: "ClavisPass",
+      kind: "info",
+      okLabel: t("settings:browserLockedSavePromptConfirm"),
+      cancelLabel: t("settings:browserLockedSavePromptCancel"),
+    }
+  );
+}
+
+/**
+ * Saves captured while the vault was locked were not seen by anyone, so the
+ * user accepts each one before it is applied. Returns `false` if the request
+ * may no longer be applied.
+ */
+async function acceptLockedSave(
+  request: BrowserWriteRequest,
+  title: string,
+  t: TFunction
+) {
+  if (!request.queuedWhileLocked) {
+    return true;
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  await invoke("bridge_mark_write_awaiting_user", { requestId: request.id });
+  const accepted = await confirmLockedSave(
+    title,
+    request.client.name ?? request.client.extensionId,
+    t
+  );
+  if (!accepted) {
+    throw new Error("USER_REJECTED");
+  }
+  return invoke<boolean>("bridge_confirm_write_request", {
+    requestId: request.id,
+  });
+}
+
 function BrowserBridgeWriteSync() {
   const auth = useAuth();
   const vault = useVault();
+  const { t } = useTranslation();
   const processingRef = useRef(false);
 
   useEffect(() => {
@@ -154,10 +265,32 @@ function BrowserBridgeWriteSync() {
 
       try {
         const { invoke } = await import("@tauri-apps/api/core");
+
+        // The inbox key is kept in the vault so saves captured while locked
+        // can only be opened after unlock.
+        let inboxSecret = vault.exportFullData().bridgeInboxSecret;
+        if (!inboxSecret) {
+          const generated = await invoke<string>("bridge_generate_inbox_secret");
+          vault.update((draft) => {
+            draft.bridgeInboxSecret = generated;
+          });
+          inboxSecret = generated;
+        }
+
         const requests = await invoke<BrowserWriteRequest[]>(
-          "bridge_claim_pending_writes"
+          "bridge_claim_pending_writes",
+          { inboxSecret }
         );
 
+        const lockedCount = requests.filter(
+          (request) => request.queuedWhileLocked
+        ).length;
+        if (lockedCount > 0) {
+          logger.info(
+            `[BrowserBridge] Found ${lockedCount} browser save(s) captured while locked.`
+          );
+        }
+
         for (const request of requests) {
           try {
             if (request.kind === "createEntryFromBrowser") {
@@ -165,6 +298,9 @@ function BrowserBridgeWriteSync() {
                 request.payload as unknown as CreatePayload,
                 vault.folders
               );
+              if (!(await acceptLockedSave(request, entry.title, t))) {
+                continue;
+              }
               vault.upsertEntry(entry);
               await invoke("bridge_complete_write_request", {
                 requestId: request.id,
@@ -178,10 +314,121 @@ function BrowserBridgeWriteSync() {
               continue;
             }
 
+            if (
+              request.kind === "deleteEntryFromBrowser" ||
+              request.kind === "moveEntryToFolder" ||
+              request.kind === "setFavorite"
+            ) {
+              const payload = request.payload as unknown as EntryChangePayload;
+              const existing = vault
+                .exportFullData()
+                .values.find((entry) => entry.id === payload.entryId);
+              if (!existing || existing.deletedAt) {
+                throw new Error("ENTRY_NOT_FOUND");
+              }
+              if (
+                payload.lastUpdated &&
+                payload.lastUpdated !== existing.lastUpdated
+              ) {
+                throw new EntryConflictError(existing);
+              }
+
+              const folder =
+                request.kind === "moveEntryToFolder"
+                  ? pickFolder(vault.folders, payload.folderId)
+                  : existing.folder;
+              if (
+                request.kind === "moveEntryToFolder" &&
+                normalizeText(payload.folderId) &&
+                !folder
+              ) {
+                throw new Error("FOLDER_NOT_FOUND");
+              }
+
+              if (request.kind === "deleteEntryFromBrowser") {
+                await invoke("bridge_mark_write_awaiting_user", {
+                  requestId: request.id,
+                });
+                const confirmed = await confirmBrowserDelete(
+                  existing,
+                  request.client.name ?? request.client.extensionId,
+                  t
+                );
+                if (!confirmed) {
+                  throw new Error("USER_REJECTED");
+                }
+                // The browser has been told the request timed out; it was
+                // expired and must not be applied anymore.
+                const inTime = await invoke<boolean>(
+                  "bridge_confirm_write_request",
+                  { requestId: request.id }
+                );
+                if (!inTime) {
+                  continue;
+                }
+              }
+
+              const now = getDateTime();
+              vault.update((draft) => {
+                const index = draft.values.findIndex(
+                  (entry) => entry.id === payload.entryId
+                );
+                if (index < 0) {
+                  throw new Error("ENTRY_NOT_FOUND");
+                }
+
+                const entry = draft.values[index];
+                draft.values[index] =
+                  request.kind === "deleteEntryFromBrowser"
+                    ? { ...entry, deletedAt: now, lastUpdated: now }
+                    : request.kind === "moveEntryToFolder"
+                      ? { ...entry, folder, lastUpdated: now }
+                      : { ...entry, fav: payload.fav === true, lastUpdated: now };
+              });
+
+              await invoke("bridge_complete_write_request", {
+                requestId: request.id,
+                ok: true,
+                result: { entryId: existing.id, updatedAt: now },
+              });
+              continue;
+            }
+
             if (request.kind === "updateEntryFromBrowser") {
               const payload = request.payload as unknown as UpdatePayload;
+              const existing = vault
+                .exportFullData()
+                .values.find((entry) => entry.id === payload.entryId);
+              if (!existing) {
+                throw new Error("ENTRY_NOT_FOUND");
+              }
+              if (
+                payload.lastUpdated &&
+                payload.lastUpdated !== existing.lastUpdated
+              ) {
+                throw new EntryConflictError(existing);
+              }
+              if (!(await acceptLockedSave(request, existing.title, t))) {
+                continue;
+              }
+
+              const historyUpdate = await invoke<PasswordHistoryUpdate>(
+                "bridge_record_password_change",
+                {
+                  history: existing.passwordHistory ?? [],
+                  previous: currentPassword(existing),
+                  next: payload.password,
+                  client: request.client.name ?? request.client.extensionId,
+                }
+              );
+
               let updateResult:
-                | { entryId: string; updatedAt: string; title: string }
+                | {
+                    entryId: string;
+                    updatedAt: string;
+                    title: string;
+                    passwordHistory: PasswordHistoryUpdate["report"];
+                  }
                 | null = null;
 
               vault.update((draft) => {
@@ -194,13 +441,15 @@ function BrowserBridgeWriteSync() {
 
                 const updatedEntry = applyUpdateToEntry(
                   draft.values[index],
-                  payload
+                  payload,
+                  historyUpdate.history
                 );
                 draft.values[index] = updatedEntry;
                 updateResult = {
                   entryId: updatedEntry.id,
                   updatedAt: updatedEntry.lastUpdated,
                   title: updatedEntry.title,
+                  passwordHistory: historyUpdate.report,
                 };
               });
 
@@ -215,14 +464,35 @@ function BrowserBridgeWriteSync() {
               });
             }
           } catch (error) {
+            if (error instanceof EntryConflictError) {
+              await invoke("bridge_complete_write_request", {
+                requestId: request.id,
+                ok: false,
+                result: {
+                  entryId: error.entry.id,
+                  title: error.entry.title,
+                  lastUpdated: error.entry.lastUpdated,
+                },
+                errorCode: "ENTRY_CONFLICT",
+                errorMessage:
+                  "The entry was changed since the browser last read it.",
+              });
+              continue;
+            }
+
+            const knownCodes = ["ENTRY_NOT_FOUND", "FOLDER_NOT_FOUND", "USER_REJECTED"];
             const code =
-              error instanceof Error && error.message === "ENTRY_NOT_FOUND"
-                ? "ENTRY_NOT_FOUND"
+              error instanceof Error && knownCodes.includes(error.message)
+                ? error.message
                 : "WRITE_FAILED";
             const message =
               code === "ENTRY_NOT_FOUND"
                 ? "Entry could not be resolved for browser update."
-                : "Browser write request could not be applied.";
+                : code === "FOLDER_NOT_FOUND"
+                  ? "Target folder does not exist."
+                  : code === "USER_REJECTED"
+                    ? "The change was declined in ClavisPass."
+                    : "Browser write request could not be applied.";
 
             await invoke("bridge_complete_write_request", {
               requestId: request.id,
@@ -254,7 +524,7 @@ function BrowserBridgeWriteSync() {
       cancelled = true;
       clearInterval(timer);
     };
-  }, [auth.isLoggedIn, vault, vault.isUnlocked, vault.folders]);
+  }, [auth.isLoggedIn, vault, vault.isUnlocked, vault.folders, t]);
 
   return null;
 }
@@ -0,0 +1,218 @@
+import React, { useCallback, useEffect, useState } from "react";
+import { StyleSheet, View } from "react-native";
+import { Icon, Text } from "react-native-paper";
+import { useTranslation } from "react-i18next";
+
+import { useTheme } from "../../../app/providers/ThemeProvider";
+import { detectTauriEnvironment } from "../../../infrastructure/platform/isTauri";
+import { PromptButton } from "../../browserBridge/components/BrowserBridgePairingPrompt";
+
+const POLL_INTERVAL_MS = 1000;
+
+type ApprovalAction = "control_approve_process" | "control_reject_process";
+
+type PendingProcessApproval = {
+  id: string;
+  pid: number;
+  exe?: string;
+  commandLine?: string;
+  requestedAtMs: number;
+};
+
+export default function CliAccessPrompt() {
+  const { theme } = useTheme();
+  const { t } = useTranslation();
+  const [pending, setPending] = useState<PendingProcessApproval | null>(null);
+  const [acting, setActing] = useState<ApprovalAction | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadPending = useCallback(async () => {
+    if (!(await detectTauriEnvironment())) {
+      setPending(null);
+      return;
+    }
+
+    const { invoke } = await import("@tauri-apps/api/core");
+    const result = await invoke<PendingProcessApproval[]>(
+      "control_list_pending_approvals",
+    );
+    setPending(result[0] ?? null);
+  }, []);
+
+  useEffect(() => {
+    let cancelled = false;
+
+    const tick = async () => {
+      try {
+        if (!cancelled) {
+          await loadPending();
+        }
+      } catch {
+        if (!cancelled) {
+          setPending(null);
+        }
+      }
+    };
+
+    void tick();
+    const timer = setInterval(() => {
+      void tick();
+    }, POLL_INTERVAL_MS);
+
+    return () => {
+      cancelled = true;
+      clearInterval(timer);
+    };
+  }, [loadPending]);
+
+  const act = useCallback(
+    async (action: ApprovalAction) => {
+      if (!pending) {
+        return;
+      }
+
+      setActing(action);
+      setError(null);
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        await invoke<boolean>(action, { approvalId: pending.id });
+        await loadPending();
+      } catch (actionError) {
+        setError(
+          actionError instanceof Error
+            ? actionError.message
+            : t("settings:cliAccessActionFailed"),
+        );
+      } finally {
+        setActing(null);
+      }
+    },
+    [loadPending, pending, t],
+  );
+
+  if (!pending) {
+    return null;
+  }
+
+  const processName =
+    pending.exe?.split("/").pop()?.trim() ||
+    t("settings:cliAccessUnknownProcess");
+  const busy = acting !== null;
+
+  return (
+    <View pointerEvents="box-none" style={styles.overlay}>
+      <View
+        style={[
+          styles.prompt,
+          {
+            backgroundColor: theme.colors.elevation.level1,
+            borderColor: theme.colors.primary,
+          },
+        ]}
+      >
+        <View
+          style={[
+            styles.iconBubble,
+            { backgroundColor: `${theme.colors.primary}1F` },
+          ]}
+        >
+          <Icon source="console" size={24} color={theme.colors.primary} />
+        </View>
+
+        <View style={styles.content}>
+          <Text variant="titleMedium" style={styles.title}>
+            {t("settings:cliAccessPromptTitle")}
+          </Text>
+          <Text
+            variant="bodyMedium"
+            style={{ color: theme.colors.onSurfaceVariant }}
+          >
+            {t("settings:cliAccessPromptDescription", {
+              process: processName,
+              pid: pending.pid,
+            })}
+          </Text>
+          {pending.commandLine ? (
+            <Text
+              numberOfLines={2}
+              selectable
+              variant="bodySmall"
+              style={[styles.commandLine, { color: theme.colors.onSurface }]}
+            >
+              {pending.commandLine}
+            </Text>
+          ) : null}
+          {error ? (
+            <Text variant="bodySmall" style={{ color: theme.colors.error }}>
+              {error}
+            </Text>
+          ) : null}
+
+          <View style={styles.actions}>
+            <PromptButton
+              disabled={busy}
+              icon="check"
+              label={t("settings:cliAccessAllow")}
+              loading={acting === "control_approve_process"}
+              onPress={() => void act("control_approve_process")}
+              variant="primary"
+            />
+            <PromptButton
+              disabled={busy}
+              icon="close"
+              label={t("settings:cliAccessDeny")}
+              loading={acting === "control_reject_process"}
+              onPress={() => void act("control_reject_process")}
+              variant="danger"
+            />
+          </View>
+        </View>
+      </View>
+    </View>
+  );
+}
+
+const styles = StyleSheet.create({
+  overlay: {
+    bottom: 16,
+    left: 16,
+    pointerEvents: "box-none",
+    position: "absolute",
+    right: 16,
+    zIndex: 51,
+  },
+  prompt: {
+    alignSelf: "flex-end",
+    borderRadius: 12,
+    borderWidth: StyleSheet.hairlineWidth,
+    boxShadow: "0px 16px 42px rgba(21, 28, 44, 0.18)",
+    flexDirection: "row",
+    gap: 12,
+    maxWidth: 460,
+    padding: 14,
+  },
+  iconBubble: {
+    alignItems: "center",
+    borderRadius: 10,
+    height: 42,
+    justifyContent: "center",
+    width: 42,
+  },
+  content: {
+    flex: 1,
+    gap: 8,
+  },
+  title: {
+    fontWeight: "800",
+    userSelect: "none",
+  },
+  commandLine: {
+    fontFamily: "monospace",
+  },
+  actions: {
+    flexDirection: "row",
+    flexWrap: "wrap",
+    gap: 8,
+    marginTop: 2,
+  },
+});
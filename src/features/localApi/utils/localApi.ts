@@ -0,0 +1,72 @@
+export type LocalApiStatus = {
+  enabled: boolean;
+  running: boolean;
+  port: number;
+};
+
+export type LocalApiToken = {
+  id: string;
+  name: string;
+  folderIds: string[];
+  write: boolean;
+  createdAtMs: number;
+  lastUsedAtMs?: number | null;
+};
+
+export type IssuedLocalApiToken = LocalApiToken & {
+  token: string;
+};
+
+export type LocalApiAuditRecord = {
+  atMs: number;
+  method: string;
+  path: string;
+  status: number;
+  tokenId?: string;
+  tokenName?: string;
+  entryId?: string;
+};
+
+async function invokeLocalApi<T>(
+  command: string,
+  args?: Record<string, unknown>,
+) {
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<T>(command, args);
+}
+
+export function getLocalApiStatus() {
+  return invokeLocalApi<LocalApiStatus>("local_api_get_status");
+}
+
+export function setLocalApiEnabled(enabled: boolean) {
+  return invokeLocalApi<LocalApiStatus>("local_api_set_enabled", { enabled });
+}
+
+export async function listLocalApiTokens() {
+  return (await invokeLocalApi<LocalApiToken[]>("local_api_list_tokens")) ?? [];
+}
+
+export function issueLocalApiToken(
+  name: string,
+  folderIds: string[],
+  write: boolean,
+) {
+  return invokeLocalApi<IssuedLocalApiToken>("local_api_issue_token", {
+    name,
+    folderIds,
+    write,
+  });
+}
+
+export function revokeLocalApiToken(tokenId: string) {
+  return invokeLocalApi<boolean>("local_api_revoke_token", { tokenId });
+}
+
+export async function listLocalApiAudit(limit = 50) {
+  return (
+    (await invokeLocalApi<LocalApiAuditRecord[]>("local_api_list_audit", {
+      limit,
+    })) ?? []
+  );
+}
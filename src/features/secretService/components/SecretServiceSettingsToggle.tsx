@@ -0,0 +1,91 @@
+import React, { useEffect, useState } from "react";
+import { Text } from "react-native-paper";
+import { useTranslation } from "react-i18next";
+
+import { useTheme } from "../../../app/providers/ThemeProvider";
+import SettingsDivider from "../../settings/components/SettingsDivider";
+import SettingsSwitch from "../../settings/components/SettingsSwitch";
+import type { SettingInfo } from "../../settings/components/SettingInfoButton";
+
+type SecretServiceStatus = {
+  supported: boolean;
+  enabled: boolean;
+  running: boolean;
+};
+
+type Props = {
+  info?: SettingInfo;
+};
+
+export function SecretServiceSettingsToggle(props: Props) {
+  const { t } = useTranslation();
+  const { theme } = useTheme();
+  const [status, setStatus] = useState<SecretServiceStatus | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let active = true;
+    void (async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        const next = await invoke<SecretServiceStatus>(
+          "secret_service_get_status",
+        );
+        if (active) {
+          setStatus(next);
+        }
+      } catch {
+        if (active) {
+          setStatus(null);
+        }
+      }
+    })();
+    return () => {
+      active = false;
+    };
+  }, []);
+
+  if (!status?.supported) {
+    return null;
+  }
+
+  return (
+    <>
+      <SettingsDivider />
+      <SettingsSwitch
+        label={t("settings:secretService")}
+        value={status.enabled}
+        disabled={busy}
+        info={props.info}
+        onValueChange={async (next) => {
+          setBusy(true);
+          setError(null);
+          try {
+            const { invoke } = await import("@tauri-apps/api/core");
+            setStatus(
+              await invoke<SecretServiceStatus>("secret_service_set_enabled", {
+                enabled: next,
+              }),
+            );
+          } catch (e: any) {
+            setError(String(e));
+          } finally {
+            setBusy(false);
+          }
+        }}
+      />
+      {error ? (
+        <Text
+          style={{
+            color: theme.colors.error,
+            paddingHorizontal: 16,
+            paddingBottom: 8,
+          }}
+        >
+          {error}
+        </Text>
+      ) : null}
+    </>
+  );
+}
@@ -0,0 +1,62 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+
+import { useSetting } from "../../../app/providers/SettingsProvider";
+import SettingsDivider from "./SettingsDivider";
+import SettingsDropdownItem from "./SettingsDropdownItem";
+import type { SettingInfo } from "./SettingInfoButton";
+
+type Props = {
+  info?: SettingInfo;
+};
+
+export function PasteLimitSettingsItem(props: Props) {
+  const { t } = useTranslation();
+  const { value: pasteLimit, setValue: setPasteLimit } =
+    useSetting("PASTE_LIMIT");
+  const [supported, setSupported] = useState(false);
+
+  useEffect(() => {
+    let active = true;
+    void (async () => {
+      try {
+        const { invoke } = await import("@tauri-apps/api/core");
+        const next = await invoke<boolean>("clipboard_supports_paste_limit");
+        if (active) {
+          setSupported(next);
+        }
+      } catch {
+        if (active) {
+          setSupported(false);
+        }
+      }
+    })();
+    return () => {
+      active = false;
+    };
+  }, []);
+
+  if (!supported) {
+    return null;
+  }
+
+  return (
+    <>
+      <SettingsDropdownItem
+        value={String(pasteLimit ?? 0)}
+        setValue={(v) => setPasteLimit(Number(v))}
+        label={t("settings:pasteLimit")}
+        info={props.info}
+        dropdownMaxWidth={260}
+        dropdownMinWidth={200}
+        options={[
+          { label: t("settings:pasteLimitOff"), value: "0" },
+          { label: t("settings:pasteLimitOnce"), value: "1" },
+          { label: t("settings:pasteLimitTimes", { count: 2 }), value: "2" },
+          { label: t("settings:pasteLimitTimes", { count: 3 }), value: "3" },
+        ]}
+      />
+      <SettingsDivider />
+    </>
+  );
+}
@@ -19,6 +19,16 @@ export type PairedClient = {
   capabilities?: string[];
 };
 
+/** Commands that change existing entries; each is granted per client. */
+export const BROWSER_WRITE_CAPABILITIES = [
+  "deleteEntryFromBrowser",
+  "moveEntryToFolder",
+  "setFavorite",
+] as const;
+
+export type BrowserWriteCapability =
+  (typeof BROWSER_WRITE_CAPABILITIES)[number];
+
 type BrowserExtensionPairingChangeListener = () => void;
 
 const pairingChangeListeners = new Set<BrowserExtensionPairingChangeListener>();
@@ -78,6 +88,29 @@ export async function actOnBrowserExtensionPairing(
   notifyBrowserExtensionPairingChanges();
 }
 
+export async function setBrowserClientCapability(
+  item: PairedClient,
+  capability: BrowserWriteCapability,
+  granted: boolean,
+) {
+  if (!(await detectTauriEnvironment())) {
+    return;
+  }
+
+  const current = item.capabilities ?? [];
+  const capabilities = granted
+    ? [...current.filter((value) => value !== capability), capability]
+    : current.filter((value) => value !== capability);
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  await invoke("bridge_set_client_capabilities", {
+    extensionId: item.extensionId,
+    clientInstanceId: item.clientInstanceId ?? null,
+    capabilities,
+  });
+  notifyBrowserExtensionPairingChanges();
+}
+
 export function buildBrowserClientKey(
   extensionId: string,
   instanceId?: string | null,
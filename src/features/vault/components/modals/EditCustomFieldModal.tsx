@@ -15,6 +15,8 @@ type Props = {
   title: string;
   setTitle: (title: string) => void;
   value: string;
+  fieldName: string;
+  setFieldName: (fieldName: string) => void;
   inputType: CustomFieldInputType;
   setInputType: (inputType: CustomFieldInputType) => void;
 };
@@ -142,6 +144,24 @@ function EditCustomFieldModal(props: Props) {
           ) : null}
         </View>
 
+        <View style={{ gap: 8 }}>
+          <Text variant="bodyMedium" style={{ opacity: 0.72 }}>
+            {t("modules:customFieldName")}
+          </Text>
+          <TextInput
+            outlineStyle={[globalStyles.outlineStyle]}
+            style={globalStyles.textInputStyle}
+            value={props.fieldName}
+            mode="outlined"
+            onChangeText={(text) => props.setFieldName(text)}
+            autoCapitalize="none"
+            autoCorrect={false}
+          />
+          <Text variant="bodySmall" style={{ opacity: 0.72 }}>
+            {t("modules:customFieldNameHint")}
+          </Text>
+        </View>
+
         <View style={{ flexDirection: "row", justifyContent: "flex-end" }}>
           <Button
             mode="contained"
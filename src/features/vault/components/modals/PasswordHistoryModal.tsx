@@ -0,0 +1,137 @@
+import { ScrollView, StyleSheet, View } from "react-native";
+import { Icon, IconButton, Text } from "react-native-paper";
+import { useTranslation } from "react-i18next";
+
+import { useTheme } from "../../../../app/providers/ThemeProvider";
+import { useSetting } from "../../../../app/providers/SettingsProvider";
+import Modal from "../../../../shared/components/modals/Modal";
+import { formatAbsoluteLocal } from "../../../../shared/utils/Timestamp";
+import { PasswordHistoryItem } from "../../model/ValuesType";
+
+type Props = {
+  visible: boolean;
+  setVisible: (visible: boolean) => void;
+  items: PasswordHistoryItem[];
+  onRestore: (item: PasswordHistoryItem) => void;
+};
+
+function PasswordHistoryModal(props: Props) {
+  const { theme } = useTheme();
+  const { t } = useTranslation();
+  const { value: dateFormat } = useSetting("DATE_FORMAT");
+  const { value: timeFormat } = useSetting("TIME_FORMAT");
+
+  const hideModal = () => props.setVisible(false);
+  const items = [...props.items].sort((a, b) => b.replacedAtMs - a.replacedAtMs);
+
+  return (
+    <Modal visible={props.visible} onDismiss={hideModal}>
+      <View
+        style={{
+          width: 320,
+          minHeight: 220,
+          maxHeight: 440,
+          display: "flex",
+          flexDirection: "column",
+          padding: 14,
+          gap: 12,
+          borderRadius: 12,
+          borderWidth: StyleSheet.hairlineWidth,
+          borderColor: theme.colors.outlineVariant,
+          backgroundColor: theme.colors.background,
+        }}
+      >
+        <View style={{ display: "flex", flexDirection: "column", gap: 4 }}>
+          <Text variant="headlineSmall" style={{ userSelect: "none" }}>
+            {t("common:passwordHistory")}
+          </Text>
+          <Text variant="bodyMedium" style={{ userSelect: "none", opacity: 0.72 }}>
+            {t("common:passwordHistoryDescription")}
+          </Text>
+        </View>
+
+        {items.length === 0 ? (
+          <View style={styles.emptyState}>
+            <Text variant="bodyMedium" style={{ opacity: 0.72 }}>
+              {t("common:passwordHistoryEmpty")}
+            </Text>
+          </View>
+        ) : (
+          <ScrollView contentContainerStyle={{ gap: 8 }}>
+            {items.map((item, index) => (
+              <View
+                key={`${item.replacedAtMs}-${index}`}
+                style={[
+                  styles.item,
+                  {
+                    backgroundColor: theme.colors.surface,
+                    borderColor: theme.colors.outlineVariant,
+                  },
+                ]}
+              >
+                <View style={styles.iconWrap}>
+                  <Icon
+                    source="form-textbox-password"
+                    size={18}
+                    color={theme.colors.primary}
+                  />
+                </View>
+                <View style={{ flex: 1, minWidth: 0, gap: 2 }}>
+                  <Text variant="bodyMedium">
+                    {formatAbsoluteLocal(
+                      new Date(item.replacedAtMs).toISOString(),
+                      dateFormat,
+                      timeFormat,
+                    )}
+                  </Text>
+                  {item.client ? (
+                    <Text variant="bodySmall" style={{ opacity: 0.65 }}>
+                      {t("common:passwordHistoryReplacedBy", {
+                        client: item.client,
+                      })}
+                    </Text>
+                  ) : null}
+                </View>
+                <IconButton
+                  iconColor={theme.colors.primary}
+                  icon="restore"
+                  size={20}
+                  accessibilityLabel={t("common:passwordHistoryRestore")}
+                  onPress={() => {
+                    props.onRestore(item);
+                    hideModal();
+                  }}
+                />
+              </View>
+            ))}
+          </ScrollView>
+        )}
+      </View>
+    </Modal>
+  );
+}
+
+const styles = StyleSheet.create({
+  emptyState: {
+    flex: 1,
+    alignItems: "center",
+    justifyContent: "center",
+    minHeight: 120,
+  },
+  item: {
+    flexDirection: "row",
+    alignItems: "center",
+    gap: 10,
+    paddingHorizontal: 12,
+    paddingVertical: 10,
+    borderRadius: 12,
+    borderWidth: StyleSheet.hairlineWidth,
+  },
+  iconWrap: {
+    width: 24,
+    alignItems: "center",
+    justifyContent: "center",
+  },
+});
+
+export default PasswordHistoryModal;
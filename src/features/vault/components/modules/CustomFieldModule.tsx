@@ -26,7 +26,10 @@ function CustomFieldModule(props: CustomFieldModuleType & Props) {
   const changeCustomField = useCallback(
     (
       next: Partial<
-        Pick<CustomFieldModuleType, "title" | "value" | "inputType">
+        Pick<
+          CustomFieldModuleType,
+          "title" | "value" | "inputType" | "fieldName"
+        >
       >,
     ) => {
       props.changeModule({
@@ -35,12 +38,14 @@ function CustomFieldModule(props: CustomFieldModuleType & Props) {
         title: props.title,
         value: props.value,
         inputType,
+        fieldName: props.fieldName,
         ...next,
       });
     },
     [
       inputType,
       props.changeModule,
+      props.fieldName,
       props.id,
       props.module,
       props.title,
@@ -111,6 +116,10 @@ function CustomFieldModule(props: CustomFieldModuleType & Props) {
         title={props.title}
         setTitle={(nextTitle) => changeCustomField({ title: nextTitle })}
         value={props.value}
+        fieldName={props.fieldName ?? ""}
+        setFieldName={(nextFieldName) =>
+          changeCustomField({ fieldName: nextFieldName.trim() || undefined })
+        }
         inputType={inputType}
         setInputType={(nextInputType) =>
           changeCustomField({ inputType: nextInputType })
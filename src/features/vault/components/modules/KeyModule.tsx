@@ -1,6 +1,6 @@
 import React, { useEffect, useRef, useState } from "react";
 import { View } from "react-native";
-import { TextInput, Text } from "react-native-paper";
+import { Button, TextInput, Text } from "react-native-paper";
 import KeyModuleType from "../../model/modules/KeyModuleType";
 import ModuleContainer from "../ModuleContainer";
 import Props from "../../model/ModuleProps";
@@ -10,6 +10,24 @@ import identifyKeyType from "../../utils/regex/identifyKeyType";
 import { useTranslation } from "react-i18next";
 import ModulesEnum from "../../model/ModulesEnum";
 import { MODULE_ICON } from "../../model/ModuleIconsEnum";
+import { detectTauriEnvironment } from "../../../../infrastructure/platform/isTauri";
+
+type SshKeyAlgorithm = "ed25519" | "rsa-3072" | "rsa-4096";
+
+type SshPublicKeyInfo = {
+  publicKey: string;
+  fingerprint: string;
+};
+
+type GeneratedSshKey = SshPublicKeyInfo & {
+  module: KeyModuleType;
+};
+
+const SSH_KEY_ALGORITHMS: { algorithm: SshKeyAlgorithm; label: string }[] = [
+  { algorithm: "ed25519", label: "Ed25519" },
+  { algorithm: "rsa-3072", label: "RSA 3072" },
+  { algorithm: "rsa-4096", label: "RSA 4096" },
+];
 
 function KeyModule(props: KeyModuleType & Props) {
   const didMount = useRef(false);
@@ -17,6 +35,37 @@ function KeyModule(props: KeyModuleType & Props) {
   const { t } = useTranslation();
   const [value, setValue] = useState(props.value);
   const [keyType, setKeyType] = useState(identifyKeyType(value));
+  const [canGenerate, setCanGenerate] = useState(false);
+  const [generating, setGenerating] = useState<SshKeyAlgorithm | null>(null);
+  const [comment, setComment] = useState("");
+  const [passphrase, setPassphrase] = useState("");
+  // Public key of the current value, kept with the value it belongs to.
+  const [publicKey, setPublicKey] = useState<
+    (SshPublicKeyInfo & { value: string }) | null
+  >(null);
+  const [generateError, setGenerateError] = useState<string | null>(null);
+  useEffect(() => {
+    void detectTauriEnvironment().then(setCanGenerate);
+  }, []);
+  useEffect(() => {
+    if (!canGenerate || value === "" || publicKey?.value === value) return;
+    let cancelled = false;
+    void import("@tauri-apps/api/core")
+      .then(({ invoke }) =>
+        invoke<SshPublicKeyInfo | null>("ssh_describe_key", { value }),
+      )
+      .then((info) => {
+        if (!cancelled) setPublicKey(info ? { ...info, value } : null);
+      })
+      .catch(() => {
+        if (!cancelled) setPublicKey(null);
+      });
+    return () => {
+      cancelled = true;
+    };
+    // `publicKey` only short-circuits a lookup the generator already answered.
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [canGenerate, value]);
   useEffect(() => {
     setValue(props.value);
   }, [props.value]);
@@ -36,6 +85,35 @@ function KeyModule(props: KeyModuleType & Props) {
     }
   }, [value]);
 
+  const generateKey = async (algorithm: SshKeyAlgorithm) => {
+    setGenerating(algorithm);
+    setGenerateError(null);
+    try {
+      const { invoke } = await import("@tauri-apps/api/core");
+      const result = await invoke<GeneratedSshKey>("ssh_generate_key", {
+        moduleId: props.id,
+        algorithm,
+        comment: comment.trim() || null,
+        passphrase: passphrase || null,
+      });
+      setPublicKey({
+        publicKey: result.publicKey,
+        fingerprint: result.fingerprint,
+        value: result.module.value,
+      });
+      setPassphrase("");
+      setValue(result.module.value);
+    } catch (error) {
+      setGenerateError(
+        error instanceof Error
+          ? error.message
+          : t("modules:sshKeyGenerateFailed"),
+      );
+    } finally {
+      setGenerating(null);
+    }
+  };
+
   return (
     <ModuleContainer
       id={props.id}
@@ -65,6 +143,76 @@ function KeyModule(props: KeyModuleType & Props) {
           {keyType}
         </Text>
       )}
+      {canGenerate && value === "" && (
+        <View style={{ gap: 6 }}>
+          <View style={{ flexDirection: "row", gap: 6 }}>
+            <TextInput
+              dense
+              label={t("modules:sshKeyComment")}
+              mode="outlined"
+              outlineStyle={globalStyles.outlineStyle}
+              style={{ flex: 1 }}
+              value={comment}
+              onChangeText={setComment}
+              autoCapitalize="none"
+            />
+            <TextInput
+              dense
+              label={t("modules:sshKeyPassphrase")}
+              mode="outlined"
+              outlineStyle={globalStyles.outlineStyle}
+              style={{ flex: 1 }}
+              value={passphrase}
+              onChangeText={setPassphrase}
+              secureTextEntry
+              autoComplete="new-password"
+            />
+          </View>
+          <View style={{ flexDirection: "row", flexWrap: "wrap", gap: 4 }}>
+            {SSH_KEY_ALGORITHMS.map(({ algorithm, label }) => (
+              <Button
+                key={algorithm}
+                compact
+                disabled={generating !== null}
+                icon="key-plus"
+                loading={generating === algorithm}
+                mode="text"
+                onPress={() => void generateKey(algorithm)}
+                style={{ borderRadius: 12 }}
+                labelStyle={{ fontSize: 12 }}
+              >
+                {t("modules:sshKeyGenerate", { algorithm: label })}
+              </Button>
+            ))}
+          </View>
+        </View>
+      )}
+      {generateError && (
+        <Text style={{ marginLeft: 6, color: theme.colors.error }}>
+          {generateError}
+        </Text>
+      )}
+      {publicKey && publicKey.value === value && (
+        <View style={{ gap: 4 }}>
+          <Text style={{ marginLeft: 6 }}>{t("modules:sshPublicKey")}</Text>
+          <View style={globalStyles.moduleView}>
+            <Text
+              numberOfLines={2}
+              selectable
+              style={{ flex: 1, marginLeft: 6, fontFamily: "monospace" }}
+            >
+              {publicKey.publicKey}
+            </Text>
+            <CopyToClipboard value={publicKey.publicKey} kind="generic" />
+          </View>
+          <Text
+            selectable
+            style={{ marginLeft: 6, color: theme.colors.onSurfaceVariant }}
+          >
+            {publicKey.fingerprint}
+          </Text>
+        </View>
+      )}
     </ModuleContainer>
   );
 }
@@ -12,6 +12,12 @@ export const EntryExternalRefsSchema = z.object({
     .optional(),
 });
 
+export const PasswordHistoryItemSchema = z.object({
+  password: z.string(),
+  replacedAtMs: z.number(),
+  client: z.string().optional(),
+});
+
 export const ValuesTypeSchema = z.object({
   id: z.string(),
   modules: ModulesTypeSchema.default([]),
@@ -22,6 +28,9 @@ export const ValuesTypeSchema = z.object({
   created: z.string().datetime(),
   lastUpdated: z.string().datetime(),
   folder: FolderSchema.nullable().default(null),
+  allowCrossOriginFill: z.boolean().optional(),
+  passwordHistory: z.array(PasswordHistoryItemSchema).optional(),
+  deletedAt: z.string().datetime().nullable().optional(),
   externalRefs: EntryExternalRefsSchema.optional(),
 });
 
@@ -29,6 +38,8 @@ export const ValuesListTypeSchema = z.array(ValuesTypeSchema);
 
 export type ValuesListType = z.infer<typeof ValuesListTypeSchema>;
 
+export type PasswordHistoryItem = z.infer<typeof PasswordHistoryItemSchema>;
+
 type ValuesType = z.infer<typeof ValuesTypeSchema>;
 
 export default ValuesType;
@@ -9,6 +9,7 @@ export const VaultDataTypeSchema = z
     folder: z.array(FolderSchema).default([]),
     values: ValuesListTypeSchema.default([]),
     devices: z.array(VaultDeviceSchema).default([]),
+    bridgeInboxSecret: z.string().optional(),
   })
   .passthrough();
 
@@ -10,6 +10,7 @@ export const CustomFieldModuleTypeSchema = z.object({
   title: z.string(),
   value: z.string(),
   inputType: z.enum(["text", "secret", "number", "date"]).optional(),
+  fieldName: z.string().optional(),
 });
 
 type CustomFieldModuleType = z.infer<typeof CustomFieldModuleTypeSchema>;
@@ -0,0 +1,43 @@
+import { describe, expect, it } from "vitest";
+
+import ModulesEnum from "../model/ModulesEnum";
+import ValuesType from "../model/ValuesType";
+import { applyRestoredPassword, currentPassword } from "./passwordHistory";
+
+function entry(password: string): ValuesType {
+  return {
+    id: "entry",
+    title: "Example",
+    fav: false,
+    created: "2026-01-01T10:00:00.000Z",
+    lastUpdated: "2026-01-01T10:00:00.000Z",
+    folder: null,
+    modules: [{ id: "pw", module: ModulesEnum.PASSWORD, value: password }],
+    passwordHistory: [{ password: "older", replacedAtMs: 2_000 }],
+  } as ValuesType;
+}
+
+describe("passwordHistory", () => {
+  it("sets the restored password and the history the app returned", () => {
+    const current = entry("current");
+    const history = [{ password: "current", replacedAtMs: 3_000 }];
+    const restored = applyRestoredPassword(
+      current,
+      current.passwordHistory![0],
+      { history },
+    );
+
+    expect(currentPassword(restored)).toBe("older");
+    expect(restored.passwordHistory).toEqual(history);
+  });
+
+  it("leaves entries without a password module untouched", () => {
+    const current = { ...entry("current"), modules: [] };
+
+    expect(
+      applyRestoredPassword(current, current.passwordHistory![0], {
+        history: [],
+      }),
+    ).toBe(current);
+  });
+});
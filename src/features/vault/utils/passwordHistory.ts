@@ -0,0 +1,48 @@
+import ModulesEnum from "../model/ModulesEnum";
+import ValuesType, { PasswordHistoryItem } from "../model/ValuesType";
+
+type PasswordHistoryUpdate = {
+  history: PasswordHistoryItem[];
+};
+
+export function currentPassword(entry: ValuesType): string | null {
+  const module = entry.modules.find(
+    (item) => item.module === ModulesEnum.PASSWORD,
+  );
+  return module && "value" in module ? String(module.value) : null;
+}
+
+/**
+ * Puts a retained password back into the entry. The desktop app swaps it with
+ * the current password and applies the history retention.
+ */
+export async function restorePasswordFromHistory(
+  entry: ValuesType,
+  item: PasswordHistoryItem,
+): Promise<PasswordHistoryUpdate> {
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<PasswordHistoryUpdate>("bridge_restore_password_from_history", {
+    history: entry.passwordHistory ?? [],
+    current: currentPassword(entry),
+    item,
+  });
+}
+
+/** Sets the entry's password to `item` and stores the updated history. */
+export function applyRestoredPassword(
+  entry: ValuesType,
+  item: PasswordHistoryItem,
+  update: PasswordHistoryUpdate,
+): ValuesType {
+  const index = entry.modules.findIndex(
+    (module) => module.module === ModulesEnum.PASSWORD,
+  );
+  if (index < 0) return entry;
+
+  const modules = [...entry.modules];
+  modules[index] = {
+    ...modules[index],
+    value: item.password,
+  } as (typeof modules)[number];
+  return { ...entry, modules, passwordHistory: update.history };
+}
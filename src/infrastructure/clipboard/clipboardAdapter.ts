@@ -54,6 +54,54 @@ export async function setClipboardText(value: string) {
   return Clipboard.setStringAsync(value);
 }
 
+/**
+ * Copies through the desktop backend, which hides the value from clipboard
+ * managers and clears it after `clearAfterMs` if the clipboard still holds
+ * it. Returns false when the backend is unavailable so callers can fall back
+ * to `setClipboardText`.
+ */
+export async function copySecretText(value: string, clearAfterMs: number) {
+  if (!isTauriEnvironment()) {
+    return false;
+  }
+
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    await invoke("copy_secret_to_clipboard", {
+      value,
+      clearAfterMs: clearAfterMs > 0 ? clearAfterMs : null,
+    });
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Lets the desktop backend serve `values` to the next pastes, one value each,
+ * and then empty the clipboard. Only Linux supports this; returns false
+ * elsewhere so callers can fall back to a regular copy.
+ */
+export async function copySecretForPastes(
+  values: string[],
+  timeoutMs: number,
+) {
+  if (!isTauriEnvironment()) {
+    return false;
+  }
+
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    await invoke("copy_secret_for_pastes", {
+      values,
+      timeoutMs: timeoutMs > 0 ? timeoutMs : null,
+    });
+    return true;
+  } catch {
+    return false;
+  }
+}
+
 export async function getClipboardText(): Promise<string | null> {
   if (isTauriEnvironment()) {
     const navigatorValue = await tryNavigatorReadText();
@@ -3,29 +3,59 @@ import {
   clipboardOwnership,
   ClipboardContentKind,
 } from "./clipboardOwnership";
-import { setClipboardText } from "./clipboardAdapter";
+import {
+  copySecretForPastes,
+  copySecretText,
+  setClipboardText,
+} from "./clipboardAdapter";
 
 type CopyWithAutoClearOptions = {
   kind?: ClipboardContentKind;
   sensitive?: boolean;
+  /** Pastes a sensitive value survives where supported; 0 means no limit. */
+  pasteLimit?: number;
 };
 
 /**
  * Copies a value to the clipboard and schedules an auto-clear guarded by
- * "only clear if clipboard still matches the last copied value".
+ * "only clear if clipboard still matches the last copied value". On desktop
+ * sensitive values go through the backend, which runs that timer even while
+ * the window is hidden and keeps them out of clipboard history.
  */
 export async function copyWithAutoClear(
   value: string,
   durationMs: number,
   options?: CopyWithAutoClearOptions
 ) {
-  await setClipboardText(value);
-
   const safeDurationMs = Math.max(0, Math.floor(durationMs ?? 0));
+  const sensitive = options?.sensitive ?? true;
+  const pasteLimit = Math.max(0, Math.floor(options?.pasteLimit ?? 0));
+
+  const pasteLimited =
+    sensitive &&
+    pasteLimit > 0 &&
+    (await copySecretForPastes(
+      Array<string>(pasteLimit).fill(value),
+      safeDurationMs,
+    ));
+  if (pasteLimited) {
+    // Reading the clipboard to check ownership would use up a paste; the
+    // backend empties it on its own.
+    clipboardOwnership.clearTracking();
+    clipboardClearScheduler.cancel();
+    return;
+  }
+
+  const copiedByBackend =
+    sensitive && (await copySecretText(value, safeDurationMs));
+  if (!copiedByBackend) {
+    await setClipboardText(value);
+  }
+
   clipboardOwnership.trackCopy(value, {
     kind: options?.kind,
-    sensitive: options?.sensitive,
+    sensitive,
     expiresAtMs: safeDurationMs > 0 ? Date.now() + safeDurationMs : null,
   });
-  clipboardClearScheduler.scheduleClear(safeDurationMs);
+  clipboardClearScheduler.scheduleClear(copiedByBackend ? 0 : safeDurationMs);
 }
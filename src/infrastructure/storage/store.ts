@@ -151,6 +151,10 @@ export const storeSchema = {
     type: "number",
     default: 20,
   },
+  PASTE_LIMIT: {
+    type: "number",
+    default: 0,
+  },
   AUTOSAVE_DELAY: {
     type: "number",
     default: 30,
@@ -249,6 +253,7 @@ const SETTINGS_RESET_KEYS = [
   "DATE_FORMAT",
   "TIME_FORMAT",
   "COPY_DURATION",
+  "PASTE_LIMIT",
   "AUTOSAVE_DELAY",
   "SESSION_DURATION",
   "EXPIRY_REMINDERS",
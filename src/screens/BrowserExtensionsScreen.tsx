@@ -3,7 +3,7 @@ import { RefreshControl, ScrollView, StyleSheet, View } from "react-native";
 import type { NativeStackScreenProps } from "@react-navigation/native-stack";
 import { useFocusEffect } from "@react-navigation/native";
 import Animated, { FadeInDown } from "react-native-reanimated";
-import { ActivityIndicator, Icon, Text } from "react-native-paper";
+import { ActivityIndicator, Chip, Icon, Text } from "react-native-paper";
 import FocusAwareStatusBar from "../shared/components/FocusAwareStatusBar";
 import { useTranslation } from "react-i18next";
 
@@ -18,9 +18,12 @@ import { useSetting } from "../app/providers/SettingsProvider";
 import { formatAbsoluteLocal } from "../shared/utils/Timestamp";
 import {
   actOnBrowserExtensionPairing,
+  BROWSER_WRITE_CAPABILITIES,
   buildBrowserClientKey,
   listBrowserExtensionPairings,
+  setBrowserClientCapability,
   subscribeBrowserExtensionPairingChanges,
+  type BrowserWriteCapability,
   type PairedClient,
   type PendingPairing,
 } from "../features/settings/utils/browserExtensionPairings";
@@ -59,6 +62,12 @@ function browserIconForClient(name?: string | null) {
   return "web";
 }
 
+const capabilityLabelKeys = {
+  deleteEntryFromBrowser: "settings:browserCapabilityDelete",
+  moveEntryToFolder: "settings:browserCapabilityMove",
+  setFavorite: "settings:browserCapabilityFavorite",
+} as const;
+
 const BrowserExtensionsScreen: React.FC<BrowserExtensionsScreenProps> = ({
   navigation,
 }) => {
@@ -154,6 +163,31 @@ const BrowserExtensionsScreen: React.FC<BrowserExtensionsScreenProps> = ({
     [t],
   );
 
+  const toggleCapability = useCallback(
+    async (
+      item: PairedClient,
+      capability: BrowserWriteCapability,
+      granted: boolean,
+    ) => {
+      setActingKey(
+        buildBrowserClientKey(item.extensionId, item.clientInstanceId),
+      );
+      setError(null);
+      try {
+        await setBrowserClientCapability(item, capability, granted);
+      } catch (actionError) {
+        setError(
+          actionError instanceof Error
+            ? actionError.message
+            : t("settings:browserActionFailed"),
+        );
+      } finally {
+        setActingKey(null);
+      }
+    },
+    [t],
+  );
+
   const pairedCount = paired.length;
   const pendingCount = pending.length;
   const state =
@@ -293,6 +327,9 @@ const BrowserExtensionsScreen: React.FC<BrowserExtensionsScreenProps> = ({
                   dateFormat={dateFormat}
                   timeFormat={timeFormat}
                   onDisconnect={() => void act("bridge_revoke_pairing", item)}
+                  onToggleCapability={(capability, granted) =>
+                    void toggleCapability(item, capability, granted)
+                  }
                 />
               ))
             )}
@@ -453,6 +490,10 @@ function BrowserClientCard(props: {
   onApprove?: () => void;
   onReject?: () => void;
   onDisconnect?: () => void;
+  onToggleCapability?: (
+    capability: BrowserWriteCapability,
+    granted: boolean,
+  ) => void;
 }) {
   const { theme, darkmode } = useTheme();
   const { t } = useTranslation();
@@ -569,6 +610,30 @@ function BrowserClientCard(props: {
               {eventLabel}
             </Text>
           </View>
+          <View style={styles.capabilityRow}>
+            <Text numberOfLines={1} style={styles.clientCompactMeta}>
+              {t("settings:browserCapabilities")}
+            </Text>
+            {BROWSER_WRITE_CAPABILITIES.map((capability) => {
+              const granted =
+                (props.item as PairedClient).capabilities?.includes(
+                  capability,
+                ) ?? false;
+              return (
+                <Chip
+                  key={capability}
+                  compact
+                  selected={granted}
+                  disabled={props.acting}
+                  onPress={() =>
+                    props.onToggleCapability?.(capability, !granted)
+                  }
+                >
+                  {t(capabilityLabelKeys[capability])}
+                </Chip>
+              );
+            })}
+          </View>
           <View style={styles.clientCompactBottomRow}>
             <Text
               numberOfLines={1}
@@ -768,6 +833,13 @@ const styles = StyleSheet.create({
     opacity: 0.72,
     userSelect: "none",
   },
+  capabilityRow: {
+    alignItems: "center",
+    flexDirection: "row",
+    flexWrap: "wrap",
+    gap: 6,
+    paddingLeft: 50,
+  },
   clientCompactBottomRow: {
     alignItems: "center",
     flexDirection: "row",
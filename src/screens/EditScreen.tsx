@@ -34,6 +34,7 @@ import Button from "../shared/components/buttons/Button";
 import DeleteModuleModal from "../features/vault/components/modals/DeleteModuleModal";
 import ClearModulesModal from "../features/vault/components/modals/ClearModulesModal";
 import EditHistoryModal from "../features/vault/components/modals/EditHistoryModal";
+import PasswordHistoryModal from "../features/vault/components/modals/PasswordHistoryModal";
 import EntryTagsModal, {
   normalizeTags,
 } from "../features/vault/components/modals/EntryTagsModal";
@@ -54,7 +55,13 @@ import ModulesList from "../features/vault/components/lists/ModulesList";
 import { useVault } from "../app/providers/VaultProvider";
 import { HomeStackParamList } from "../app/navigation/model/types";
 import { logger } from "../infrastructure/logging/logger";
+import { detectTauriEnvironment } from "../infrastructure/platform/isTauri";
 import { useEditHistory } from "../features/vault/utils/editHistory";
+import {
+  applyRestoredPassword,
+  restorePasswordFromHistory,
+} from "../features/vault/utils/passwordHistory";
+import { PasswordHistoryItem } from "../features/vault/model/ValuesType";
 import AdaptiveMenu, {
   AdaptiveMenuItem,
 } from "../shared/components/menus/AdaptiveMenu";
@@ -141,6 +148,13 @@ const EditScreen: React.FC<EditScreenProps> = ({ route, navigation }) => {
   const [clearModulesModalVisible, setClearModulesModalVisible] =
     useState(false);
   const [historyModalVisible, setHistoryModalVisible] = useState(false);
+  const [passwordHistoryModalVisible, setPasswordHistoryModalVisible] =
+    useState(false);
+  // Restoring goes through the desktop app, which owns the history retention.
+  const [canRestorePasswords, setCanRestorePasswords] = useState(false);
+  useEffect(() => {
+    void detectTauriEnvironment().then(setCanRestorePasswords);
+  }, []);
   const [tagsModalVisible, setTagsModalVisible] = useState(false);
   const [overflowMenuVisible, setOverflowMenuVisible] = useState(false);
   const [overflowMenuAnchor, setOverflowMenuAnchor] = useState<{
@@ -495,6 +509,31 @@ const EditScreen: React.FC<EditScreenProps> = ({ route, navigation }) => {
     );
   };
 
+  const changeCrossOriginFill = () => {
+    applyChange(
+      (current) => ({
+        ...current,
+        allowCrossOriginFill: !current.allowCrossOriginFill,
+      }),
+      {
+        action: "system",
+        label: t("common:editHistoryCrossOriginFillUpdated"),
+      },
+    );
+  };
+
+  const restorePassword = async (item: PasswordHistoryItem) => {
+    try {
+      const update = await restorePasswordFromHistory(value, item);
+      applyChange((current) => applyRestoredPassword(current, item, update), {
+        action: "system",
+        label: t("common:editHistoryPasswordRestored"),
+      });
+    } catch (error) {
+      logger.warn("[EditScreen] Failed to restore password:", error);
+    }
+  };
+
   const changePin = () => {
     applyChange(
       (current) => ({
@@ -728,6 +767,32 @@ const EditScreen: React.FC<EditScreenProps> = ({ route, navigation }) => {
         label: value.pinnedAt ? t("common:removePin") : t("common:addPin"),
         onPress: changePin,
       },
+      // The browser extension fills iframes from another origin than the
+      // page only for entries that allow it.
+      ...(value.modules.some((module) => module.module === ModulesEnum.URL)
+        ? [
+            {
+              key: "crossOriginFill",
+              icon: "application-brackets-outline",
+              label: value.allowCrossOriginFill
+                ? t("common:blockCrossOriginFill")
+                : t("common:allowCrossOriginFill"),
+              onPress: changeCrossOriginFill,
+            },
+          ]
+        : []),
+      ...(canRestorePasswords &&
+      (value.passwordHistory?.length ?? 0) > 0 &&
+      value.modules.some((module) => module.module === ModulesEnum.PASSWORD)
+        ? [
+            {
+              key: "passwordHistory",
+              icon: "lock-reset",
+              label: t("common:passwordHistory"),
+              onPress: () => setPasswordHistoryModalVisible(true),
+            },
+          ]
+        : []),
       {
         key: "history",
         icon: "history",
@@ -765,6 +830,7 @@ const EditScreen: React.FC<EditScreenProps> = ({ route, navigation }) => {
       },
     ],
     [
+      canRestorePasswords,
       sessionLog.length,
       sortCompletedTasksDown,
       t,
@@ -1204,6 +1270,12 @@ const EditScreen: React.FC<EditScreenProps> = ({ route, navigation }) => {
         setVisible={setHistoryModalVisible}
         entries={sessionLog}
       />
+      <PasswordHistoryModal
+        visible={passwordHistoryModalVisible}
+        setVisible={setPasswordHistoryModalVisible}
+        items={value.passwordHistory ?? []}
+        onRestore={(item) => void restorePassword(item)}
+      />
       <EntryTagsModal
         visible={tagsModalVisible}
         setVisible={setTagsModalVisible}
@@ -0,0 +1,559 @@
+import React, { useCallback, useState } from "react";
+import { RefreshControl, ScrollView, StyleSheet, View } from "react-native";
+import type { NativeStackScreenProps } from "@react-navigation/native-stack";
+import { useFocusEffect } from "@react-navigation/native";
+import { ActivityIndicator, Chip, Icon, Text, TextInput } from "react-native-paper";
+import FocusAwareStatusBar from "../shared/components/FocusAwareStatusBar";
+import { useTranslation } from "react-i18next";
+
+import AnimatedContainer from "../shared/components/container/AnimatedContainer";
+import Header from "../shared/components/Header";
+import AnimatedPressable from "../shared/components/AnimatedPressable";
+import HintCard from "../shared/components/HintCard";
+import CopyToClipboard from "../shared/components/buttons/CopyToClipboard";
+import SettingsSwitch from "../features/settings/components/SettingsSwitch";
+
+import { useTheme } from "../app/providers/ThemeProvider";
+import { useVault } from "../app/providers/VaultProvider";
+import { SettingsStackParamList } from "../app/navigation/model/types";
+import { useSetting } from "../app/providers/SettingsProvider";
+import { formatAbsoluteLocal } from "../shared/utils/Timestamp";
+import {
+  getLocalApiStatus,
+  issueLocalApiToken,
+  listLocalApiAudit,
+  listLocalApiTokens,
+  revokeLocalApiToken,
+  setLocalApiEnabled,
+  type IssuedLocalApiToken,
+  type LocalApiAuditRecord,
+  type LocalApiStatus,
+  type LocalApiToken,
+} from "../features/localApi/utils/localApi";
+
+const H_PAD = 8;
+
+type LocalApiScreenProps = NativeStackScreenProps<
+  SettingsStackParamList,
+  "LocalApi"
+>;
+
+const LocalApiScreen: React.FC<LocalApiScreenProps> = ({ navigation }) => {
+  const {
+    theme,
+    globalStyles,
+    headerWhite,
+    darkmode,
+    setHeaderWhite,
+    setHeaderSpacing,
+  } = useTheme();
+  const { t } = useTranslation();
+  const { folders } = useVault();
+  const { value: dateFormat } = useSetting("DATE_FORMAT");
+  const { value: timeFormat } = useSetting("TIME_FORMAT");
+
+  const [status, setStatus] = useState<LocalApiStatus | null>(null);
+  const [tokens, setTokens] = useState<LocalApiToken[]>([]);
+  const [audit, setAudit] = useState<LocalApiAuditRecord[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [refreshing, setRefreshing] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const [tokenName, setTokenName] = useState("");
+  const [tokenFolderIds, setTokenFolderIds] = useState<string[]>([]);
+  const [tokenWrite, setTokenWrite] = useState(false);
+  const [issued, setIssued] = useState<IssuedLocalApiToken | null>(null);
+
+  const formatTime = useCallback(
+    (ms: number) =>
+      formatAbsoluteLocal(new Date(ms).toISOString(), dateFormat, timeFormat),
+    [dateFormat, timeFormat],
+  );
+
+  const folderName = useCallback(
+    (folderId: string) =>
+      folders.find((folder) => folder.id === folderId)?.name ?? folderId,
+    [folders],
+  );
+
+  const load = useCallback(
+    async (showRefresh = false) => {
+      if (showRefresh) {
+        setRefreshing(true);
+      } else {
+        setLoading(true);
+      }
+      setError(null);
+
+      try {
+        const [nextStatus, nextTokens, nextAudit] = await Promise.all([
+          getLocalApiStatus(),
+          listLocalApiTokens(),
+          listLocalApiAudit(),
+        ]);
+        setStatus(nextStatus);
+        setTokens(nextTokens);
+        setAudit(nextAudit);
+      } catch (loadError) {
+        setError(String(loadError));
+      } finally {
+        setLoading(false);
+        setRefreshing(false);
+      }
+    },
+    [],
+  );
+
+  useFocusEffect(
+    React.useCallback(() => {
+      setHeaderSpacing(40);
+      setHeaderWhite(false);
+      void load();
+    }, [load, setHeaderSpacing, setHeaderWhite]),
+  );
+
+  const run = useCallback(
+    async (action: () => Promise<void>) => {
+      setBusy(true);
+      setError(null);
+      try {
+        await action();
+      } catch (actionError) {
+        setError(String(actionError));
+      } finally {
+        setBusy(false);
+      }
+    },
+    [],
+  );
+
+  const toggleFolder = (folderId: string) => {
+    setTokenFolderIds((current) =>
+      current.includes(folderId)
+        ? current.filter((id) => id !== folderId)
+        : [...current, folderId],
+    );
+  };
+
+  const issue = () =>
+    run(async () => {
+      const next = await issueLocalApiToken(
+        tokenName,
+        tokenFolderIds,
+        tokenWrite,
+      );
+      setIssued(next);
+      setTokenName("");
+      setTokenFolderIds([]);
+      setTokenWrite(false);
+      setTokens(await listLocalApiTokens());
+    });
+
+  const revoke = (tokenId: string) =>
+    run(async () => {
+      await revokeLocalApiToken(tokenId);
+      if (issued?.id === tokenId) {
+        setIssued(null);
+      }
+      setTokens(await listLocalApiTokens());
+    });
+
+  const panelStyle = [
+    styles.panel,
+    {
+      backgroundColor: theme.colors.background,
+      borderColor: darkmode ? theme.colors.outlineVariant : "white",
+      boxShadow: theme.colors.shadow as any,
+    },
+  ];
+
+  return (
+    <AnimatedContainer style={globalStyles.container}>
+      <FocusAwareStatusBar
+        animated
+        style={headerWhite ? "light" : darkmode ? "light" : "dark"}
+        translucent
+      />
+      <Header
+        title={t("settings:localApi")}
+        onPress={() => navigation.goBack()}
+      />
+
+      <ScrollView
+        style={{ flex: 1, width: "100%" }}
+        contentContainerStyle={{
+          paddingHorizontal: H_PAD,
+          paddingBottom: 20,
+          gap: 8,
+        }}
+        refreshControl={
+          <RefreshControl
+            refreshing={refreshing}
+            tintColor={theme.colors.primary}
+            onRefresh={() => void load(true)}
+          />
+        }
+      >
+        <HintCard hintLine={t("settings:localApiDescription")} />
+
+        {error ? (
+          <View
+            style={[
+              styles.notice,
+              {
+                backgroundColor: theme.colors.errorContainer,
+                borderColor: theme.colors.error,
+              },
+            ]}
+          >
+            <Icon source="alert-circle-outline" size={18} color={theme.colors.error} />
+            <Text style={{ flex: 1, color: theme.colors.onErrorContainer }}>
+              {error}
+            </Text>
+          </View>
+        ) : null}
+
+        {loading || !status ? (
+          <View style={styles.loadingRow}>
+            <ActivityIndicator size={18} />
+            <Text style={{ opacity: 0.75 }}>{t("settings:localApiLoading")}</Text>
+          </View>
+        ) : (
+          <>
+            <View style={panelStyle}>
+              <SettingsSwitch
+                label={t("settings:localApiEnabled")}
+                value={status.enabled}
+                disabled={busy}
+                onValueChange={(enabled) =>
+                  void run(async () => {
+                    setStatus(await setLocalApiEnabled(enabled));
+                  })
+                }
+              />
+              <Text style={{ opacity: 0.72 }}>
+                {status.running
+                  ? t("settings:localApiListening", {
+                      address: `http://127.0.0.1:${status.port}/v1`,
+                    })
+                  : t("settings:localApiStopped")}
+              </Text>
+            </View>
+
+            {issued ? (
+              <View style={[panelStyle, { borderColor: theme.colors.primary }]}>
+                <Text style={styles.panelTitle}>
+                  {t("settings:localApiTokenIssuedTitle", {
+                    name: issued.name,
+                  })}
+                </Text>
+                <Text style={{ opacity: 0.72 }}>
+                  {t("settings:localApiTokenIssuedDescription")}
+                </Text>
+                <View style={styles.tokenRow}>
+                  <Text selectable numberOfLines={1} style={styles.tokenValue}>
+                    {issued.token}
+                  </Text>
+                  <CopyToClipboard value={issued.token} kind="key" sensitive compact />
+                </View>
+              </View>
+            ) : null}
+
+            <SectionTitle title={t("settings:localApiNewToken")} />
+            <View style={panelStyle}>
+              <TextInput
+                outlineStyle={globalStyles.outlineStyle}
+                style={globalStyles.textInputStyle}
+                mode="outlined"
+                label={t("settings:localApiTokenName")}
+                value={tokenName}
+                onChangeText={setTokenName}
+              />
+              <Text style={{ opacity: 0.72 }}>
+                {t("settings:localApiTokenFolders")}
+              </Text>
+              <View style={styles.chips}>
+                {folders.map((folder) => (
+                  <Chip
+                    key={folder.id}
+                    compact
+                    selected={tokenFolderIds.includes(folder.id)}
+                    onPress={() => toggleFolder(folder.id)}
+                  >
+                    {folder.name}
+                  </Chip>
+                ))}
+              </View>
+              <SettingsSwitch
+                label={t("settings:localApiTokenWrite")}
+                value={tokenWrite}
+                onValueChange={setTokenWrite}
+              />
+              <ActionButton
+                label={t("settings:localApiIssueToken")}
+                icon="key-plus"
+                disabled={busy || tokenName.trim().length === 0}
+                onPress={() => void issue()}
+              />
+            </View>
+
+            <SectionTitle
+              title={t("settings:localApiTokens")}
+              count={tokens.length}
+            />
+            {tokens.length === 0 ? (
+              <EmptyState
+                icon="key-remove"
+                description={t("settings:localApiTokensEmpty")}
+              />
+            ) : (
+              tokens.map((token) => (
+                <View key={token.id} style={panelStyle}>
+                  <View style={styles.tokenRow}>
+                    <View style={{ flex: 1, minWidth: 0 }}>
+                      <Text numberOfLines={1} style={styles.panelTitle}>
+                        {token.name}
+                      </Text>
+                      <Text numberOfLines={2} style={{ opacity: 0.72 }}>
+                        {[
+                          token.write
+                            ? t("settings:localApiScopeReadWrite")
+                            : t("settings:localApiScopeRead"),
+                          token.folderIds.length === 0
+                            ? t("settings:localApiScopeAllFolders")
+                            : token.folderIds.map(folderName).join(", "),
+                        ].join(" · ")}
+                      </Text>
+                      <Text variant="labelSmall" style={{ opacity: 0.55 }}>
+                        {t("settings:localApiTokenLastUsed", {
+                          value: token.lastUsedAtMs
+                            ? formatTime(token.lastUsedAtMs)
+                            : "-",
+                        })}
+                      </Text>
+                    </View>
+                    <ActionButton
+                      label={t("settings:localApiRevokeToken")}
+                      icon="key-remove"
+                      variant="mutedDanger"
+                      disabled={busy}
+                      onPress={() => void revoke(token.id)}
+                    />
+                  </View>
+                </View>
+              ))
+            )}
+
+            <SectionTitle title={t("settings:localApiAudit")} count={audit.length} />
+            {audit.length === 0 ? (
+              <EmptyState
+                icon="text-box-search-outline"
+                description={t("settings:localApiAuditEmpty")}
+              />
+            ) : (
+              <View style={panelStyle}>
+                {audit.map((record, index) => (
+                  <View key={`${record.atMs}-${index}`} style={styles.auditRow}>
+                    <Text
+                      variant="labelSmall"
+                      style={{
+                        fontWeight: "800",
+                        color:
+                          record.status < 400
+                            ? theme.colors.primary
+                            : theme.colors.error,
+                      }}
+                    >
+                      {record.status}
+                    </Text>
+                    <Text numberOfLines={1} style={{ flex: 1 }}>
+                      {`${record.method} ${record.path}`}
+                    </Text>
+                    <Text variant="labelSmall" style={{ opacity: 0.55 }}>
+                      {[record.tokenName, formatTime(record.atMs)]
+                        .filter(Boolean)
+                        .join(" · ")}
+                    </Text>
+                  </View>
+                ))}
+              </View>
+            )}
+          </>
+        )}
+      </ScrollView>
+    </AnimatedContainer>
+  );
+};
+
+function SectionTitle(props: { title: string; count?: number }) {
+  const { theme } = useTheme();
+
+  return (
+    <View style={styles.sectionTitle}>
+      <Text style={{ fontWeight: "800", userSelect: "none" }}>
+        {props.title}
+      </Text>
+      {props.count !== undefined ? (
+        <View
+          style={[
+            styles.countPill,
+            { backgroundColor: theme.colors.elevation.level2 },
+          ]}
+        >
+          <Text variant="labelSmall" style={{ fontWeight: "800" }}>
+            {props.count}
+          </Text>
+        </View>
+      ) : null}
+    </View>
+  );
+}
+
+function EmptyState(props: { icon: string; description: string }) {
+  const { theme, darkmode } = useTheme();
+
+  return (
+    <View
+      style={[
+        styles.emptyState,
+        {
+          backgroundColor: theme.colors.background,
+          borderColor: darkmode ? theme.colors.outlineVariant : "white",
+        },
+      ]}
+    >
+      <Icon source={props.icon} size={20} color={theme.colors.onSurfaceVariant} />
+      <Text style={{ flex: 1, opacity: 0.7 }}>{props.description}</Text>
+    </View>
+  );
+}
+
+function ActionButton(props: {
+  label: string;
+  icon: string;
+  onPress?: () => void;
+  disabled?: boolean;
+  variant?: "primary" | "mutedDanger";
+}) {
+  const { theme } = useTheme();
+  const danger = props.variant === "mutedDanger";
+  const textColor = danger ? theme.colors.error : "white";
+
+  return (
+    <AnimatedPressable
+      disabled={props.disabled}
+      onPress={props.onPress}
+      style={[
+        styles.actionButton,
+        {
+          backgroundColor: props.disabled
+            ? theme.colors.surfaceDisabled
+            : danger
+              ? theme.colors.elevation.level3
+              : theme.colors.primary,
+          borderColor: danger ? `${theme.colors.error}40` : "transparent",
+          borderWidth: danger ? StyleSheet.hairlineWidth : 0,
+          opacity: props.disabled ? 0.7 : 1,
+        },
+      ]}
+    >
+      <View style={styles.actionButtonContent}>
+        <Icon source={props.icon} size={18} color={textColor} />
+        <Text
+          variant="bodySmall"
+          style={[styles.actionButtonLabel, { color: textColor }]}
+        >
+          {props.label}
+        </Text>
+      </View>
+    </AnimatedPressable>
+  );
+}
+
+const styles = StyleSheet.create({
+  panel: {
+    borderRadius: 12,
+    borderWidth: StyleSheet.hairlineWidth,
+    gap: 8,
+    overflow: "hidden",
+    padding: 12,
+  },
+  panelTitle: {
+    fontWeight: "800",
+    userSelect: "none",
+  },
+  notice: {
+    alignItems: "center",
+    borderRadius: 12,
+    borderWidth: StyleSheet.hairlineWidth,
+    flexDirection: "row",
+    gap: 8,
+    padding: 10,
+  },
+  loadingRow: {
+    alignItems: "center",
+    flexDirection: "row",
+    gap: 8,
+    justifyContent: "center",
+    paddingVertical: 18,
+  },
+  sectionTitle: {
+    alignItems: "center",
+    flexDirection: "row",
+    gap: 8,
+    marginTop: 4,
+  },
+  countPill: {
+    alignItems: "center",
+    borderRadius: 999,
+    minWidth: 24,
+    paddingHorizontal: 8,
+    paddingVertical: 3,
+  },
+  emptyState: {
+    alignItems: "center",
+    borderRadius: 12,
+    borderWidth: StyleSheet.hairlineWidth,
+    flexDirection: "row",
+    gap: 10,
+    padding: 12,
+  },
+  tokenRow: {
+    alignItems: "center",
+    flexDirection: "row",
+    gap: 8,
+  },
+  tokenValue: {
+    flex: 1,
+    fontFamily: "monospace",
+  },
+  chips: {
+    flexDirection: "row",
+    flexWrap: "wrap",
+    gap: 6,
+  },
+  auditRow: {
+    alignItems: "center",
+    flexDirection: "row",
+    gap: 8,
+  },
+  actionButton: {
+    alignSelf: "flex-start",
+    borderRadius: 8,
+    justifyContent: "center",
+    minHeight: 34,
+    paddingHorizontal: 10,
+    paddingVertical: 6,
+  },
+  actionButtonContent: {
+    alignItems: "center",
+    flexDirection: "row",
+    gap: 7,
+  },
+  actionButtonLabel: {
+    fontWeight: "700",
+  },
+});
+
+export default LocalApiScreen;
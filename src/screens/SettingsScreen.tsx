@@ -73,6 +73,8 @@ import BackupExportButton from "../features/settings/components/buttons/BackupEx
 import type { NativeStackScreenProps } from "@react-navigation/native-stack";
 import { SettingsStackParamList } from "../app/navigation/model/types";
 import { ContentProtectionSettingsToggle } from "../features/settings/components/ContentProtectionSettingsToggle";
+import { SecretServiceSettingsToggle } from "../features/secretService/components/SecretServiceSettingsToggle";
+import { PasteLimitSettingsItem } from "../features/settings/components/PasteLimitSettingsItem";
 import AppearanceSettingsSection from "../features/settings/components/AppearanceSettingsSection";
 import FastAccessPositionPicker from "../features/settings/components/FastAccessPositionPicker";
 import HotkeyRecorderItem from "../features/settings/components/HotkeyRecorderItem";
@@ -413,8 +415,10 @@ const SettingsScreen: React.FC<SettingsScreenProps> = ({ navigation }) => {
         t("settings:expiryReminders"),
         t("settings:manageDevices"),
         t("settings:copyDuration"),
+        t("settings:pasteLimit"),
         t("settings:autosaveDelay"),
         t("settings:sessionDuration"),
+        t("settings:secretService"),
         "2fa",
         "auth",
         "biometric",
@@ -424,17 +428,24 @@ const SettingsScreen: React.FC<SettingsScreenProps> = ({ navigation }) => {
         "expiry",
         "reminder",
         "notification",
+        "secret service",
+        "libsecret",
       ]),
       browserExtensions:
         isTauri &&
         matchesSettingsSearch([
           t("settings:browserExtensions"),
           t("settings:browserExtensionSettingsItem"),
+          t("settings:localApi"),
           "browser",
           "extension",
           "firefox",
           "chrome",
           "edge",
+          "api",
+          "rest",
+          "token",
+          "automation",
         ]),
       cryptography: matchesSettingsSearch([
         t("settings:cryptography"),
@@ -458,9 +469,12 @@ const SettingsScreen: React.FC<SettingsScreenProps> = ({ navigation }) => {
         t("settings:backup"),
         t("settings:importBackup"),
         t("settings:exportBackup"),
+        t("settings:trash"),
         "backup",
         "export",
         "import",
+        "trash",
+        "restore",
       ]),
       import: matchesSettingsSearch([
         t("settings:import"),
@@ -862,6 +876,14 @@ const SettingsScreen: React.FC<SettingsScreenProps> = ({ navigation }) => {
           t("settings:infoContentProtectionBullet2"),
         ],
       },
+      secretService: {
+        title: t("settings:infoSecretServiceTitle"),
+        body: t("settings:infoSecretServiceBody"),
+        bullets: [
+          t("settings:infoSecretServiceBullet1"),
+          t("settings:infoSecretServiceBullet2"),
+        ],
+      },
       blurOnUnfocus: {
         title: t("settings:infoBlurOnUnfocusTitle"),
         body: t("settings:infoBlurOnUnfocusBody"),
@@ -874,6 +896,14 @@ const SettingsScreen: React.FC<SettingsScreenProps> = ({ navigation }) => {
           t("settings:infoCopyDurationBullet2"),
         ],
       },
+      pasteLimit: {
+        title: t("settings:infoPasteLimitTitle"),
+        body: t("settings:infoPasteLimitBody"),
+        bullets: [
+          t("settings:infoPasteLimitBullet1"),
+          t("settings:infoPasteLimitBullet2"),
+        ],
+      },
       autosaveDelay: {
         title: t("settings:infoAutosaveDelayTitle"),
         body: t("settings:infoAutosaveDelayBody"),
@@ -1437,6 +1467,9 @@ const SettingsScreen: React.FC<SettingsScreenProps> = ({ navigation }) => {
                         void setBlurOnUnfocus(checked);
                       }}
                     />
+                    <SecretServiceSettingsToggle
+                      info={settingInfo.secretService}
+                    />
                   </>
                 ) : null}
                 {Platform.OS !== "web" ? (
@@ -1497,6 +1530,7 @@ const SettingsScreen: React.FC<SettingsScreenProps> = ({ navigation }) => {
                   ]}
                 />
                 <SettingsDivider />
+                <PasteLimitSettingsItem info={settingInfo.pasteLimit} />
 
                 <SettingsDropdownItem
                   value={String(autosaveDelaySeconds ?? 30)}
@@ -1603,6 +1637,15 @@ const SettingsScreen: React.FC<SettingsScreenProps> = ({ navigation }) => {
                 <BackupImportButton />
                 <SettingsDivider />
                 <BackupExportButton />
+                <SettingsDivider />
+                <SettingsItem
+                  leadingIcon="delete-outline"
+                  onPress={() => {
+                    navigation.navigate("Trash");
+                  }}
+                >
+                  {t("settings:trash")}
+                </SettingsItem>
               </SettingsContainer>
             ) : null}
 
@@ -1637,6 +1680,15 @@ const SettingsScreen: React.FC<SettingsScreenProps> = ({ navigation }) => {
                 >
                   {t("settings:browserExtensionSettingsItem")}
                 </SettingsItem>
+                <SettingsDivider />
+                <SettingsItem
+                  leadingIcon="api"
+                  onPress={() => {
+                    navigation.navigate("LocalApi");
+                  }}
+                >
+                  {t("settings:localApi")}
+                </SettingsItem>
               </SettingsContainer>
             ) : null}
 
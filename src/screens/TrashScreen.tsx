@@ -0,0 +1,249 @@
+import React, { useMemo, useState } from "react";
+import { ScrollView, StyleSheet, View } from "react-native";
+import type { NativeStackScreenProps } from "@react-navigation/native-stack";
+import { useFocusEffect } from "@react-navigation/native";
+import { Icon, Text } from "react-native-paper";
+import FocusAwareStatusBar from "../shared/components/FocusAwareStatusBar";
+import { useTranslation } from "react-i18next";
+
+import AnimatedContainer from "../shared/components/container/AnimatedContainer";
+import Header from "../shared/components/Header";
+import AnimatedPressable from "../shared/components/AnimatedPressable";
+import HintCard from "../shared/components/HintCard";
+import DeleteModal from "../features/vault/components/modals/DeleteModal";
+
+import { useTheme } from "../app/providers/ThemeProvider";
+import { useVault } from "../app/providers/VaultProvider";
+import { SettingsStackParamList } from "../app/navigation/model/types";
+import { useSetting } from "../app/providers/SettingsProvider";
+import { formatAbsoluteLocal, getDateTime } from "../shared/utils/Timestamp";
+
+const H_PAD = 8;
+
+type TrashScreenProps = NativeStackScreenProps<SettingsStackParamList, "Trash">;
+
+const TrashScreen: React.FC<TrashScreenProps> = ({ navigation }) => {
+  const {
+    theme,
+    globalStyles,
+    headerWhite,
+    darkmode,
+    setHeaderWhite,
+    setHeaderSpacing,
+  } = useTheme();
+  const { t } = useTranslation();
+  const vault = useVault();
+  const { value: dateFormat } = useSetting("DATE_FORMAT");
+  const { value: timeFormat } = useSetting("TIME_FORMAT");
+  const [deleteId, setDeleteId] = useState<string | null>(null);
+
+  useFocusEffect(
+    React.useCallback(() => {
+      setHeaderSpacing(40);
+      setHeaderWhite(false);
+    }, [setHeaderSpacing, setHeaderWhite]),
+  );
+
+  const trashed = useMemo(
+    () =>
+      vault.isUnlocked
+        ? vault
+            .exportFullData()
+            .values.filter((entry) => entry.deletedAt)
+            .map((entry) => ({
+              id: entry.id,
+              title: entry.title,
+              deletedAt: entry.deletedAt as string,
+            }))
+            .sort((a, b) => b.deletedAt.localeCompare(a.deletedAt))
+        : [],
+    // `revision` changes with every vault write.
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+    [vault.isUnlocked, vault.revision],
+  );
+
+  const restore = (id: string) => {
+    vault.update((draft) => {
+      const index = draft.values.findIndex((entry) => entry.id === id);
+      if (index >= 0) {
+        draft.values[index] = {
+          ...draft.values[index],
+          deletedAt: null,
+          lastUpdated: getDateTime(),
+        };
+      }
+    });
+  };
+
+  return (
+    <AnimatedContainer style={globalStyles.container}>
+      <FocusAwareStatusBar
+        animated
+        style={headerWhite ? "light" : darkmode ? "light" : "dark"}
+        translucent
+      />
+      <Header title={t("settings:trash")} onPress={() => navigation.goBack()} />
+
+      <ScrollView
+        style={{ flex: 1, width: "100%" }}
+        contentContainerStyle={{
+          paddingHorizontal: H_PAD,
+          paddingBottom: 20,
+          gap: 8,
+        }}
+      >
+        <HintCard hintLine={t("settings:trashDescription")} />
+
+        {trashed.length === 0 ? (
+          <View
+            style={[
+              styles.panel,
+              styles.row,
+              {
+                backgroundColor: theme.colors.background,
+                borderColor: darkmode ? theme.colors.outlineVariant : "white",
+              },
+            ]}
+          >
+            <Icon
+              source="delete-empty-outline"
+              size={20}
+              color={theme.colors.onSurfaceVariant}
+            />
+            <Text style={{ flex: 1, opacity: 0.7 }}>
+              {t("settings:trashEmpty")}
+            </Text>
+          </View>
+        ) : (
+          trashed.map((entry) => (
+            <View
+              key={entry.id}
+              style={[
+                styles.panel,
+                styles.row,
+                {
+                  backgroundColor: theme.colors.background,
+                  borderColor: darkmode ? theme.colors.outlineVariant : "white",
+                  boxShadow: theme.colors.shadow as any,
+                },
+              ]}
+            >
+              <View style={{ flex: 1, minWidth: 0 }}>
+                <Text numberOfLines={1} style={styles.title}>
+                  {entry.title}
+                </Text>
+                <Text variant="labelSmall" style={{ opacity: 0.6 }}>
+                  {t("settings:trashDeletedAt", {
+                    value: formatAbsoluteLocal(
+                      entry.deletedAt,
+                      dateFormat,
+                      timeFormat,
+                    ),
+                  })}
+                </Text>
+              </View>
+              <ActionButton
+                label={t("settings:trashRestore")}
+                icon="restore"
+                onPress={() => restore(entry.id)}
+              />
+              <ActionButton
+                label={t("settings:trashDeleteForever")}
+                icon="delete-forever-outline"
+                variant="mutedDanger"
+                onPress={() => setDeleteId(entry.id)}
+              />
+            </View>
+          ))
+        )}
+      </ScrollView>
+
+      <DeleteModal
+        visible={deleteId !== null}
+        setVisible={(visible) => {
+          if (!visible) {
+            setDeleteId(null);
+          }
+        }}
+        onDelete={() => {
+          if (deleteId) {
+            vault.deleteEntry(deleteId);
+          }
+          setDeleteId(null);
+        }}
+      />
+    </AnimatedContainer>
+  );
+};
+
+function ActionButton(props: {
+  label: string;
+  icon: string;
+  onPress?: () => void;
+  variant?: "primary" | "mutedDanger";
+}) {
+  const { theme } = useTheme();
+  const danger = props.variant === "mutedDanger";
+  const textColor = danger ? theme.colors.error : "white";
+
+  return (
+    <AnimatedPressable
+      onPress={props.onPress}
+      style={[
+        styles.actionButton,
+        {
+          backgroundColor: danger
+            ? theme.colors.elevation.level3
+            : theme.colors.primary,
+          borderColor: danger ? `${theme.colors.error}40` : "transparent",
+          borderWidth: danger ? StyleSheet.hairlineWidth : 0,
+        },
+      ]}
+    >
+      <View style={styles.actionButtonContent}>
+        <Icon source={props.icon} size={18} color={textColor} />
+        <Text
+          variant="bodySmall"
+          style={[styles.actionButtonLabel, { color: textColor }]}
+        >
+          {props.label}
+        </Text>
+      </View>
+    </AnimatedPressable>
+  );
+}
+
+const styles = StyleSheet.create({
+  panel: {
+    borderRadius: 12,
+    borderWidth: StyleSheet.hairlineWidth,
+    overflow: "hidden",
+    padding: 12,
+  },
+  row: {
+    alignItems: "center",
+    flexDirection: "row",
+    gap: 8,
+  },
+  title: {
+    fontWeight: "800",
+    userSelect: "none",
+  },
+  actionButton: {
+    borderRadius: 8,
+    justifyContent: "center",
+    minHeight: 34,
+    paddingHorizontal: 10,
+    paddingVertical: 6,
+  },
+  actionButtonContent: {
+    alignItems: "center",
+    flexDirection: "row",
+    gap: 7,
+  },
+  actionButtonLabel: {
+    fontWeight: "700",
+  },
+});
+
+export default TrashScreen;
@@ -11,6 +11,7 @@ type CopyOptions = {
 
 export function useClipboardCopy() {
   const { value: copyDurationSeconds } = useSetting("COPY_DURATION");
+  const { value: pasteLimit } = useSetting("PASTE_LIMIT");
 
   const copy = React.useCallback(
     async (value: string, options?: CopyOptions) => {
@@ -21,11 +22,12 @@ export function useClipboardCopy() {
       await copyWithAutoClear(value, durationMs, {
         kind: options?.kind,
         sensitive: options?.sensitive,
+        pasteLimit,
       });
 
       return { durationMs };
     },
-    [copyDurationSeconds]
+    [copyDurationSeconds, pasteLimit]
   );
 
   return { copy };
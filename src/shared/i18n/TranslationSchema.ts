@@ -87,12 +87,19 @@ export type TranslationSchema = {
     editHistoryModulesCleared: string;
     editHistoryFavoriteUpdated: string;
     editHistoryPinUpdated: string;
+    editHistoryCrossOriginFillUpdated: string;
     editHistoryFolderUpdated: string;
     editHistoryFolderFavoriteUpdated: string;
     editHistoryUndo: string;
     editHistoryRedo: string;
     editHistorySaved: string;
     editHistoryOpened: string;
+    editHistoryPasswordRestored: string;
+    passwordHistory: string;
+    passwordHistoryDescription: string;
+    passwordHistoryEmpty: string;
+    passwordHistoryReplacedBy: string;
+    passwordHistoryRestore: string;
     sortCompletedTasksDown: string;
     selectFolder: string;
     selectFolderDescription: string;
@@ -104,6 +111,8 @@ export type TranslationSchema = {
     removeFavorite: string;
     addPin: string;
     removePin: string;
+    allowCrossOriginFill: string;
+    blockCrossOriginFill: string;
     moveToFolder: string;
     tags: string;
     manageTags: string;
@@ -239,6 +248,12 @@ export type TranslationSchema = {
     fastAccessPositionBottomRight: string;
     importBackup: string;
     exportBackup: string;
+    trash: string;
+    trashDescription: string;
+    trashEmpty: string;
+    trashDeletedAt: string;
+    trashRestore: string;
+    trashDeleteForever: string;
     importData: string;
     importDataHint: string;
     importPasswords: string;
@@ -255,6 +270,10 @@ export type TranslationSchema = {
     seconds: string;
     copyDuration: string;
     copyDurationOff: string;
+    pasteLimit: string;
+    pasteLimitOff: string;
+    pasteLimitOnce: string;
+    pasteLimitTimes: string;
     autosaveDelay: string;
     autosaveOff: string;
     sessionDuration: string;
@@ -296,6 +315,10 @@ export type TranslationSchema = {
     browserApprove: string;
     browserReject: string;
     browserDisconnect: string;
+    browserCapabilities: string;
+    browserCapabilityDelete: string;
+    browserCapabilityMove: string;
+    browserCapabilityFavorite: string;
     browserRequestedAt: string;
     browserApprovedAt: string;
     browserLastSeenAt: string;
@@ -310,10 +333,52 @@ export type TranslationSchema = {
     browserPairingPromptTitle: string;
     browserPairingPromptDescription: string;
     browserPairingPromptLater: string;
+    browserDeletePrompt: string;
+    browserDeletePromptConfirm: string;
+    browserDeletePromptCancel: string;
+    browserLockedSavePrompt: string;
+    browserLockedSavePromptConfirm: string;
+    browserLockedSavePromptCancel: string;
     browserUnknownClientShort: string;
     browserLoading: string;
     browserLoadFailed: string;
     browserActionFailed: string;
+    localApi: string;
+    localApiDescription: string;
+    localApiLoading: string;
+    localApiEnabled: string;
+    localApiListening: string;
+    localApiStopped: string;
+    localApiNewToken: string;
+    localApiTokenName: string;
+    localApiTokenFolders: string;
+    localApiTokenWrite: string;
+    localApiIssueToken: string;
+    localApiTokenIssuedTitle: string;
+    localApiTokenIssuedDescription: string;
+    localApiTokens: string;
+    localApiTokensEmpty: string;
+    localApiScopeRead: string;
+    localApiScopeReadWrite: string;
+    localApiScopeAllFolders: string;
+    localApiTokenLastUsed: string;
+    localApiRevokeToken: string;
+    localApiAudit: string;
+    localApiAuditEmpty: string;
+    cliAccessPromptTitle: string;
+    cliAccessPromptDescription: string;
+    cliAccessAllow: string;
+    cliAccessDeny: string;
+    cliAccessUnknownProcess: string;
+    cliAccessActionFailed: string;
+    sshSignPromptTitle: string;
+    sshSignPromptDescription: string;
+    sshSignAllow: string;
+    sshSignActionFailed: string;
+    secretService: string;
+    secretServicePromptTitle: string;
+    secretServicePromptDescription: string;
+    secretServiceActionFailed: string;
     infoSystemAuthTitle: string;
     infoSystemAuthBody: string;
     infoSystemAuthBullet1: string;
@@ -322,12 +387,20 @@ export type TranslationSchema = {
     infoContentProtectionBody: string;
     infoContentProtectionBullet1: string;
     infoContentProtectionBullet2: string;
+    infoSecretServiceTitle: string;
+    infoSecretServiceBody: string;
+    infoSecretServiceBullet1: string;
+    infoSecretServiceBullet2: string;
     infoBlurOnUnfocusTitle: string;
     infoBlurOnUnfocusBody: string;
     infoCopyDurationTitle: string;
     infoCopyDurationBody: string;
     infoCopyDurationBullet1: string;
     infoCopyDurationBullet2: string;
+    infoPasteLimitTitle: string;
+    infoPasteLimitBody: string;
+    infoPasteLimitBullet1: string;
+    infoPasteLimitBullet2: string;
     infoAutosaveDelayTitle: string;
     infoAutosaveDelayBody: string;
     infoAutosaveDelayBullet1: string;
@@ -560,6 +633,8 @@ export type TranslationSchema = {
     creditCardShowLess: string;
     customField: string;
     customFieldTitle: string;
+    customFieldName: string;
+    customFieldNameHint: string;
     customFieldType: string;
     customFieldTypeText: string;
     customFieldTypeSecret: string;
@@ -576,6 +651,11 @@ export type TranslationSchema = {
     email: string;
     expiry: string;
     key: string;
+    sshKeyGenerate: string;
+    sshKeyGenerateFailed: string;
+    sshKeyComment: string;
+    sshKeyPassphrase: string;
+    sshPublicKey: string;
     note: string;
     password: string;
     person: string;
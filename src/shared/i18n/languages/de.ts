@@ -89,12 +89,20 @@ const de: TranslationSchema = {
     editHistoryModulesCleared: "Alle Module entfernt",
     editHistoryFavoriteUpdated: "Favorit geändert",
     editHistoryPinUpdated: "Pin geändert",
+    editHistoryCrossOriginFillUpdated: "Ausfüllen in Frames geändert",
     editHistoryFolderUpdated: "Ordner geändert",
     editHistoryFolderFavoriteUpdated: "Ordner und Favorit geändert",
     editHistoryUndo: "Letzte Änderung rückgängig gemacht",
     editHistoryRedo: "Änderung wiederhergestellt",
     editHistorySaved: "Änderungen gespeichert",
     editHistoryOpened: "Editor geöffnet",
+    editHistoryPasswordRestored: "Früheres Passwort wiederhergestellt",
+    passwordHistory: "Passwortverlauf",
+    passwordHistoryDescription:
+      "Frühere Passwörter dieses Eintrags. Beim Wiederherstellen bleibt das aktuelle Passwort im Verlauf.",
+    passwordHistoryEmpty: "Noch keine früheren Passwörter.",
+    passwordHistoryReplacedBy: "Ersetzt durch {{client}}",
+    passwordHistoryRestore: "Passwort wiederherstellen",
     sortCompletedTasksDown: "Erledigte Tasks nach unten",
     selectFolder: "Ordner auswählen",
     selectFolderDescription: "Wähle aus, wo dieser Eintrag liegen soll.",
@@ -107,6 +115,8 @@ const de: TranslationSchema = {
     removeFavorite: "Aus Favoriten entfernen",
     addPin: "Eintrag anpinnen",
     removePin: "Pin entfernen",
+    allowCrossOriginFill: "Ausfüllen in eingebetteten Frames erlauben",
+    blockCrossOriginFill: "Ausfüllen in eingebetteten Frames blockieren",
     moveToFolder: "In Ordner verschieben",
     tags: "Tags",
     manageTags: "Tags verwalten",
@@ -252,6 +262,13 @@ const de: TranslationSchema = {
     fastAccessPositionBottomRight: "Unten rechts",
     importBackup: "Importieren",
     exportBackup: "Exportieren",
+    trash: "Papierkorb",
+    trashDescription:
+      "Einträge, die eine Browser-Erweiterung in den Papierkorb verschoben hat, bleiben hier, bis du sie wiederherstellst oder löschst.",
+    trashEmpty: "Der Papierkorb ist leer.",
+    trashDeletedAt: "In den Papierkorb verschoben: {{value}}",
+    trashRestore: "Wiederherstellen",
+    trashDeleteForever: "Löschen",
     importData: "Daten importieren",
     importDataHint:
       "Importiere Passwörter und Einträge aus anderen Passwortmanagern oder Browser-Exporten.",
@@ -270,6 +287,10 @@ const de: TranslationSchema = {
     seconds: "{{count}} Sekunden",
     copyDuration: "Kopierdauer",
     copyDurationOff: "Aus",
+    pasteLimit: "Einfügelimit",
+    pasteLimitOff: "Unbegrenzt",
+    pasteLimitOnce: "Einmal einfügen",
+    pasteLimitTimes: "{{count}}-mal einfügen",
     autosaveDelay: "Autospeichern",
     autosaveOff: "Aus",
     sessionDuration: "Sperren bei Inaktivität",
@@ -317,6 +338,10 @@ const de: TranslationSchema = {
     browserApprove: "Browser vertrauen",
     browserReject: "Browser ablehnen",
     browserDisconnect: "Browser entfernen",
+    browserCapabilities: "Erlaubte Änderungen",
+    browserCapabilityDelete: "In den Papierkorb",
+    browserCapabilityMove: "In Ordner verschieben",
+    browserCapabilityFavorite: "Favoriten setzen",
     browserRequestedAt: "Angefragt: {{value}}",
     browserApprovedAt: "Vertraut seit: {{value}}",
     browserLastSeenAt: "Zuletzt gesehen: {{value}}",
@@ -335,10 +360,60 @@ const de: TranslationSchema = {
     browserPairingPromptDescription:
       "{{client}} möchte ClavisPass verwenden. Vertraue nur Browsern, die du gerade selbst eingerichtet hast.",
     browserPairingPromptLater: "Später",
+    browserDeletePrompt:
+      "{{client}} möchte „{{title}}“ in den Papierkorb verschieben.",
+    browserDeletePromptConfirm: "In den Papierkorb",
+    browserDeletePromptCancel: "Behalten",
+    browserLockedSavePrompt:
+      "{{client}} hat „{{title}}“ gespeichert, während ClavisPass gesperrt war. Jetzt übernehmen?",
+    browserLockedSavePromptConfirm: "Übernehmen",
+    browserLockedSavePromptCancel: "Verwerfen",
     browserUnknownClientShort: "Dieser Browser",
     browserLoading: "Browser-Zugriff wird geladen...",
     browserLoadFailed: "Browser-Zugriff konnte nicht geladen werden.",
     browserActionFailed: "Browser-Zugriff konnte nicht aktualisiert werden.",
+    localApi: "Lokale Automatisierungs-API",
+    localApiDescription:
+      "Skripte auf diesem Computer können mit einem hier ausgestellten Token Einträge per HTTP lesen und anlegen. Jeder Aufruf wird protokolliert.",
+    localApiLoading: "Lokale API wird geladen...",
+    localApiEnabled: "Lokale API aktivieren",
+    localApiListening:
+      "Erreichbar unter {{address}}, solange ClavisPass entsperrt ist.",
+    localApiStopped: "Die lokale API ist ausgeschaltet.",
+    localApiNewToken: "Neues Token",
+    localApiTokenName: "Name des Tokens",
+    localApiTokenFolders: "Ordner (keine Auswahl bedeutet alle Einträge)",
+    localApiTokenWrite: "Anlegen von Einträgen erlauben",
+    localApiIssueToken: "Token ausstellen",
+    localApiTokenIssuedTitle: "Token für {{name}}",
+    localApiTokenIssuedDescription:
+      "Jetzt kopieren. Es wird nicht erneut angezeigt.",
+    localApiTokens: "Tokens",
+    localApiTokensEmpty: "Es wurden keine Tokens ausgestellt.",
+    localApiScopeRead: "Lesen",
+    localApiScopeReadWrite: "Lesen und anlegen",
+    localApiScopeAllFolders: "Alle Ordner",
+    localApiTokenLastUsed: "Zuletzt verwendet: {{value}}",
+    localApiRevokeToken: "Widerrufen",
+    localApiAudit: "Letzte Aufrufe",
+    localApiAuditEmpty: "Bisher gab es keine Aufrufe.",
+    cliAccessPromptTitle: "Kommandozeilen-Zugriff erlauben",
+    cliAccessPromptDescription:
+      "{{process}} (PID {{pid}}) möchte aus ClavisPass lesen, bis der Tresor gesperrt wird. Erlaube nur Befehle, die du gerade selbst gestartet hast.",
+    cliAccessAllow: "Erlauben",
+    cliAccessDeny: "Ablehnen",
+    cliAccessUnknownProcess: "Ein Prozess",
+    cliAccessActionFailed: "Kommandozeilen-Zugriff konnte nicht aktualisiert werden.",
+    sshSignPromptTitle: "SSH-Signatur bestätigen",
+    sshSignPromptDescription:
+      "{{process}} möchte mit dem SSH-Schlüssel von {{key}} signieren.",
+    sshSignAllow: "Signieren",
+    sshSignActionFailed: "Die Signaturanfrage konnte nicht beantwortet werden.",
+    secretService: "Secret Service für Linux-Apps",
+    secretServicePromptTitle: "Zugriff auf Geheimnisse erlauben",
+    secretServicePromptDescription:
+      "{{process}} (PID {{pid}}) möchte über den Secret Service Passwörter lesen. Die Freigabe gilt für jeden Start dieses Programms, bis der Tresor gesperrt wird.",
+    secretServiceActionFailed: "Secret-Service-Zugriff konnte nicht aktualisiert werden.",
     infoSystemAuthTitle: "Systemauthentifizierung",
     infoSystemAuthBody:
       "Nutzt deine Geräteentsperrung, um das aktuelle Master-Passwort nach der Aktivierung wiederherzustellen.",
@@ -353,6 +428,13 @@ const de: TranslationSchema = {
       "Der Schutz hängt davon ab, was die Plattform zulässt.",
     infoContentProtectionBullet2:
       "Es ist eine zusätzliche Datenschutzschicht und ersetzt nicht das Sperren des Vaults.",
+    infoSecretServiceTitle: "Secret Service",
+    infoSecretServiceBody:
+      "Stellt die Logins dieses Vaults Linux-Apps, die libsecret nutzen, etwa NetworkManager oder GNOME-Apps, in einer ClavisPass-Sammlung bereit.",
+    infoSecretServiceBullet1:
+      "Jede App muss nach dem Entsperren einmal erlaubt werden, bevor sie Passwörter lesen kann.",
+    infoSecretServiceBullet2:
+      "Pro Sitzung kann nur ein Secret Service laufen, GNOME Keyring oder KWallet dürfen ihn also nicht bereitstellen.",
     infoBlurOnUnfocusTitle: "Bei Fokusverlust verwischen",
     infoBlurOnUnfocusBody:
       "Verwischt das App-Fenster, wenn es den Fokus verliert, damit sensible Inhalte weniger leicht sichtbar sind.",
@@ -363,6 +445,12 @@ const de: TranslationSchema = {
       "Bei 'Aus' übernimmt das System die Zwischenablage vollständig.",
     infoCopyDurationBullet2:
       "Manche Plattformen oder andere Apps können das Leeren der Zwischenablage einschränken.",
+    infoPasteLimitTitle: "Einfügelimit",
+    infoPasteLimitBody:
+      "Leert die Zwischenablage, nachdem ein kopiertes Geheimnis so oft eingefügt wurde.",
+    infoPasteLimitBullet1: "Verfügbar unter Linux mit X11 oder Wayland.",
+    infoPasteLimitBullet2:
+      "Zwischenablage-Manager, die die Zwischenablage lesen, verbrauchen ebenfalls ein Einfügen.",
     infoAutosaveDelayTitle: "Autospeichern",
     infoAutosaveDelayBody:
       "Legt fest, wie lange ClavisPass nach Änderungen wartet, bevor gespeichert und synchronisiert wird.",
@@ -626,6 +714,9 @@ const de: TranslationSchema = {
     creditCardShowLess: "Weniger anzeigen",
     customField: "Benutzerdefiniert",
     customFieldTitle: "Titel",
+    customFieldName: "Name des Formularfelds",
+    customFieldNameHint:
+      "Name oder ID des Eingabefelds auf der Seite, das dieser Wert ausfüllt, z. B. customerNo. Leer lassen, um nach dem Titel zuzuordnen.",
     customFieldType: "Typ",
     customFieldTypeText: "Text",
     customFieldTypeSecret: "Secret",
@@ -642,6 +733,11 @@ const de: TranslationSchema = {
     email: "E-Mail",
     expiry: "Ablauf",
     key: "Schlüssel",
+    sshKeyGenerate: "{{algorithm}} erzeugen",
+    sshKeyGenerateFailed: "Der SSH-Schlüssel konnte nicht erzeugt werden.",
+    sshKeyComment: "Kommentar (optional)",
+    sshKeyPassphrase: "Passphrase (optional)",
+    sshPublicKey: "Öffentlicher Schlüssel (authorized_keys)",
     note: "Notiz",
     password: "Passwort",
     person: "Person",
@@ -89,12 +89,20 @@ const en: TranslationSchema = {
     editHistoryModulesCleared: "Removed all modules",
     editHistoryFavoriteUpdated: "Updated favorite",
     editHistoryPinUpdated: "Updated pin",
+    editHistoryCrossOriginFillUpdated: "Updated frame filling",
     editHistoryFolderUpdated: "Updated folder",
     editHistoryFolderFavoriteUpdated: "Updated folder and favorite",
     editHistoryUndo: "Undid latest change",
     editHistoryRedo: "Restored change",
     editHistorySaved: "Saved changes",
     editHistoryOpened: "Editor opened",
+    editHistoryPasswordRestored: "Restored an earlier password",
+    passwordHistory: "Password history",
+    passwordHistoryDescription:
+      "Earlier passwords of this entry. Restoring one keeps the current password in the history.",
+    passwordHistoryEmpty: "No earlier passwords yet.",
+    passwordHistoryReplacedBy: "Replaced by {{client}}",
+    passwordHistoryRestore: "Restore password",
     sortCompletedTasksDown: "Move completed tasks down",
     selectFolder: "Select Folder",
     selectFolderDescription: "Choose where this entry should live.",
@@ -106,6 +114,8 @@ const en: TranslationSchema = {
     removeFavorite: "Remove from favorites",
     addPin: "Pin entry",
     removePin: "Unpin entry",
+    allowCrossOriginFill: "Allow filling into embedded frames",
+    blockCrossOriginFill: "Block filling into embedded frames",
     moveToFolder: "Move to folder",
     tags: "Tags",
     manageTags: "Manage tags",
@@ -249,6 +259,13 @@ const en: TranslationSchema = {
     fastAccessPositionBottomRight: "Bottom right",
     importBackup: "Import",
     exportBackup: "Export",
+    trash: "Trash",
+    trashDescription:
+      "Entries a browser extension moved to the trash stay here until you restore or delete them.",
+    trashEmpty: "The trash is empty.",
+    trashDeletedAt: "Moved to trash: {{value}}",
+    trashRestore: "Restore",
+    trashDeleteForever: "Delete",
     importData: "Import data",
     importDataHint:
       "Import passwords and entries from other password managers or browser exports.",
@@ -267,6 +284,10 @@ const en: TranslationSchema = {
     seconds: "{{count}} seconds",
     copyDuration: "Copy Duration",
     copyDurationOff: "Off",
+    pasteLimit: "Paste limit",
+    pasteLimitOff: "Unlimited",
+    pasteLimitOnce: "Paste once",
+    pasteLimitTimes: "{{count}} pastes",
     autosaveDelay: "Autosave",
     autosaveOff: "Off",
     sessionDuration: "Lock after inactivity",
@@ -314,6 +335,10 @@ const en: TranslationSchema = {
     browserApprove: "Trust browser",
     browserReject: "Reject browser",
     browserDisconnect: "Remove browser",
+    browserCapabilities: "Allowed changes",
+    browserCapabilityDelete: "Move to trash",
+    browserCapabilityMove: "Move to folder",
+    browserCapabilityFavorite: "Mark favorites",
     browserRequestedAt: "Requested: {{value}}",
     browserApprovedAt: "Trusted: {{value}}",
     browserLastSeenAt: "Last seen: {{value}}",
@@ -332,10 +357,57 @@ const en: TranslationSchema = {
     browserPairingPromptDescription:
       "{{client}} wants to use ClavisPass. Only trust browsers you are setting up right now.",
     browserPairingPromptLater: "Later",
+    browserDeletePrompt: '{{client}} wants to move "{{title}}" to the trash.',
+    browserDeletePromptConfirm: "Move to trash",
+    browserDeletePromptCancel: "Keep",
+    browserLockedSavePrompt:
+      '{{client}} saved "{{title}}" while ClavisPass was locked. Apply it now?',
+    browserLockedSavePromptConfirm: "Apply",
+    browserLockedSavePromptCancel: "Discard",
     browserUnknownClientShort: "This browser",
     browserLoading: "Loading browser access...",
     browserLoadFailed: "Browser access could not be loaded.",
     browserActionFailed: "Browser access could not be updated.",
+    localApi: "Local automation API",
+    localApiDescription:
+      "Scripts on this computer can read and create entries over HTTP with a token you issue here. Every call is logged.",
+    localApiLoading: "Loading local API...",
+    localApiEnabled: "Enable local API",
+    localApiListening: "Listening on {{address}} while ClavisPass is unlocked.",
+    localApiStopped: "The local API is off.",
+    localApiNewToken: "New token",
+    localApiTokenName: "Token name",
+    localApiTokenFolders: "Folders (none selected means all entries)",
+    localApiTokenWrite: "Allow creating entries",
+    localApiIssueToken: "Issue token",
+    localApiTokenIssuedTitle: "Token for {{name}}",
+    localApiTokenIssuedDescription: "Copy it now. It will not be shown again.",
+    localApiTokens: "Tokens",
+    localApiTokensEmpty: "No tokens have been issued.",
+    localApiScopeRead: "Read",
+    localApiScopeReadWrite: "Read and create",
+    localApiScopeAllFolders: "All folders",
+    localApiTokenLastUsed: "Last used: {{value}}",
+    localApiRevokeToken: "Revoke",
+    localApiAudit: "Recent calls",
+    localApiAuditEmpty: "No calls have been made yet.",
+    cliAccessPromptTitle: "Allow command-line access",
+    cliAccessPromptDescription:
+      "{{process}} (PID {{pid}}) wants to read from ClavisPass until the vault locks. Only allow commands you just started.",
+    cliAccessAllow: "Allow",
+    cliAccessDeny: "Deny",
+    cliAccessUnknownProcess: "A process",
+    cliAccessActionFailed: "Command-line access could not be updated.",
+    sshSignPromptTitle: "Confirm SSH signature",
+    sshSignPromptDescription:
+      "{{process}} wants to sign with the SSH key of {{key}}.",
+    sshSignAllow: "Sign",
+    sshSignActionFailed: "The signature request could not be answered.",
+    secretService: "Secret Service for Linux apps",
+    secretServicePromptTitle: "Allow access to secrets",
+    secretServicePromptDescription:
+      "{{process}} (PID {{pid}}) wants to read passwords through the Secret Service. Allowing it covers every run of this program until the vault locks.",
+    secretServiceActionFailed: "Secret Service access could not be updated.",
     infoSystemAuthTitle: "System Authentication",
     infoSystemAuthBody:
       "Uses your device unlock method to restore the current master password after you have enabled it.",
@@ -350,6 +422,13 @@ const en: TranslationSchema = {
       "Protection depends on what the platform allows.",
     infoContentProtectionBullet2:
       "It is an extra privacy layer, not a replacement for locking the vault.",
+    infoSecretServiceTitle: "Secret Service",
+    infoSecretServiceBody:
+      "Offers the logins of this vault to Linux apps that use libsecret, such as NetworkManager or GNOME apps, in a ClavisPass collection.",
+    infoSecretServiceBullet1:
+      "Each app has to be allowed once per unlock before it can read passwords.",
+    infoSecretServiceBullet2:
+      "Only one Secret Service can run per session, so GNOME Keyring or KWallet must not provide it.",
     infoBlurOnUnfocusTitle: "Blur when unfocused",
     infoBlurOnUnfocusBody:
       "Blurs the app window when it loses focus so nearby people are less likely to see sensitive content.",
@@ -360,6 +439,12 @@ const en: TranslationSchema = {
       "Setting this to off leaves clipboard handling to the system.",
     infoCopyDurationBullet2:
       "Some platforms or other apps may restrict clipboard clearing.",
+    infoPasteLimitTitle: "Paste limit",
+    infoPasteLimitBody:
+      "Empties the clipboard after a copied secret was pasted this many times.",
+    infoPasteLimitBullet1: "Available on Linux with X11 or Wayland.",
+    infoPasteLimitBullet2:
+      "Clipboard managers that read the clipboard also use up a paste.",
     infoAutosaveDelayTitle: "Autosave",
     infoAutosaveDelayBody:
       "Controls how long ClavisPass waits after changes before saving and syncing them.",
@@ -617,6 +702,9 @@ const en: TranslationSchema = {
     creditCardShowLess: "Show less",
     customField: "Custom Field",
     customFieldTitle: "Title",
+    customFieldName: "Form field name",
+    customFieldNameHint:
+      "The name or id of the input on the page this value fills, e.g. customerNo. Leave empty to match by title.",
     customFieldType: "Type",
     customFieldTypeText: "Text",
     customFieldTypeSecret: "Secret",
@@ -633,6 +721,11 @@ const en: TranslationSchema = {
     email: "E-Mail",
     expiry: "Expiry",
     key: "Key",
+    sshKeyGenerate: "Generate {{algorithm}}",
+    sshKeyGenerateFailed: "The SSH key could not be generated.",
+    sshKeyComment: "Comment (optional)",
+    sshKeyPassphrase: "Passphrase (optional)",
+    sshPublicKey: "Public key (authorized_keys)",
     note: "Note",
     password: "Password",
     person: "Person",